@@ -1,12 +1,18 @@
 use crate::core::{
-    alerts::engine::AlertEngine,
-    config::{ConfigManager, Settings},
-    coordinator, discovery, log_io, replay_engine,
+    alerts::{self, engine::AlertEngine, model::AlertRuleId},
+    analysis,
+    config::{self, ConfigManager, Settings},
+    coordinator, discovery,
+    log_dir_watch::LogDirWatch,
+    log_io,
+    log_ring::{LogLine, LogRing},
+    model::{self, format_log_timestamp, DpsSample, RunSummary, SurvivabilityScore, TzMode},
+    replay_engine,
     state::EngineState,
 };
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
 use tauri::{Emitter, Manager, State, WebviewUrl, WebviewWindowBuilder};
@@ -17,6 +23,15 @@ static REPLAY_SESSION_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 enum LoopCommand {
     Replay,
+    SetPaused(bool),
+    /// Drive the live overlay from a log file instead of tracked logs; `None` stops it
+    Simulate(Option<replay_engine::LiveSimulation>),
+    /// Suppress all alerts for this long; zero unmutes
+    MuteAlerts(Duration),
+    /// Count outgoing DPS only against this target; `None` counts all targets
+    FocusTarget(Option<String>),
+    /// Stop the loop after flushing its writers; the sender is signalled once done
+    Shutdown(std::sync::mpsc::Sender<()>),
 }
 
 struct ReplaySession {
@@ -32,6 +47,44 @@ struct AppState {
     loop_tx: mpsc::Sender<LoopCommand>,
     audio_tx: std::sync::mpsc::Sender<AudioCommand>,
     replay: Arc<RwLock<Option<ReplaySession>>>,
+    /// Most recent live DPS sample, for on-demand summaries
+    latest_sample: Mutex<Option<DpsSample>>,
+    /// Run state per tracked gamelog, refreshed every live tick
+    run_statuses: Mutex<HashMap<PathBuf, coordinator::RunStatus>>,
+    /// Status of every tracked character, refreshed every live tick
+    fleet_status: Mutex<Vec<coordinator::CharacterStatus>>,
+    /// Summaries of runs closed this session, oldest first
+    recent_runs: Mutex<Vec<RunSummary>>,
+    /// Recent backend diagnostics for the debug panel
+    backend_logs: LogRing,
+    /// `auto_track_latest` is applied once, on the first character scan
+    auto_track_pending: AtomicBool,
+}
+
+/// How many closed runs `recent_runs` keeps
+const MAX_RECENT_RUNS: usize = 50;
+
+/// How long exit waits for the background loop to finish flushing
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Stop the background loop and write settings to disk before the process exits.
+fn shutdown(app: &tauri::AppHandle) {
+    let state = app.state::<AppState>();
+    let saved = coordinator::shutdown_and_save(
+        |done| {
+            state
+                .loop_tx
+                .blocking_send(LoopCommand::Shutdown(done))
+                .is_ok()
+        },
+        SHUTDOWN_TIMEOUT,
+        &state.settings,
+        &state.config_manager,
+    );
+    match saved {
+        Ok(()) => println!("Settings saved on exit"),
+        Err(e) => println!("Failed to save settings on exit: {}", e),
+    }
 }
 
 #[tauri::command]
@@ -54,10 +107,13 @@ async fn get_logs_by_character(
     path: Option<PathBuf>,
     state: State<'_, AppState>,
 ) -> Result<HashMap<String, Vec<log_io::CharacterLog>>, String> {
-    let target_dir = path.unwrap_or_else(|| state.settings.lock().unwrap().gamelog_dir.clone());
+    let target_dirs = match path {
+        Some(dir) => vec![dir],
+        None => state.settings.lock().unwrap().gamelog_dirs.clone(),
+    };
 
-    println!("Scanning logs in {:?}", target_dir);
-    let logs = log_io::scan_all_logs(&target_dir).map_err(|e| e.to_string())?;
+    println!("Scanning logs in {:?}", target_dirs);
+    let logs = log_io::scan_all_logs_in(&target_dirs).map_err(|e| e.to_string())?;
     let groups = log_io::group_logs_by_character(logs);
     println!("Found {} characters with logs.", groups.len());
     Ok(groups)
@@ -76,7 +132,8 @@ async fn start_replay(
     app: tauri::AppHandle,
 ) -> Result<ReplaySessionInfo, String> {
     println!("Starting replay with {} logs...", logs.len());
-    let controller = replay_engine::ReplayController::new(logs)
+    let locale = state.settings.lock().unwrap().locale.clone();
+    let controller = replay_engine::ReplayController::new_localized(logs, locale)
         .ok_or("Failed to initialize replay controller")?;
     let duration = controller.session_duration().as_secs();
     let start_time = controller.start_time().as_secs();
@@ -96,9 +153,56 @@ async fn start_replay(
         *replay = Some(session);
     }
 
-    let handle = app.clone();
-    let replay_state = state.replay.clone();
+    spawn_replay_loop(session_id, state.replay.clone(), app);
+
+    Ok(ReplaySessionInfo {
+        duration,
+        start_time,
+    })
+}
+
+/// Swap the active replay's logs for `logs`, keeping its play/pause state and
+/// speed. The engine starts fresh and the old loop exits on the id change.
+#[tauri::command]
+async fn replace_replay_logs(
+    logs: Vec<(String, PathBuf)>,
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<ReplaySessionInfo, String> {
+    println!("Replacing replay logs with {} logs...", logs.len());
+    let locale = state.settings.lock().unwrap().locale.clone();
+    let mut controller = replay_engine::ReplayController::new_localized(logs, locale)
+        .ok_or("Failed to initialize replay controller")?;
+    let duration = controller.session_duration().as_secs();
+    let start_time = controller.start_time().as_secs();
+
+    let session_id = REPLAY_SESSION_COUNTER.fetch_add(1, Ordering::SeqCst) + 1;
+
+    {
+        let mut replay = state.replay.write().unwrap();
+        let previous = replay.as_ref().ok_or("No active replay session")?;
+        controller.resume_from(&previous.controller);
+        *replay = Some(ReplaySession {
+            controller,
+            engine: EngineState::new(),
+            id: session_id,
+        });
+    }
+
+    spawn_replay_loop(session_id, state.replay.clone(), app);
+
+    Ok(ReplaySessionInfo {
+        duration,
+        start_time,
+    })
+}
 
+/// Drive replay session `session_id` until it is stopped or replaced.
+fn spawn_replay_loop(
+    session_id: u64,
+    replay_state: Arc<RwLock<Option<ReplaySession>>>,
+    handle: tauri::AppHandle,
+) {
     tauri::async_runtime::spawn(async move {
         println!("Replay loop {} started.", session_id);
         loop {
@@ -114,16 +218,25 @@ async fn start_replay(
                 }
             }
 
-            let (events, lines, current_sim_time, progress) = {
+            let (events, lines, bookmarks, current_sim_time, progress) = {
                 let mut replay_lock = replay_state.write().unwrap();
                 if let Some(session) = replay_lock.as_mut() {
-                    let (events, lines) = session.controller.tick();
-                    for event in &events {
+                    let (events, lines, bookmarks) = session.controller.tick();
+                    if session.controller.take_loop_wrapped() {
+                        session.engine = EngineState::new();
+                    }
+                    // Start each run with a fresh engine so earlier runs don't bleed in
+                    let run_start = session.controller.take_run_boundary();
+                    if run_start.is_some() {
+                        session.engine = EngineState::new();
+                    }
+                    for event in &events[run_start.unwrap_or(0)..] {
                         session.engine.push_event(event.clone());
                     }
                     (
                         events,
                         lines,
+                        bookmarks,
                         session.controller.current_sim_time(),
                         session.controller.relative_progress(),
                     )
@@ -154,6 +267,10 @@ async fn start_replay(
                         let _ = handle.emit("replay-raw-lines", lines);
                     }
 
+                    if !bookmarks.is_empty() {
+                        let _ = handle.emit("replay-bookmarks", bookmarks);
+                    }
+
                     let status = serde_json::json!({
                         "current_time": current_sim_time.as_secs(),
                         "progress": progress.as_secs(),
@@ -165,11 +282,6 @@ async fn start_replay(
             tokio::time::sleep(Duration::from_millis(100)).await;
         }
     });
-
-    Ok(ReplaySessionInfo {
-        duration,
-        start_time,
-    })
 }
 
 #[tauri::command]
@@ -186,6 +298,37 @@ fn seek_replay(offset_secs: u64, state: State<'_, AppState>) -> Result<(), Strin
     Ok(())
 }
 
+/// DPS sample at `offset_secs` into the replay, for the scrubber preview.
+#[tauri::command]
+fn replay_snapshot_at(
+    offset_secs: u64,
+    state: State<'_, AppState>,
+) -> Result<Option<DpsSample>, String> {
+    let window = Duration::from_secs(state.settings.lock().unwrap().dps_window_seconds);
+    let replay = state.replay.read().unwrap();
+    let session = replay.as_ref().ok_or("No active replay session")?;
+    Ok(session
+        .controller
+        .snapshot_at(Duration::from_secs(offset_secs), window))
+}
+
+#[tauri::command]
+fn seek_replay_run(forward: bool, state: State<'_, AppState>) -> Result<u64, String> {
+    let mut replay = state.replay.write().unwrap();
+    let Some(session) = replay.as_mut() else {
+        return Ok(0);
+    };
+    let offset = if forward {
+        session.controller.seek_next_run()
+    } else {
+        session.controller.seek_prev_run()
+    }
+    .map_err(|e| e.to_string())?;
+    session.engine = EngineState::new();
+    println!("Seeked replay to run at {}s", offset.as_secs());
+    Ok(offset.as_secs())
+}
+
 #[tauri::command]
 fn toggle_replay_pause(state: State<'_, AppState>) {
     let mut replay = state.replay.write().unwrap();
@@ -210,8 +353,15 @@ fn step_replay(state: State<'_, AppState>, app: tauri::AppHandle) -> Result<(),
         session.controller.step(Duration::from_secs(1));
 
         // Process any events in that step
-        let (events, lines) = session.controller.tick();
-        for event in &events {
+        let (events, lines, bookmarks) = session.controller.tick();
+        if session.controller.take_loop_wrapped() {
+            session.engine = EngineState::new();
+        }
+        let run_start = session.controller.take_run_boundary();
+        if run_start.is_some() {
+            session.engine = EngineState::new();
+        }
+        for event in &events[run_start.unwrap_or(0)..] {
             session.engine.push_event(event.clone());
         }
 
@@ -227,6 +377,9 @@ fn step_replay(state: State<'_, AppState>, app: tauri::AppHandle) -> Result<(),
         if !lines.is_empty() {
             let _ = app.emit("replay-raw-lines", lines);
         }
+        if !bookmarks.is_empty() {
+            let _ = app.emit("replay-bookmarks", bookmarks);
+        }
         let status = serde_json::json!({
             "current_time": sim_time.as_secs(),
             "progress": progress.as_secs(),
@@ -244,6 +397,43 @@ fn set_replay_speed(speed: f64, state: State<'_, AppState>) {
     }
 }
 
+/// Quiet period (seconds) that splits replayed runs; `None` splits only at RUN_START bookmarks.
+#[tauri::command]
+fn set_replay_run_gap(seconds: Option<u64>, state: State<'_, AppState>) {
+    let mut replay = state.replay.write().unwrap();
+    if let Some(session) = replay.as_mut() {
+        session
+            .controller
+            .set_run_gap(seconds.map(Duration::from_secs));
+    }
+}
+
+#[tauri::command]
+fn set_replay_loop(
+    start_secs: Option<u64>,
+    end_secs: Option<u64>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut replay = state.replay.write().unwrap();
+    if let Some(session) = replay.as_mut() {
+        let range = start_secs
+            .zip(end_secs)
+            .map(|(start, end)| (Duration::from_secs(start), Duration::from_secs(end)));
+        session.controller.set_loop(range);
+        println!("Replay loop set to {:?}", session.controller.loop_range());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn set_replay_source_offset(character: String, offset_ms: i64, state: State<'_, AppState>) {
+    let mut replay = state.replay.write().unwrap();
+    if let Some(session) = replay.as_mut() {
+        session.controller.set_source_offset(&character, offset_ms);
+        println!("Replay offset for {} set to {}ms", character, offset_ms);
+    }
+}
+
 #[tauri::command]
 fn stop_replay(state: State<'_, AppState>) {
     println!("Stopping active replay session...");
@@ -263,14 +453,77 @@ async fn replay_logs(state: State<'_, AppState>) -> Result<(), String> {
         .map_err(|e| e.to_string())
 }
 
+/// Freeze (or unfreeze) the live overlay numbers without resetting the session.
+#[tauri::command]
+async fn set_tracking_paused(paused: bool, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .loop_tx
+        .send(LoopCommand::SetPaused(paused))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Show outgoing DPS against a single target only; `None` shows all targets again.
+#[tauri::command]
+async fn set_focus_target(
+    target: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .loop_tx
+        .send(LoopCommand::FocusTarget(target))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Play a gamelog through the live overlay at `speed`x, for demos and UI testing.
+#[tauri::command]
+async fn simulate_live(
+    path: PathBuf,
+    character: String,
+    speed: f64,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let locale = state.settings.lock().unwrap().locale.clone();
+    let simulation = replay_engine::LiveSimulation::new(character, path, speed, locale)
+        .ok_or("Failed to open log for simulation")?;
+    state
+        .loop_tx
+        .send(LoopCommand::Simulate(Some(simulation)))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn stop_simulation(state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .loop_tx
+        .send(LoopCommand::Simulate(None))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Silence all alerts for `seconds` (0 unmutes) without changing the alert config.
+#[tauri::command]
+async fn mute_alerts(seconds: u64, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .loop_tx
+        .send(LoopCommand::MuteAlerts(Duration::from_secs(seconds)))
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn get_settings(state: State<'_, AppState>) -> Settings {
     state.settings.lock().unwrap().clone()
 }
 
 #[tauri::command]
-fn save_settings(settings: Settings, state: State<'_, AppState>) -> Result<(), String> {
+fn save_settings(settings: serde_json::Value, state: State<'_, AppState>) -> Result<(), String> {
     let mut current = state.settings.lock().unwrap();
+    let mut settings = current.merged_with(settings)?;
+    settings.validate()?;
+    settings.normalize_gamelog_dirs();
     *current = settings.clone();
     state
         .config_manager
@@ -278,6 +531,85 @@ fn save_settings(settings: Settings, state: State<'_, AppState>) -> Result<(), S
         .map_err(|e| e.to_string())
 }
 
+/// Report how much of a gamelog the parser understands, for bug reports.
+#[tauri::command]
+async fn diagnose_log(
+    path: PathBuf,
+    state: State<'_, AppState>,
+) -> Result<log_io::LogDiagnostics, String> {
+    let locale = state.settings.lock().unwrap().locale.clone();
+    tauri::async_runtime::spawn_blocking(move || log_io::diagnose(&path, &locale))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Report `character`'s stats under `pilot`, or under its own name again when
+/// `pilot` is `None`. Persisted; the live loop picks it up on the next tick.
+#[tauri::command]
+fn set_character_alias(
+    character: String,
+    pilot: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut settings = state.settings.lock().unwrap();
+    match pilot.filter(|pilot| *pilot != character) {
+        Some(pilot) => settings.character_aliases.insert(character, pilot),
+        None => settings.character_aliases.remove(&character),
+    };
+    state
+        .config_manager
+        .save(&settings)
+        .map_err(|e| e.to_string())
+}
+
+/// Step the DPS window to the next configured preset and persist it.
+/// Returns the new window length in seconds.
+#[tauri::command]
+fn cycle_dps_window(state: State<'_, AppState>) -> Result<u64, String> {
+    let mut settings = state.settings.lock().unwrap();
+    let window = settings.cycle_dps_window();
+    state
+        .config_manager
+        .save(&settings)
+        .map_err(|e| e.to_string())?;
+    Ok(window)
+}
+
+/// Move the DPS window one preset up or down and persist it.
+/// Returns the new window length in seconds.
+#[tauri::command]
+fn step_dps_window(forward: bool, state: State<'_, AppState>) -> Result<u64, String> {
+    let mut settings = state.settings.lock().unwrap();
+    let window = settings.step_dps_window(forward);
+    state
+        .config_manager
+        .save(&settings)
+        .map_err(|e| e.to_string())?;
+    Ok(window)
+}
+
+/// Lock or unlock the overlay in place. Returns the new state.
+#[tauri::command]
+fn toggle_overlay_lock(state: State<'_, AppState>) -> Result<bool, String> {
+    let mut settings = state.settings.lock().unwrap();
+    settings.locked = !settings.locked;
+    state
+        .config_manager
+        .save(&settings)
+        .map_err(|e| e.to_string())?;
+    Ok(settings.locked)
+}
+
+/// Move the window with the mouse, unless the overlay is locked.
+#[tauri::command]
+fn start_window_drag(window: tauri::Window, state: State<'_, AppState>) -> Result<(), String> {
+    if !config::should_handle_drag(state.settings.lock().unwrap().locked) {
+        return Ok(());
+    }
+    window.start_dragging().map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn pick_gamelog_dir(app: tauri::AppHandle) -> Result<Option<PathBuf>, String> {
     // Run blocking dialog on a separate thread to avoid freezing the UI
@@ -303,19 +635,26 @@ struct CharacterUIState {
 async fn get_available_characters(
     state: State<'_, AppState>,
 ) -> Result<Vec<CharacterUIState>, String> {
-    let gamelog_dir = {
+    let gamelog_dirs = {
         let settings = state.settings.lock().unwrap();
-        settings.gamelog_dir.clone()
+        settings.gamelog_dirs.clone()
     };
 
     // Run blocking file I/O on a separate thread
     let logs = tauri::async_runtime::spawn_blocking(move || {
-        log_io::scan_gamelogs_dir(&gamelog_dir).unwrap_or_default()
+        log_io::scan_gamelogs_dirs(&gamelog_dirs).unwrap_or_default()
     })
     .await
     .map_err(|e| e.to_string())?;
 
-    let tracked = state.tracked_paths.lock().unwrap();
+    let mut tracked = state.tracked_paths.lock().unwrap();
+    if state.auto_track_pending.swap(false, Ordering::SeqCst) {
+        if let Some(path) = log_io::auto_track_latest(&logs, &mut tracked) {
+            state
+                .backend_logs
+                .log(format!("Auto-tracking most recent log: {:?}", path));
+        }
+    }
 
     Ok(logs
         .into_iter()
@@ -364,18 +703,95 @@ struct SimpleRoomResponse {
 #[derive(serde::Serialize)]
 struct SimpleBookmarkResponse {
     timestamp_secs: u64,
+    /// `timestamp_secs` formatted in the configured display time zone
+    display_time: String,
     bookmark_type: String,
     label: Option<String>,
 }
 
+/// Paste-ready fleet summary built from the latest live DPS sample.
+/// The frontend puts the returned text on the clipboard.
+#[tauri::command]
+async fn copy_fleet_summary(state: State<'_, AppState>) -> Result<String, String> {
+    let style = state.settings.lock().unwrap().number_format;
+    let latest = state.latest_sample.lock().unwrap();
+    let sample = latest.as_ref().ok_or("No combat data yet")?;
+    Ok(analysis::fleet_summary(sample, style))
+}
+
+/// Markdown tables for the last closed run (of `character`, if given).
+/// The frontend puts the returned text on the clipboard.
+#[tauri::command]
+async fn copy_run_markdown(
+    character: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let recent = state.recent_runs.lock().unwrap();
+    let summary = recent
+        .iter()
+        .rev()
+        .find(|run| character.as_ref().is_none_or(|name| &run.character == name))
+        .ok_or("No finished runs yet")?;
+    Ok(analysis::run_summary_markdown(summary))
+}
+
+/// Chart colors (RGBA) for the given characters, defaulting unset ones.
+#[tauri::command]
+fn get_character_colors(
+    names: Vec<String>,
+    state: State<'_, AppState>,
+) -> HashMap<String, [u8; 4]> {
+    let settings = state.settings.lock().unwrap();
+    names
+        .into_iter()
+        .map(|name| {
+            let color = settings.color_for(&name);
+            (name, color)
+        })
+        .collect()
+}
+
+/// Whether the character tracked via `gamelog_path` is in an Abyss run, and for how long.
+/// `None` when the log isn't tracked or has no Local chat log.
+#[tauri::command]
+fn get_run_status(
+    gamelog_path: PathBuf,
+    state: State<'_, AppState>,
+) -> Option<coordinator::RunStatus> {
+    state
+        .run_statuses
+        .lock()
+        .unwrap()
+        .get(&gamelog_path)
+        .cloned()
+}
+
+/// Current DPS, Abyss state and last activity of every tracked character,
+/// for the fleet roster.
+#[tauri::command]
+fn get_fleet_status(state: State<'_, AppState>) -> Vec<coordinator::CharacterStatus> {
+    state.fleet_status.lock().unwrap().clone()
+}
+
+/// Dealt-vs-received score of the latest live DPS sample.
+#[tauri::command]
+async fn get_room_score(state: State<'_, AppState>) -> Result<SurvivabilityScore, String> {
+    let latest = state.latest_sample.lock().unwrap();
+    let sample = latest.as_ref().ok_or("No combat data yet")?;
+    Ok(analysis::room_score(sample))
+}
+
 #[tauri::command]
 async fn create_highlight_bookmark(
     gamelog_path: PathBuf,
     label: Option<String>,
+    state: State<'_, AppState>,
 ) -> Result<(), String> {
     use crate::core::inline_bookmarks;
     inline_bookmarks::add_highlight(&gamelog_path, label.as_deref()).map_err(|e| e.to_string())?;
-    println!("Added HIGHLIGHT bookmark to {:?}", gamelog_path);
+    state
+        .backend_logs
+        .log(format!("Added HIGHLIGHT bookmark to {:?}", gamelog_path));
     Ok(())
 }
 
@@ -383,26 +799,69 @@ async fn create_highlight_bookmark(
 async fn toggle_room_marker(
     gamelog_path: PathBuf,
     currently_in_room: bool,
+    state: State<'_, AppState>,
 ) -> Result<SimpleRoomResponse, String> {
     use crate::core::inline_bookmarks;
 
     if currently_in_room {
         // End room
         inline_bookmarks::add_room_end(&gamelog_path).map_err(|e| e.to_string())?;
-        println!("Added ROOM_END to {:?}", gamelog_path);
+        state
+            .backend_logs
+            .log(format!("Added ROOM_END to {:?}", gamelog_path));
         Ok(SimpleRoomResponse { room_open: false })
     } else {
         // Start room
         inline_bookmarks::add_room_start(&gamelog_path).map_err(|e| e.to_string())?;
-        println!("Added ROOM_START to {:?}", gamelog_path);
+        state
+            .backend_logs
+            .log(format!("Added ROOM_START to {:?}", gamelog_path));
         Ok(SimpleRoomResponse { room_open: true })
     }
 }
 
+#[tauri::command]
+async fn delete_bookmark(
+    gamelog_path: PathBuf,
+    timestamp_secs: u64,
+    bookmark_type: String,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    use crate::core::inline_bookmarks::{self, BookmarkType};
+    let kind = BookmarkType::from_str(&bookmark_type)
+        .ok_or_else(|| format!("Unknown bookmark type: {}", bookmark_type))?;
+    let removed = inline_bookmarks::remove_bookmark(&gamelog_path, timestamp_secs, kind)
+        .map_err(|e| e.to_string())?;
+    if removed {
+        state.backend_logs.log(format!(
+            "Removed {} bookmark from {:?}",
+            bookmark_type, gamelog_path
+        ));
+    }
+    Ok(removed)
+}
+
+#[tauri::command]
+async fn set_bookmark_label(
+    gamelog_path: PathBuf,
+    timestamp_secs: u64,
+    bookmark_type: String,
+    label: Option<String>,
+) -> Result<bool, String> {
+    use crate::core::inline_bookmarks::{self, BookmarkType};
+    let kind = BookmarkType::from_str(&bookmark_type)
+        .ok_or_else(|| format!("Unknown bookmark type: {}", bookmark_type))?;
+    inline_bookmarks::set_bookmark_label(&gamelog_path, timestamp_secs, kind, label.as_deref())
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn get_session_bookmarks(
     gamelog_path: PathBuf,
+    state: State<'_, AppState>,
 ) -> Result<Vec<SimpleBookmarkResponse>, String> {
+    let tz = state.settings.lock().unwrap().display_timezone;
+
     // Read gamelog and parse bookmark lines
     use std::fs;
     use std::io::{BufRead, BufReader};
@@ -414,7 +873,7 @@ async fn get_session_bookmarks(
 
     for line in reader.lines() {
         let line = line.map_err(|e| e.to_string())?;
-        if let Some(bm) = parse_bookmark_line(&line) {
+        if let Some(bm) = parse_bookmark_line(&line, tz) {
             bookmarks.push(bm);
         }
     }
@@ -422,42 +881,160 @@ async fn get_session_bookmarks(
     Ok(bookmarks)
 }
 
-/// Parse a bookmark line like: [ 2026.01.04 03:56:49 ] (bookmark) TYPE: label
-fn parse_bookmark_line(line: &str) -> Option<SimpleBookmarkResponse> {
-    if !line.contains("(bookmark)") {
-        return None;
-    }
+/// A run recorded in a gamelog's bookmarks, for the run history view
+#[derive(serde::Serialize)]
+struct RunSummaryDto {
+    gamelog_path: PathBuf,
+    #[serde(flatten)]
+    run: crate::core::inline_bookmarks::StoredRun,
+    duration_secs: Option<u64>,
+    /// `run.start_secs` formatted in the configured display time zone
+    display_time: String,
+}
 
-    // Extract timestamp from [ YYYY.MM.DD HH:MM:SS ]
-    let timestamp_start = line.find('[')? + 1;
-    let timestamp_end = line.find(']')?;
-    let timestamp_str = line[timestamp_start..timestamp_end].trim();
-
-    // Parse to epoch seconds
-    use chrono::NaiveDateTime;
-    let naive = NaiveDateTime::parse_from_str(timestamp_str, "%Y.%m.%d %H:%M:%S").ok()?;
-    let timestamp_secs = naive.and_utc().timestamp() as u64;
-
-    // Extract type and optional label after (bookmark)
-    let after_bookmark = line.split("(bookmark)").nth(1)?.trim();
-    let (bookmark_type, label) = if let Some(colon_pos) = after_bookmark.find(':') {
-        let btype = after_bookmark[..colon_pos].trim().to_string();
-        let lbl = after_bookmark[colon_pos + 1..].trim().to_string();
-        (btype, Some(lbl))
-    } else {
-        (after_bookmark.to_string(), None)
+/// Runs recorded in all of a character's gamelogs, newest first.
+/// Only bookmark lines are read; the logs' combat isn't parsed.
+#[tauri::command]
+async fn list_runs(
+    character: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<RunSummaryDto>, String> {
+    use crate::core::inline_bookmarks;
+    let (gamelog_dirs, tz) = {
+        let settings = state.settings.lock().unwrap();
+        (settings.gamelog_dirs.clone(), settings.display_timezone)
     };
 
+    tauri::async_runtime::spawn_blocking(move || {
+        let logs = log_io::scan_all_logs_in(&gamelog_dirs).map_err(|e| e.to_string())?;
+        let mut runs = Vec::new();
+        for log in logs.into_iter().filter(|log| log.character == character) {
+            let Ok(bookmarks) = inline_bookmarks::read_bookmarks(&log.path) else {
+                continue;
+            };
+            for run in inline_bookmarks::runs_from_bookmarks(&bookmarks) {
+                runs.push(RunSummaryDto {
+                    gamelog_path: log.path.clone(),
+                    duration_secs: run.duration_secs(),
+                    display_time: format_log_timestamp(Duration::from_secs(run.start_secs), tz),
+                    run,
+                });
+            }
+        }
+        runs.sort_by(|a, b| b.run.start_secs.cmp(&a.run.start_secs));
+        Ok(runs)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Bookmarks placed during one run of a gamelog (see `list_runs`)
+#[tauri::command]
+async fn load_run(
+    gamelog_path: PathBuf,
+    index: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<SimpleBookmarkResponse>, String> {
+    use crate::core::inline_bookmarks;
+    let tz = state.settings.lock().unwrap().display_timezone;
+    let bookmarks = inline_bookmarks::read_bookmarks(&gamelog_path).map_err(|e| e.to_string())?;
+    let run = inline_bookmarks::runs_from_bookmarks(&bookmarks)
+        .into_iter()
+        .nth(index)
+        .ok_or("No such run in this gamelog")?;
+
+    Ok(bookmarks
+        .into_iter()
+        .filter(|bm| {
+            bm.timestamp_secs >= run.start_secs
+                && run.end_secs.is_none_or(|end| bm.timestamp_secs <= end)
+        })
+        .map(|bm| SimpleBookmarkResponse {
+            timestamp_secs: bm.timestamp_secs,
+            display_time: format_log_timestamp(Duration::from_secs(bm.timestamp_secs), tz),
+            bookmark_type: bm.bookmark_type.as_str().to_string(),
+            label: bm.label,
+        })
+        .collect())
+}
+
+/// One run in a gamelog, as listed by `list_runs`
+#[derive(serde::Deserialize)]
+struct RunRef {
+    gamelog_path: PathBuf,
+    index: usize,
+}
+
+/// Combined stats across the selected runs, for comparing a night of runs
+#[tauri::command]
+async fn aggregate_runs(
+    runs: Vec<RunRef>,
+    state: State<'_, AppState>,
+) -> Result<crate::core::inline_bookmarks::MultiRunStats, String> {
+    use crate::core::{inline_bookmarks, parser::LineParser};
+
+    let locale = state.settings.lock().unwrap().locale.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut by_log: HashMap<PathBuf, Vec<usize>> = HashMap::new();
+        for run in runs {
+            by_log.entry(run.gamelog_path).or_default().push(run.index);
+        }
+
+        let mut selected = Vec::new();
+        let mut events_by_run = Vec::new();
+        for (path, indices) in by_log {
+            let bookmarks = inline_bookmarks::read_bookmarks(&path).map_err(|e| e.to_string())?;
+            let log_runs = inline_bookmarks::runs_from_bookmarks(&bookmarks);
+
+            let mut parser = LineParser::new().with_locale(locale.clone());
+            let events: Vec<_> = log_io::read_full_lines(&path)
+                .map_err(|e| e.to_string())?
+                .iter()
+                .filter_map(|line| parser.parse_line(line, ""))
+                .collect();
+            let base_time = parser
+                .get_base_time()
+                .ok_or_else(|| format!("No session start in {:?}", path))?;
+
+            for index in indices {
+                let run = log_runs
+                    .get(index)
+                    .ok_or_else(|| format!("No run {} in {:?}", index, path))?;
+                events_by_run.push(inline_bookmarks::slice_run_events(&events, base_time, run));
+                selected.push(run.clone());
+            }
+        }
+
+        let selected: Vec<_> = selected.iter().collect();
+        Ok(inline_bookmarks::aggregate_runs(&selected, &events_by_run))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Parse a bookmark line like: [ 2026.01.04 03:56:49 ] (bookmark) TYPE: label
+fn parse_bookmark_line(line: &str, tz: TzMode) -> Option<SimpleBookmarkResponse> {
+    use crate::core::inline_bookmarks;
+    let bm = inline_bookmarks::parse_bookmark_line(line)?;
     Some(SimpleBookmarkResponse {
-        timestamp_secs,
-        bookmark_type,
-        label,
+        timestamp_secs: bm.timestamp_secs,
+        display_time: format_log_timestamp(Duration::from_secs(bm.timestamp_secs), tz),
+        bookmark_type: bm.bookmark_type.as_str().to_string(),
+        label: bm.label,
     })
 }
 
 #[tauri::command]
-async fn detect_filaments(gamelog_path: PathBuf) -> Result<(), String> {
-    println!("Detecting filaments for {:?}", gamelog_path);
+async fn detect_filaments(gamelog_path: PathBuf, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .backend_logs
+        .log(format!("Detecting filaments for {:?}", gamelog_path));
+    let abyss_markers = state
+        .settings
+        .lock()
+        .unwrap()
+        .abyss_location_markers
+        .clone();
 
     // 1. Identify character and session from gamelog header
     let header = discovery::extract_header(&gamelog_path, discovery::LogType::Gamelog)
@@ -493,96 +1070,101 @@ async fn detect_filaments(gamelog_path: PathBuf) -> Result<(), String> {
     // 3. Scan Chatlog for Abyss Runs
     let clean_content = discovery::read_log_file(&chatlog_path).map_err(|e| e.to_string())?;
 
-    use crate::core::chatlog::parser::{detect_abyss_runs, ChatlogParser};
+    use crate::core::chatlog::parser::{detect_abyss_runs_with_sessions, ChatlogParser};
     let parser = ChatlogParser::new();
-    let changes = parser.parse_lines(&clean_content.lines().map(String::from).collect::<Vec<_>>());
-    let runs = detect_abyss_runs(&changes);
+    let lines = clean_content.lines().map(String::from).collect::<Vec<_>>();
+    let changes = parser.parse_lines(&lines);
+    let sessions = parser.parse_session_starts(&lines);
+    let runs = detect_abyss_runs_with_sessions(&changes, &sessions, &abyss_markers);
 
     if runs.is_empty() {
         return Ok(());
     }
 
-    // 4. Insert bookmarks into gamelog at correct chronological positions
-    use chrono::{DateTime, NaiveDateTime, Utc};
-    use std::io::{BufRead, BufReader, Write};
-
-    // Helper to parse EVE log timestamp from a line
-    let parse_line_timestamp = |line: &str| -> Option<Duration> {
-        // EVE format: [ YYYY.MM.DD HH:MM:SS ]
-        let start = line.find('[')? + 1;
-        let end = line.find(']')?;
-        let ts_str = line[start..end].trim();
-        let naive = NaiveDateTime::parse_from_str(ts_str, "%Y.%m.%d %H:%M:%S").ok()?;
-        Some(Duration::from_secs(naive.and_utc().timestamp() as u64))
-    };
-
-    let format_ts = |dur: Duration| -> String {
-        let dt: DateTime<Utc> = DateTime::from(std::time::UNIX_EPOCH + dur);
-        dt.format("%Y.%m.%d %H:%M:%S").to_string()
-    };
-
-    // Build bookmark lines with their target timestamps
-    let mut bookmarks_to_insert: Vec<(Duration, String)> = Vec::new();
+    // 4. Insert bookmarks into gamelog at correct chronological positions, keeping
+    // any run markers the live tracker already wrote
+    use crate::core::inline_bookmarks::{self, BookmarkType};
+    let mut markers = Vec::new();
     for run in &runs {
-        let start_line = format!("[ {} ] (bookmark) RUN_START", format_ts(run.entry_time));
-        bookmarks_to_insert.push((run.entry_time, start_line));
-
+        // The origin system rides along as the RUN_START label for the run history
+        markers.push((
+            run.entry_time,
+            BookmarkType::RunStart,
+            run.origin_location.as_deref(),
+        ));
         if let Some(exit_time) = run.exit_time {
-            let end_line = format!("[ {} ] (bookmark) RUN_END", format_ts(exit_time));
-            bookmarks_to_insert.push((exit_time, end_line));
+            markers.push((exit_time, BookmarkType::RunEnd, None));
         }
     }
 
-    // Sort bookmarks by timestamp
-    bookmarks_to_insert.sort_by_key(|(ts, _)| *ts);
-
-    // Read all existing lines
-    let file = std::fs::File::open(&gamelog_path).map_err(|e| e.to_string())?;
-    let reader = BufReader::new(file);
-    let existing_lines: Vec<String> = reader.lines().filter_map(|l| l.ok()).collect();
-
-    // Merge: insert bookmarks at correct positions
-    let mut merged_lines: Vec<String> =
-        Vec::with_capacity(existing_lines.len() + bookmarks_to_insert.len());
-    let mut bookmark_iter = bookmarks_to_insert.into_iter().peekable();
-
-    for line in existing_lines {
-        // Skip any existing bookmark lines to avoid duplicates
-        if line.contains("(bookmark)") && (line.contains("RUN_START") || line.contains("RUN_END")) {
-            continue;
-        }
-
-        // Insert bookmarks that should come before this line
-        if let Some(line_ts) = parse_line_timestamp(&line) {
-            while let Some((bm_ts, _)) = bookmark_iter.peek() {
-                if *bm_ts <= line_ts {
-                    let (_, bm_line) = bookmark_iter.next().unwrap();
-                    merged_lines.push(bm_line);
-                } else {
-                    break;
-                }
-            }
-        }
-        merged_lines.push(line);
+    let added_count = inline_bookmarks::insert_bookmarks_if_absent(&gamelog_path, &markers)
+        .map_err(|e| e.to_string())?;
+    if added_count == 0 {
+        state.backend_logs.log(format!(
+            "All run bookmarks already present in {:?}",
+            gamelog_path
+        ));
+    } else {
+        state.backend_logs.log(format!(
+            "Inserted {} run bookmarks into {:?}",
+            added_count, gamelog_path
+        ));
     }
+    Ok(())
+}
 
-    // Append any remaining bookmarks (in case they're after all existing lines)
-    for (_, bm_line) in bookmark_iter {
-        merged_lines.push(bm_line);
-    }
+/// Export every hit on `target` in the active replay as CSV, for kill analysis.
+#[tauri::command]
+async fn export_target_timeline(
+    target: String,
+    path: PathBuf,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    let timeline = {
+        let replay = state.replay.read().unwrap();
+        let session = replay.as_ref().ok_or("No active replay session")?;
+        analysis::target_timeline(session.engine.events(), &target)
+    };
+    log_io::write_target_timeline_csv(&path, &timeline).map_err(|e| e.to_string())?;
+    state.backend_logs.log(format!(
+        "Exported {} hits on {} to {:?}",
+        timeline.len(),
+        target,
+        path
+    ));
+    Ok(timeline.len())
+}
 
-    // Write merged content back to file
-    let mut file = std::fs::File::create(&gamelog_path).map_err(|e| e.to_string())?;
-    for line in &merged_lines {
-        writeln!(file, "{}", line).map_err(|e| e.to_string())?;
+/// Export the active replay's events as JSON. With `anonymize`, names are
+/// replaced by pseudonyms and the real name -> pseudonym mapping is returned.
+#[tauri::command]
+async fn export_replay_events(
+    path: PathBuf,
+    anonymize: bool,
+    state: State<'_, AppState>,
+) -> Result<HashMap<String, String>, String> {
+    let mut events = {
+        let replay = state.replay.read().unwrap();
+        let session = replay.as_ref().ok_or("No active replay session")?;
+        session.controller.all_events().to_vec()
+    };
+    let mut mapping = HashMap::new();
+    if anonymize {
+        analysis::anonymize(&mut events, &mut mapping);
     }
+    log_io::write_events_json(&path, &events).map_err(|e| e.to_string())?;
+    state.backend_logs.log(format!(
+        "Exported {} events to {:?}{}",
+        events.len(),
+        path,
+        if anonymize { " (anonymized)" } else { "" }
+    ));
+    Ok(mapping)
+}
 
-    let added_count = runs.len() * 2 - runs.iter().filter(|r| r.exit_time.is_none()).count();
-    println!(
-        "Inserted {} run bookmarks into {:?}",
-        added_count, gamelog_path
-    );
-    Ok(())
+#[tauri::command]
+async fn get_backend_logs(state: State<'_, AppState>) -> Result<Vec<LogLine>, String> {
+    Ok(state.backend_logs.snapshot())
 }
 
 // Embed all sound files directly into the binary
@@ -607,18 +1189,55 @@ fn get_embedded_sound(filename: &str) -> Option<&'static [u8]> {
 
 #[derive(Clone, Debug)]
 enum AudioCommand {
+    /// Play a bundled sound by file name ("boundary.wav")
     Play(String),
+    /// Play a user-provided sound file
+    PlayFile(PathBuf),
+}
+
+/// A custom sound in the sounds folder if there is one, else the bundled file
+fn sound_command(state: &AppState, filename: String) -> AudioCommand {
+    let name = filename.strip_suffix(".wav").unwrap_or(&filename);
+    match alerts::resolve_sound_file(name, &state.config_manager.sounds_dir()) {
+        Some(path) => AudioCommand::PlayFile(path),
+        None => AudioCommand::Play(filename),
+    }
 }
 
 #[tauri::command]
 fn play_alert_sound(filename: String, state: State<'_, AppState>) -> Result<(), String> {
     state
         .audio_tx
-        .send(AudioCommand::Play(filename))
+        .send(sound_command(&state, filename))
         .map_err(|e| e.to_string())?;
     Ok(())
 }
 
+/// Play the sound configured for a rule so users can hear it from the settings
+#[tauri::command]
+fn preview_alert_sound(rule_id: AlertRuleId, state: State<'_, AppState>) -> Result<(), String> {
+    let sound = {
+        let settings = state.settings.lock().unwrap();
+        settings
+            .alert_settings
+            .rules
+            .get(&rule_id)
+            .map(|rule| rule.sound.clone())
+            .unwrap_or_default()
+    };
+    let sounds_dir = state.config_manager.sounds_dir();
+    let command = match alerts::resolve_sound_path(&sound, rule_id, &sounds_dir) {
+        Some(path) => AudioCommand::PlayFile(path),
+        None => {
+            let name = sound
+                .filename(rule_id)
+                .ok_or("Sound is disabled for this alert")?;
+            AudioCommand::Play(format!("{}.wav", name))
+        }
+    };
+    state.audio_tx.send(command).map_err(|e| e.to_string())
+}
+
 fn spawn_audio_thread() -> std::sync::mpsc::Sender<AudioCommand> {
     let (tx, rx) = std::sync::mpsc::channel();
 
@@ -658,6 +1277,21 @@ fn spawn_audio_thread() -> std::sync::mpsc::Sender<AudioCommand> {
                         Err(e) => println!("[AUDIO] Error decoding {}: {}", filename, e),
                     }
                 }
+                AudioCommand::PlayFile(path) => {
+                    let sound_data = match std::fs::read(&path) {
+                        Ok(data) => data,
+                        Err(e) => {
+                            println!("[AUDIO] Failed to read {}: {}", path.display(), e);
+                            continue;
+                        }
+                    };
+
+                    println!("[AUDIO] Queuing sound: {}", path.display());
+                    match Decoder::new(Cursor::new(sound_data)) {
+                        Ok(source) => sink.append(source),
+                        Err(e) => println!("[AUDIO] Error decoding {}: {}", path.display(), e),
+                    }
+                }
             }
         }
     });
@@ -713,6 +1347,12 @@ pub fn run() {
                 loop_tx: tx,
                 audio_tx,
                 replay: Arc::new(RwLock::new(None)),
+                latest_sample: Mutex::new(None),
+                run_statuses: Mutex::new(HashMap::new()),
+                fleet_status: Mutex::new(Vec::new()),
+                recent_runs: Mutex::new(Vec::new()),
+                backend_logs: LogRing::default(),
+                auto_track_pending: AtomicBool::new(initial_settings.auto_track_latest),
             });
 
             app.handle().plugin(tauri_plugin_dialog::init())?;
@@ -727,17 +1367,55 @@ pub fn run() {
 
             // Start the background log watcher
             tauri::async_runtime::spawn(async move {
-                let mut current_log_dir = initial_settings.gamelog_dir.clone();
-                let mut coordinator = coordinator::Coordinator::new(current_log_dir.clone());
+                let mut current_log_dirs = initial_settings.gamelog_dirs.clone();
+                let mut coordinator = coordinator::Coordinator::new(current_log_dirs.clone());
                 let mut alert_engine = AlertEngine::new(initial_settings.alert_settings.clone());
-
-                loop {
+                let mut tracking_errors = Vec::new();
+                let mut session_markers: Vec<u64> = Vec::new();
+                let mut peaks = (0.0_f32, 0.0_f32);
+                let mut simulation: Option<replay_engine::LiveSimulation> = None;
+                let mut focus_target: Option<String> = None;
+                let mut dir_watch = LogDirWatch::new(&current_log_dirs);
+                let mut bookmark_writer = {
+                    use crate::core::inline_bookmarks::{BookmarkWriter, DEFAULT_FLUSH_DELAY};
+                    BookmarkWriter::new(DEFAULT_FLUSH_DELAY)
+                };
+
+                'live: loop {
                     // Check for commands from the frontend
                     while let Ok(cmd) = rx.try_recv() {
                         match cmd {
                             LoopCommand::Replay => {
                                 coordinator.replay_logs();
                             }
+                            LoopCommand::SetPaused(paused) => {
+                                coordinator.set_paused(paused);
+                                println!(
+                                    "Live tracking {}",
+                                    if paused { "paused" } else { "resumed" }
+                                );
+                            }
+                            LoopCommand::Simulate(sim) => {
+                                println!(
+                                    "Live simulation {}",
+                                    if sim.is_some() { "started" } else { "stopped" }
+                                );
+                                simulation = sim;
+                            }
+                            LoopCommand::MuteAlerts(duration) => {
+                                alert_engine.mute_for(duration);
+                                println!("Alerts muted for {}s", duration.as_secs());
+                            }
+                            LoopCommand::FocusTarget(target) => {
+                                println!("DPS focus target: {:?}", target);
+                                focus_target = target;
+                            }
+                            LoopCommand::Shutdown(done) => {
+                                coordinator.shutdown(&mut bookmark_writer);
+                                println!("Live loop stopped");
+                                let _ = done.send(());
+                                break 'live;
+                            }
                         }
                     }
 
@@ -750,10 +1428,16 @@ pub fn run() {
                     };
 
                     // Hot-reload: Check if log directory changed
-                    if current_settings.gamelog_dir != current_log_dir {
-                        current_log_dir = current_settings.gamelog_dir.clone();
-                        coordinator = coordinator::Coordinator::new(current_log_dir.clone());
-                        println!("Log directory changed to {:?}", current_log_dir);
+                    if current_settings.gamelog_dirs != current_log_dirs {
+                        current_log_dirs = current_settings.gamelog_dirs.clone();
+                        coordinator = coordinator::Coordinator::new(current_log_dirs.clone());
+                        dir_watch = LogDirWatch::new(&current_log_dirs);
+                        println!("Log directories changed to {:?}", current_log_dirs);
+                    }
+
+                    // New or deleted gamelogs: let the UI refresh its character list
+                    if dir_watch.changed() {
+                        let _ = handle.emit("logs-changed", ());
                     }
 
                     // Hot-reload: DPS Window
@@ -762,10 +1446,57 @@ pub fn run() {
                     // Hot-reload: Alert config
                     alert_engine.update_config(current_settings.alert_settings.clone());
 
-                    let output = coordinator.tick(&active_paths, dps_window);
+                    coordinator.set_abyss_markers(&current_settings.abyss_location_markers);
+                    coordinator.set_locale(&current_settings.locale);
+                    coordinator
+                        .set_reset_peaks_on_run_start(current_settings.reset_peaks_on_run_start);
+                    coordinator.set_jsonl_sink(current_settings.jsonl_sink.as_deref());
+                    coordinator.set_tracked_only(current_settings.tracked_only_combat);
+                    coordinator.set_focus_target(focus_target.as_deref());
+                    coordinator.set_idle_timeout(Duration::from_secs(
+                        current_settings.idle_timeout_seconds,
+                    ));
+                    let mut output = coordinator.tick(&active_paths, dps_window);
+                    let app_state = handle.state::<AppState>();
+                    let backend_logs = &app_state.backend_logs;
+                    for line in output.logs.drain(..) {
+                        backend_logs.push(line);
+                    }
+
+                    // A simulated log replaces the tracked logs' combat for the overlay
+                    if let Some(sim) = simulation.as_mut() {
+                        let (events, sample) = sim.tick(dps_window);
+                        output.new_combat_events = events;
+                        output.dps_sample = sample;
+                        if sim.is_finished() {
+                            println!("Live simulation finished");
+                            simulation = None;
+                        }
+                    }
+
+                    *handle.state::<AppState>().run_statuses.lock().unwrap() = active_paths
+                        .iter()
+                        .filter_map(|path| Some((path.clone(), coordinator.run_status(path)?)))
+                        .collect();
+                    *handle.state::<AppState>().fleet_status.lock().unwrap() =
+                        coordinator.fleet_status();
 
                     // Emit DPS
-                    if let Some(sample) = output.dps_sample {
+                    if let Some(mut sample) = output.dps_sample {
+                        model::split_environmental(
+                            &mut sample,
+                            &current_settings.environmental_sources,
+                        );
+                        model::split_incoming_by_attacker(
+                            &mut sample,
+                            &current_settings.attacker_overrides,
+                        );
+                        model::fold_character_aliases(
+                            &mut sample,
+                            &current_settings.character_aliases,
+                        );
+                        *handle.state::<AppState>().latest_sample.lock().unwrap() =
+                            Some(sample.clone());
                         let _ = handle.emit("dps-update", sample);
                     }
 
@@ -778,6 +1509,14 @@ pub fn run() {
                             .map(|(name, _)| name)
                             .collect();
 
+                        alert_engine.set_characters_in_abyss(
+                            coordinator
+                                .fleet_status()
+                                .into_iter()
+                                .filter(|status| status.in_abyss)
+                                .map(|status| status.character)
+                                .collect(),
+                        );
                         let alerts = alert_engine.evaluate(
                             &output.new_combat_events,
                             &output.new_notify_events,
@@ -785,7 +1524,7 @@ pub fn run() {
                         );
 
                         for alert in alerts {
-                            println!("[ALERT] {}", alert.message);
+                            backend_logs.log(format!("[ALERT] {}", alert.message));
                             let _ = handle.emit(
                                 "alert-triggered",
                                 serde_json::json!({
@@ -799,18 +1538,17 @@ pub fn run() {
 
                     // Handle location changes for auto run management (append to gamelog)
                     if !output.location_changes.is_empty() {
-                        use crate::core::inline_bookmarks;
-
                         for loc_change in output.location_changes {
-                            if loc_change.change.is_abyss_entry() {
+                            if loc_change
+                                .change
+                                .is_abyss_entry_with(&current_settings.abyss_location_markers)
+                            {
                                 // Entering Abyss - append RUN_START to gamelog
-                                if let Err(e) =
-                                    inline_bookmarks::add_run_start(&loc_change.gamelog_path)
-                                {
-                                    println!("Error appending run start: {}", e);
-                                } else {
-                                    println!("{} entered the Abyss", loc_change.character_name);
-                                }
+                                bookmark_writer.add_run_start(&loc_change.gamelog_path);
+                                backend_logs.log(format!(
+                                    "{} entered the Abyss",
+                                    loc_change.character_name
+                                ));
 
                                 // Emit event for frontend
                                 let _ = handle.emit(
@@ -821,16 +1559,11 @@ pub fn run() {
                                 );
                             } else {
                                 // Exiting Abyss - append RUN_END to gamelog
-                                if let Err(e) =
-                                    inline_bookmarks::add_run_end(&loc_change.gamelog_path)
-                                {
-                                    println!("Error appending run end: {}", e);
-                                } else {
-                                    println!(
-                                        "{} exited the Abyss to {}",
-                                        loc_change.character_name, loc_change.change.location
-                                    );
-                                }
+                                bookmark_writer.add_run_end(&loc_change.gamelog_path);
+                                backend_logs.log(format!(
+                                    "{} exited the Abyss to {}",
+                                    loc_change.character_name, loc_change.change.location
+                                ));
 
                                 // Emit event for frontend
                                 let _ = handle.emit(
@@ -844,6 +1577,53 @@ pub fn run() {
                         }
                     }
 
+                    if let Err(e) = bookmark_writer.flush_due() {
+                        backend_logs.log(format!("Error appending run bookmarks: {}", e));
+                    }
+
+                    let new_peaks = (output.peak_outgoing_dps, output.peak_incoming_dps);
+                    if new_peaks != peaks {
+                        peaks = new_peaks;
+                        let _ = handle.emit(
+                            "dps-peaks",
+                            serde_json::json!({
+                                "outgoing": peaks.0,
+                                "incoming": peaks.1
+                            }),
+                        );
+                    }
+
+                    // Only notify the frontend when the set of problems changes
+                    if output.tracking_errors != tracking_errors {
+                        for error in &output.tracking_errors {
+                            backend_logs.log(format!("Tracking error: {:?}", error));
+                        }
+                        let _ = handle.emit("tracking-errors", &output.tracking_errors);
+                        tracking_errors = output.tracking_errors;
+                    }
+
+                    if output.session_markers != session_markers {
+                        let _ = handle.emit("session-markers", &output.session_markers);
+                        session_markers = output.session_markers;
+                    }
+
+                    for summary in output.run_summaries {
+                        backend_logs.log(format!(
+                            "Run summary for {}: {}s, {:.0} dealt, {:.0} taken",
+                            summary.character,
+                            summary.duration_secs,
+                            summary.total_outgoing,
+                            summary.total_incoming
+                        ));
+                        {
+                            let mut recent = app_state.recent_runs.lock().unwrap();
+                            recent.push(summary.clone());
+                            let excess = recent.len().saturating_sub(MAX_RECENT_RUNS);
+                            recent.drain(..excess);
+                        }
+                        let _ = handle.emit("run-summary", summary);
+                    }
+
                     tokio::time::sleep(Duration::from_millis(250)).await;
                 }
             });
@@ -857,22 +1637,59 @@ pub fn run() {
             save_settings,
             pick_gamelog_dir,
             replay_logs,
+            set_tracking_paused,
+            set_focus_target,
+            simulate_live,
+            stop_simulation,
+            mute_alerts,
+            copy_fleet_summary,
+            copy_run_markdown,
+            get_backend_logs,
+            export_target_timeline,
+            export_replay_events,
+            replay_snapshot_at,
+            get_room_score,
+            get_character_colors,
+            get_run_status,
             open_replay_window,
             get_logs_by_character,
             start_replay,
+            replace_replay_logs,
             toggle_replay_pause,
             set_replay_speed,
             seek_replay,
             step_replay,
+            set_replay_loop,
+            set_replay_run_gap,
+            seek_replay_run,
+            set_replay_source_offset,
             // Bookmark commands
             create_highlight_bookmark,
             toggle_room_marker,
             detect_filaments,
             get_session_bookmarks,
+            delete_bookmark,
+            set_bookmark_label,
             // Audio
             play_alert_sound,
+            preview_alert_sound,
+            list_runs,
+            get_fleet_status,
+            cycle_dps_window,
+            step_dps_window,
+            set_character_alias,
+            toggle_overlay_lock,
+            start_window_drag,
+            diagnose_log,
+            load_run,
+            aggregate_runs,
             stop_replay
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                shutdown(app);
+            }
+        });
 }
@@ -0,0 +1,229 @@
+//! Headless command-line mode.
+//!
+//! `abyss-watcher analyze <path> [--window N]` reads a whole gamelog, runs the
+//! same DPS analysis as the overlay and prints summary stats to stdout. Lines
+//! are read in the client language saved in the app's settings.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::core::analysis::DpsAccumulator;
+use crate::core::config::ConfigManager;
+use crate::core::log_io;
+use crate::core::model::{DpsSample, EventType};
+use crate::core::parser::LocaleConfig;
+
+const USAGE: &str = "Usage: abyss-watcher analyze <path> [--window <seconds>]";
+
+/// How many targets/weapons to list
+const TOP_N: usize = 5;
+
+/// Bundle identifier from tauri.conf.json; names the app's config folder
+const APP_IDENTIFIER: &str = "com.abysswatcher.app";
+
+/// Handle CLI arguments. Returns the process exit code if a CLI command ran,
+/// or `None` if the GUI should start as usual.
+pub fn try_run(args: impl IntoIterator<Item = String>) -> Option<i32> {
+    let mut args = args.into_iter().skip(1);
+    if args.next()?.as_str() != "analyze" {
+        return None;
+    }
+    attach_console();
+
+    let mut path: Option<PathBuf> = None;
+    let mut window_secs = 5;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--window" => match args.next().and_then(|value| value.parse().ok()) {
+                Some(secs) if secs > 0 => window_secs = secs,
+                _ => return Some(usage_error("--window expects a positive number of seconds")),
+            },
+            _ if path.is_none() => path = Some(PathBuf::from(arg)),
+            _ => return Some(usage_error(&format!("unexpected argument: {}", arg))),
+        }
+    }
+
+    let Some(path) = path else {
+        return Some(usage_error("missing log path"));
+    };
+
+    let locale = app_config_dir()
+        .map(|dir| ConfigManager::new(dir).load().locale)
+        .unwrap_or_default();
+    let stdout = io::stdout();
+    match analyze(
+        &path,
+        Duration::from_secs(window_secs),
+        &locale,
+        &mut stdout.lock(),
+    ) {
+        Ok(()) => Some(0),
+        Err(e) => {
+            eprintln!("Failed to analyze {:?}: {}", path, e);
+            Some(1)
+        }
+    }
+}
+
+/// Release builds on Windows use the GUI subsystem and start without a console;
+/// attach to the launching terminal's so the report and errors are visible.
+#[cfg(windows)]
+fn attach_console() {
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    // Fails harmlessly when there is no parent console or one is already attached
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_console() {}
+
+/// The folder the GUI keeps settings.json in (Tauri's `app_config_dir`)
+fn app_config_dir() -> Option<PathBuf> {
+    let env_dir = |name: &str| std::env::var_os(name).map(PathBuf::from);
+    let base = if cfg!(windows) {
+        env_dir("APPDATA")?
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME")?.join("Library/Application Support")
+    } else {
+        env_dir("XDG_CONFIG_HOME").or_else(|| Some(env_dir("HOME")?.join(".config")))?
+    };
+    Some(base.join(APP_IDENTIFIER))
+}
+
+fn usage_error(message: &str) -> i32 {
+    eprintln!("{}\n{}", message, USAGE);
+    2
+}
+
+/// Analyze a full gamelog and write a plain-text report to `out`.
+pub fn analyze(
+    path: &Path,
+    window: Duration,
+    locale: &LocaleConfig,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let events = log_io::read_full_events(path, locale)?;
+
+    writeln!(out, "Log: {}", path.display())?;
+    writeln!(out, "Window: {}s", window.as_secs())?;
+
+    let (Some(first), Some(last)) = (events.first(), events.last()) else {
+        writeln!(out, "No combat events found.")?;
+        return Ok(());
+    };
+    let active_secs = last
+        .timestamp
+        .saturating_sub(first.timestamp)
+        .as_secs_f32()
+        .max(1.0);
+
+    // Walk the whole session; compute_dps_series only keeps the last minute
+    let mut accumulator = DpsAccumulator::new(window);
+    for event in &events {
+        accumulator.push(event.clone());
+    }
+    let mut samples = accumulator.advance(Duration::ZERO);
+    samples.extend(accumulator.advance(last.timestamp));
+
+    let peak = |value: fn(&DpsSample) -> f32| samples.iter().map(value).fold(0.0_f32, f32::max);
+
+    let mut outgoing_total = 0.0_f32;
+    let mut incoming_total = 0.0_f32;
+    let mut by_target: HashMap<&str, f32> = HashMap::new();
+    let mut by_weapon: HashMap<&str, f32> = HashMap::new();
+    for event in events.iter().filter(|e| e.event_type == EventType::Damage) {
+        if event.incoming {
+            incoming_total += event.amount;
+        } else {
+            outgoing_total += event.amount;
+            *by_target.entry(&event.target).or_insert(0.0) += event.amount;
+            *by_weapon.entry(&event.weapon).or_insert(0.0) += event.amount;
+        }
+    }
+
+    writeln!(out, "Duration: {:.0}s", active_secs)?;
+    writeln!(
+        out,
+        "Outgoing DPS: peak {:.1}, avg {:.1}",
+        peak(|s| s.outgoing_dps),
+        outgoing_total / active_secs
+    )?;
+    writeln!(
+        out,
+        "Incoming DPS: peak {:.1}, avg {:.1}",
+        peak(|s| s.incoming_dps),
+        incoming_total / active_secs
+    )?;
+
+    write_top(out, "Top targets", by_target)?;
+    write_top(out, "Top weapons", by_weapon)?;
+    Ok(())
+}
+
+fn write_top(out: &mut impl Write, title: &str, totals: HashMap<&str, f32>) -> io::Result<()> {
+    let mut rows: Vec<(&str, f32)> = totals.into_iter().collect();
+    rows.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.0.cmp(b.0))
+    });
+
+    writeln!(out, "{}:", title)?;
+    for (name, damage) in rows.into_iter().take(TOP_N) {
+        writeln!(out, "  {}: {:.0}", name, damage)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn analyze_reads_lines_in_the_configured_locale() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("20250101_120000_1.txt");
+        fs::write(
+            &path,
+            "  Listener: Pilot\n\
+             Session Started: 2025.01.01 12:00:00\n\
+             [ 2025.01.01 12:00:01 ] (Kampf) 100 gegen Starving Damavik - Gun\n\
+             [ 2025.01.01 12:00:02 ] (Kampf) 50 von Starving Damavik - Hits\n",
+        )
+        .unwrap();
+        let german = LocaleConfig {
+            combat_marker: "(Kampf)".to_string(),
+            outgoing: vec!["an".to_string(), "gegen".to_string()],
+            incoming: vec!["von".to_string()],
+        };
+
+        let mut report = Vec::new();
+        analyze(&path, Duration::from_secs(5), &german, &mut report).unwrap();
+        let report = String::from_utf8(report).unwrap();
+        assert!(report.contains("  Starving Damavik: 100"), "{}", report);
+        assert!(report.contains("  Gun: 100"), "{}", report);
+
+        let mut report = Vec::new();
+        analyze(
+            &path,
+            Duration::from_secs(5),
+            &LocaleConfig::default(),
+            &mut report,
+        )
+        .unwrap();
+        assert!(String::from_utf8(report)
+            .unwrap()
+            .contains("No combat events found."));
+    }
+}
@@ -1,11 +1,13 @@
 // Alert engine - orchestrates trigger evaluation and manages cooldowns.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 
-use super::model::{AlertEvent, AlertRuleConfig, AlertRuleId, AlertSound, CharacterRoles};
+use super::model::{
+    AlertEvent, AlertRuleConfig, AlertRuleId, AlertSound, CharacterRoles, CooldownScope,
+};
 use super::triggers::{evaluate_trigger, TriggerContext};
 use crate::core::model::{CombatEvent, NotifyEvent};
 
@@ -16,6 +18,9 @@ pub struct AlertEngineConfig {
     pub rules: HashMap<AlertRuleId, AlertRuleConfig>,
     /// Character role designations
     pub roles: CharacterRoles,
+    /// Minimum cooldown (seconds) applied on top of every rule's own cooldown
+    #[serde(default)]
+    pub min_cooldown_seconds: u32,
 }
 
 impl AlertEngineConfig {
@@ -28,6 +33,7 @@ impl AlertEngineConfig {
         Self {
             rules,
             roles: CharacterRoles::default(),
+            min_cooldown_seconds: 0,
         }
     }
 
@@ -44,14 +50,22 @@ impl AlertEngineConfig {
             .unwrap_or_default()
     }
 
-    /// Get the cooldown for a specific rule in seconds
+    /// Get the cooldown scope for a specific rule
+    pub fn get_cooldown_scope(&self, rule_id: AlertRuleId) -> CooldownScope {
+        self.rules
+            .get(&rule_id)
+            .map(|c| c.cooldown_scope)
+            .unwrap_or_default()
+    }
+
+    /// Get the cooldown for a specific rule, never below the global minimum
     pub fn get_cooldown(&self, rule_id: AlertRuleId) -> Duration {
         let secs = self
             .rules
             .get(&rule_id)
             .map(|c| c.cooldown_seconds)
             .unwrap_or(3);
-        Duration::from_secs(secs as u64)
+        Duration::from_secs(secs.max(self.min_cooldown_seconds) as u64)
     }
 }
 
@@ -59,8 +73,13 @@ impl AlertEngineConfig {
 pub struct AlertEngine {
     /// Configuration
     config: AlertEngineConfig,
-    /// Cooldown tracking: last fire time per rule
-    cooldowns: HashMap<AlertRuleId, Instant>,
+    /// Cooldown tracking: last fire time per rule, and per character for
+    /// `CooldownScope::RulePerSubject` rules
+    cooldowns: HashMap<(AlertRuleId, Option<String>), Instant>,
+    /// All alerts are suppressed until this instant (not persisted)
+    muted_until: Option<Instant>,
+    /// Characters currently in the Abyss, for `only_in_abyss` rules
+    characters_in_abyss: HashSet<String>,
 }
 
 impl AlertEngine {
@@ -68,6 +87,8 @@ impl AlertEngine {
         Self {
             config,
             cooldowns: HashMap::new(),
+            muted_until: None,
+            characters_in_abyss: HashSet::new(),
         }
     }
 
@@ -76,8 +97,28 @@ impl AlertEngine {
         self.config = config;
     }
 
+    /// Suppress all alerts until `until`, without touching the config.
+    pub fn mute_until(&mut self, until: Instant) {
+        self.muted_until = Some(until);
+    }
+
+    /// Suppress all alerts for `duration` from now. A zero duration unmutes.
+    pub fn mute_for(&mut self, duration: Duration) {
+        self.muted_until = (!duration.is_zero()).then(|| Instant::now() + duration);
+    }
+
+    /// Update which characters are in the Abyss, from their Local chat logs.
+    pub fn set_characters_in_abyss(&mut self, characters: HashSet<String>) {
+        self.characters_in_abyss = characters;
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.muted_until.is_some_and(|until| Instant::now() < until)
+    }
+
     /// Evaluate all triggers against current events.
-    /// Returns list of alert events that fired (deduplicated by rule_id per tick).
+    /// Returns list of alert events that fired (deduplicated by rule_id per tick, or by
+    /// rule_id and character for per-subject rules).
     /// Audio playback is handled by the frontend/audio thread sequentially.
     pub fn evaluate(
         &mut self,
@@ -88,6 +129,10 @@ impl AlertEngine {
         let mut alerts = Vec::new();
         let now = Instant::now();
 
+        if self.is_muted() {
+            return alerts;
+        }
+
         // Build sets from config for trigger context
         let logi_set: HashSet<String> = self.config.roles.logi_characters.iter().cloned().collect();
         let neut_set: HashSet<String> = self
@@ -104,6 +149,8 @@ impl AlertEngine {
             tracked_characters,
             logi_characters: &logi_set,
             neut_sensitive_characters: &neut_set,
+            characters_in_abyss: &self.characters_in_abyss,
+            window: event_span(combat_events),
         };
 
         for rule_id in AlertRuleId::all() {
@@ -112,39 +159,75 @@ impl AlertEngine {
                 continue;
             }
 
-            // Check per-rule cooldown to prevent spam
             let rule_cooldown = self.config.get_cooldown(*rule_id);
-            if let Some(last_fire) = self.cooldowns.get(rule_id) {
-                if now.duration_since(*last_fire) < rule_cooldown {
-                    continue;
+
+            // Per-rule options (ignore_vorton, dps_threshold, ...)
+            let rule_config = self.config.rules.get(rule_id).cloned().unwrap_or_default();
+
+            let subjects: Vec<Option<String>> = match self.config.get_cooldown_scope(*rule_id) {
+                CooldownScope::RuleGlobal => vec![None],
+                CooldownScope::RulePerSubject => combat_events
+                    .iter()
+                    .map(|e| e.character.as_str())
+                    .chain(notify_events.iter().map(|e| e.character.as_str()))
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .map(|character| Some(character.to_string()))
+                    .collect(),
+            };
+
+            for subject in subjects {
+                // Check cooldown to prevent spam
+                let key = (*rule_id, subject);
+                if let Some(last_fire) = self.cooldowns.get(&key) {
+                    if now.duration_since(*last_fire) < rule_cooldown {
+                        continue;
+                    }
                 }
-            }
 
-            // Get per-rule ignore_vorton setting (only used by FriendlyFire and LogiTakingDamage)
-            let ignore_vorton = self
-                .config
-                .rules
-                .get(rule_id)
-                .map(|c| c.ignore_vorton)
-                .unwrap_or(true);
-
-            // Evaluate trigger
-            if let Some(message) = evaluate_trigger(*rule_id, &ctx, ignore_vorton) {
-                self.cooldowns.insert(*rule_id, now);
-
-                // Get timestamp from the first relevant event
-                let timestamp = combat_events
-                    .first()
-                    .map(|e| e.timestamp)
-                    .or_else(|| notify_events.first().map(|e| e.timestamp))
-                    .unwrap_or_default();
-
-                alerts.push(AlertEvent {
-                    rule_id: *rule_id,
-                    timestamp,
-                    message,
-                    sound: self.config.get_sound(*rule_id),
-                });
+                // Per-subject rules only see the events of their own character
+                let (subject_combat, subject_notify): (Vec<CombatEvent>, Vec<NotifyEvent>) =
+                    match &key.1 {
+                        Some(character) => (
+                            combat_events
+                                .iter()
+                                .filter(|e| &e.character == character)
+                                .cloned()
+                                .collect(),
+                            notify_events
+                                .iter()
+                                .filter(|e| &e.character == character)
+                                .cloned()
+                                .collect(),
+                        ),
+                        None => (Vec::new(), Vec::new()),
+                    };
+                let subject_ctx = TriggerContext {
+                    combat_events: &subject_combat,
+                    notify_events: &subject_notify,
+                    window: event_span(&subject_combat),
+                    ..ctx
+                };
+                let rule_ctx = if key.1.is_some() { &subject_ctx } else { &ctx };
+
+                // Evaluate trigger
+                if let Some(message) = evaluate_trigger(*rule_id, rule_ctx, &rule_config) {
+                    // Get timestamp from the first relevant event
+                    let timestamp = rule_ctx
+                        .combat_events
+                        .first()
+                        .map(|e| e.timestamp)
+                        .or_else(|| rule_ctx.notify_events.first().map(|e| e.timestamp))
+                        .unwrap_or_default();
+
+                    self.cooldowns.insert(key, now);
+                    alerts.push(AlertEvent {
+                        rule_id: *rule_id,
+                        timestamp,
+                        message,
+                        sound: self.config.get_sound(*rule_id),
+                    });
+                }
             }
         }
 
@@ -152,6 +235,17 @@ impl AlertEngine {
     }
 }
 
+/// Time covered by a batch of events, at least one second since log
+/// timestamps only have second resolution
+fn event_span(events: &[CombatEvent]) -> Duration {
+    let first = events.iter().map(|e| e.timestamp).min();
+    let last = events.iter().map(|e| e.timestamp).max();
+    match (first, last) {
+        (Some(first), Some(last)) => (last - first).max(Duration::from_secs(1)),
+        _ => Duration::from_secs(1),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +267,10 @@ mod tests {
             incoming,
             character: character.to_string(),
             event_type,
+            hit_quality: None,
+            via_drone: false,
+            self_inflicted: false,
+            damage_layer: None,
         }
     }
 
@@ -238,4 +336,177 @@ mod tests {
         assert_eq!(alerts.len(), 1);
         assert_eq!(alerts[0].rule_id, AlertRuleId::EnvironmentalDamage);
     }
+
+    #[test]
+    fn test_engine_mute_suppresses_then_expires() {
+        let mut engine = AlertEngine::new(AlertEngineConfig::default_enabled());
+
+        let combat = vec![make_combat_event(
+            EventType::Damage,
+            true,
+            "Unstable Abyssal Depths",
+            "MyShip",
+            "MyPilot",
+        )];
+
+        engine.mute_for(Duration::from_millis(50));
+        assert!(engine.evaluate(&combat, &[], &HashSet::new()).is_empty());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(!engine.is_muted());
+        let alerts = engine.evaluate(&combat, &[], &HashSet::new());
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule_id, AlertRuleId::EnvironmentalDamage);
+    }
+
+    #[test]
+    fn test_min_cooldown_floor() {
+        let mut config = AlertEngineConfig::default_enabled();
+        config
+            .rules
+            .get_mut(&AlertRuleId::EnvironmentalDamage)
+            .unwrap()
+            .cooldown_seconds = 2;
+        config.min_cooldown_seconds = 10;
+        assert_eq!(
+            config.get_cooldown(AlertRuleId::EnvironmentalDamage),
+            Duration::from_secs(10)
+        );
+
+        config.min_cooldown_seconds = 1;
+        assert_eq!(
+            config.get_cooldown(AlertRuleId::EnvironmentalDamage),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn test_per_subject_cooldown_alerts_each_logi() {
+        let mut config = AlertEngineConfig::default_enabled();
+        config.roles.logi_characters = vec!["LogiA".to_string(), "LogiB".to_string()];
+        config
+            .rules
+            .get_mut(&AlertRuleId::LogiTakingDamage)
+            .unwrap()
+            .cooldown_scope = CooldownScope::RulePerSubject;
+        let mut engine = AlertEngine::new(config.clone());
+
+        let hit = |character: &str| {
+            vec![make_combat_event(
+                EventType::Damage,
+                true,
+                "Rat",
+                character,
+                character,
+            )]
+        };
+        let logi_alerts = |alerts: Vec<AlertEvent>| {
+            alerts
+                .into_iter()
+                .filter(|a| a.rule_id == AlertRuleId::LogiTakingDamage)
+                .count()
+        };
+
+        assert_eq!(
+            logi_alerts(engine.evaluate(&hit("LogiA"), &[], &HashSet::new())),
+            1
+        );
+        assert_eq!(
+            logi_alerts(engine.evaluate(&hit("LogiB"), &[], &HashSet::new())),
+            1
+        );
+        // Same pilot again within the cooldown is still rate-limited
+        assert_eq!(
+            logi_alerts(engine.evaluate(&hit("LogiA"), &[], &HashSet::new())),
+            0
+        );
+
+        // Both pilots in one tick alert separately
+        let mut engine = AlertEngine::new(config.clone());
+        let both = [hit("LogiA"), hit("LogiB")].concat();
+        assert_eq!(logi_alerts(engine.evaluate(&both, &[], &HashSet::new())), 2);
+
+        // A global cooldown lets only the first pilot through
+        config
+            .rules
+            .get_mut(&AlertRuleId::LogiTakingDamage)
+            .unwrap()
+            .cooldown_scope = CooldownScope::RuleGlobal;
+        let mut engine = AlertEngine::new(config);
+        assert_eq!(
+            logi_alerts(engine.evaluate(&hit("LogiA"), &[], &HashSet::new())),
+            1
+        );
+        assert_eq!(
+            logi_alerts(engine.evaluate(&hit("LogiB"), &[], &HashSet::new())),
+            0
+        );
+    }
+
+    #[test]
+    fn test_engine_incoming_dps_spike_fires_once() {
+        let mut config = AlertEngineConfig::default_enabled();
+        config
+            .rules
+            .get_mut(&AlertRuleId::IncomingDpsSpike)
+            .unwrap()
+            .dps_threshold = 1000.0;
+        let mut engine = AlertEngine::new(config);
+
+        // 2400 damage within 1.5 seconds: 1600 DPS
+        let burst: Vec<CombatEvent> = (0..4)
+            .map(|i| CombatEvent {
+                timestamp: Duration::from_millis(i * 500),
+                amount: 600.0,
+                ..make_combat_event(
+                    EventType::Damage,
+                    true,
+                    "Striking Leshak",
+                    "MyShip",
+                    "MyPilot",
+                )
+            })
+            .collect();
+
+        let spikes = |alerts: Vec<AlertEvent>| {
+            alerts
+                .iter()
+                .filter(|a| a.rule_id == AlertRuleId::IncomingDpsSpike)
+                .count()
+        };
+        assert_eq!(spikes(engine.evaluate(&burst, &[], &HashSet::new())), 1);
+        assert_eq!(spikes(engine.evaluate(&burst, &[], &HashSet::new())), 0);
+    }
+
+    #[test]
+    fn test_only_in_abyss_rule_waits_for_abyss() {
+        let mut config = AlertEngineConfig::default_enabled();
+        let rule = config
+            .rules
+            .get_mut(&AlertRuleId::IncomingDpsSpike)
+            .unwrap();
+        rule.dps_threshold = 50.0;
+        rule.only_in_abyss = true;
+        let mut engine = AlertEngine::new(config);
+
+        let hit = vec![make_combat_event(
+            EventType::Damage,
+            true,
+            "Gate Camper",
+            "MyShip",
+            "MyPilot",
+        )];
+        let spikes = |alerts: Vec<AlertEvent>| {
+            alerts
+                .iter()
+                .filter(|a| a.rule_id == AlertRuleId::IncomingDpsSpike)
+                .count()
+        };
+
+        // Docked or in known space: no alert
+        assert_eq!(spikes(engine.evaluate(&hit, &[], &HashSet::new())), 0);
+
+        engine.set_characters_in_abyss(HashSet::from(["MyPilot".to_string()]));
+        assert_eq!(spikes(engine.evaluate(&hit, &[], &HashSet::new())), 1);
+    }
 }
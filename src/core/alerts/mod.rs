@@ -8,3 +8,5 @@
 pub mod engine;
 pub mod model;
 pub mod triggers;
+
+pub use model::{resolve_sound_file, resolve_sound_path};
@@ -4,6 +4,7 @@
 // Keep both files in sync when modifying data structures.
 
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 /// Unique identifier for hardcoded alert rules
@@ -21,6 +22,12 @@ pub enum AlertRuleId {
     CapacitorFailure,
     /// Designated logi character being neuted
     LogiNeuted,
+    /// Tracked character warp scrambled or webbed
+    EwarApplied,
+    /// Incoming DPS on a character above the rule's threshold
+    IncomingDpsSpike,
+    /// Tracked character taking hits on hull
+    LowHealthWarning,
 }
 
 impl AlertRuleId {
@@ -33,6 +40,9 @@ impl AlertRuleId {
             Self::NeutSensitiveNeuted => "Neut Pressure",
             Self::CapacitorFailure => "Capacitor Failure",
             Self::LogiNeuted => "Logi Neuted",
+            Self::EwarApplied => "Scrammed / Webbed",
+            Self::IncomingDpsSpike => "Incoming DPS Spike",
+            Self::LowHealthWarning => "Hull Damage",
         }
     }
 
@@ -45,6 +55,9 @@ impl AlertRuleId {
             Self::NeutSensitiveNeuted => "Alert when a designated neut-sensitive character is neuted",
             Self::CapacitorFailure => "Alert when a module fails to activate due to insufficient capacitor",
             Self::LogiNeuted => "Alert when a designated logi character is neuted",
+            Self::EwarApplied => "Alert when a tracked character is warp scrambled or webbed",
+            Self::IncomingDpsSpike => "Alert when incoming DPS on a character exceeds a threshold",
+            Self::LowHealthWarning => "Alert when a tracked character takes damage to hull",
         }
     }
 
@@ -57,6 +70,9 @@ impl AlertRuleId {
             Self::NeutSensitiveNeuted,
             Self::CapacitorFailure,
             Self::LogiNeuted,
+            Self::EwarApplied,
+            Self::IncomingDpsSpike,
+            Self::LowHealthWarning,
         ]
     }
 }
@@ -82,12 +98,48 @@ impl AlertSound {
                 AlertRuleId::NeutSensitiveNeuted => "neut",
                 AlertRuleId::CapacitorFailure => "capacitor_empty",
                 AlertRuleId::LogiNeuted => "logi_neuted",
+                // No dedicated sound yet; reuse the out-of-bounds warning
+                AlertRuleId::EwarApplied => "boundary",
+                AlertRuleId::IncomingDpsSpike => "logi_attacked",
+                AlertRuleId::LowHealthWarning => "logi_attacked",
             }),
             Self::None => None,
         }
     }
 }
 
+/// File extensions tried, in order, when looking up a sound on disk
+const SOUND_EXTENSIONS: &[&str] = &["wav", "ogg"];
+
+/// Sound file for a rule in `sounds_dir`, e.g. `boundary.wav` or `boundary.ogg`.
+/// `None` if the sound is disabled or no matching file exists; callers then
+/// fall back to the bundled sound.
+pub fn resolve_sound_path(
+    sound: &AlertSound,
+    rule_id: AlertRuleId,
+    sounds_dir: &Path,
+) -> Option<PathBuf> {
+    resolve_sound_file(sound.filename(rule_id)?, sounds_dir)
+}
+
+/// `<name>.wav` or `<name>.ogg` in `sounds_dir`, whichever exists first.
+pub fn resolve_sound_file(name: &str, sounds_dir: &Path) -> Option<PathBuf> {
+    SOUND_EXTENSIONS
+        .iter()
+        .map(|ext| sounds_dir.join(format!("{}.{}", name, ext)))
+        .find(|path| path.is_file())
+}
+
+/// What a rule's cooldown applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CooldownScope {
+    /// One cooldown per affected character, so different pilots alert independently
+    RulePerSubject,
+    /// One cooldown for the whole rule
+    #[default]
+    RuleGlobal,
+}
+
 /// Per-rule configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlertRuleConfig {
@@ -96,9 +148,17 @@ pub struct AlertRuleConfig {
     /// Per-rule cooldown in seconds (default: 3)
     #[serde(default = "default_cooldown")]
     pub cooldown_seconds: u32,
+    #[serde(default)]
+    pub cooldown_scope: CooldownScope,
     /// For FriendlyFire: ignore damage from Vorton weapons (chain lightning AOE)
     #[serde(default = "default_ignore_vorton")]
     pub ignore_vorton: bool,
+    /// For IncomingDpsSpike: incoming DPS that fires the alert
+    #[serde(default = "default_dps_threshold")]
+    pub dps_threshold: f32,
+    /// Only fire while the affected character is in the Abyss
+    #[serde(default)]
+    pub only_in_abyss: bool,
 }
 
 fn default_cooldown() -> u32 {
@@ -109,13 +169,20 @@ fn default_ignore_vorton() -> bool {
     true
 }
 
+fn default_dps_threshold() -> f32 {
+    500.0
+}
+
 impl Default for AlertRuleConfig {
     fn default() -> Self {
         Self {
             enabled: true,
             sound: AlertSound::Default,
             cooldown_seconds: 3,
+            cooldown_scope: CooldownScope::default(),
             ignore_vorton: true, // Default to ignoring Vorton for FriendlyFire
+            dps_threshold: default_dps_threshold(),
+            only_in_abyss: false,
         }
     }
 }
@@ -159,6 +226,34 @@ mod tests {
         assert_eq!(AlertRuleId::FriendlyFire.display_name(), "Friendly Fire");
     }
 
+    #[test]
+    fn test_resolve_sound_path_finds_custom_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("neut.ogg"), b"").unwrap();
+
+        assert_eq!(
+            resolve_sound_path(
+                &AlertSound::Default,
+                AlertRuleId::NeutSensitiveNeuted,
+                dir.path()
+            ),
+            Some(dir.path().join("neut.ogg"))
+        );
+        // No file for this rule, and disabled sounds never resolve
+        assert_eq!(
+            resolve_sound_path(&AlertSound::Default, AlertRuleId::FriendlyFire, dir.path()),
+            None
+        );
+        assert_eq!(
+            resolve_sound_path(
+                &AlertSound::None,
+                AlertRuleId::NeutSensitiveNeuted,
+                dir.path()
+            ),
+            None
+        );
+    }
+
     #[test]
     fn test_sound_filenames() {
         assert_eq!(
@@ -3,10 +3,11 @@
 // Each trigger evaluates combat/notify events and returns an optional message
 // when the trigger condition is met.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
-use super::model::AlertRuleId;
-use crate::core::model::{CombatEvent, EventType, NotifyEvent};
+use super::model::{AlertRuleConfig, AlertRuleId};
+use crate::core::model::{CombatEvent, DamageLayer, EventType, EwarKind, NotifyEvent};
 
 /// Context provided to triggers for evaluation
 pub struct TriggerContext<'a> {
@@ -20,16 +21,50 @@ pub struct TriggerContext<'a> {
     pub logi_characters: &'a HashSet<String>,
     /// Characters designated as neut-sensitive
     pub neut_sensitive_characters: &'a HashSet<String>,
+    /// Characters whose Local chat currently places them in the Abyss
+    pub characters_in_abyss: &'a HashSet<String>,
+    /// Time span the combat events cover, for rate-based triggers
+    pub window: Duration,
 }
 
 /// Evaluate a specific trigger against the current context.
 /// Returns Some(message) if the trigger fired, None otherwise.
-/// `ignore_vorton` is passed per-rule for FriendlyFire and LogiTakingDamage.
+/// `rule` supplies per-rule options such as `ignore_vorton` and `dps_threshold`;
+/// an `only_in_abyss` rule only sees the events of characters in the Abyss.
 pub fn evaluate_trigger(
     rule_id: AlertRuleId,
     ctx: &TriggerContext,
-    ignore_vorton: bool,
+    rule: &AlertRuleConfig,
 ) -> Option<String> {
+    if !rule.only_in_abyss {
+        return evaluate_rule(rule_id, ctx, rule);
+    }
+    let combat: Vec<CombatEvent> = ctx
+        .combat_events
+        .iter()
+        .filter(|e| ctx.characters_in_abyss.contains(&e.character))
+        .cloned()
+        .collect();
+    let notify: Vec<NotifyEvent> = ctx
+        .notify_events
+        .iter()
+        .filter(|e| ctx.characters_in_abyss.contains(&e.character))
+        .cloned()
+        .collect();
+    let abyss_ctx = TriggerContext {
+        combat_events: &combat,
+        notify_events: &notify,
+        ..*ctx
+    };
+    evaluate_rule(rule_id, &abyss_ctx, rule)
+}
+
+fn evaluate_rule(
+    rule_id: AlertRuleId,
+    ctx: &TriggerContext,
+    rule: &AlertRuleConfig,
+) -> Option<String> {
+    let ignore_vorton = rule.ignore_vorton;
     match rule_id {
         AlertRuleId::EnvironmentalDamage => evaluate_environmental_damage(ctx),
         AlertRuleId::FriendlyFire => evaluate_friendly_fire(ctx, ignore_vorton),
@@ -37,6 +72,9 @@ pub fn evaluate_trigger(
         AlertRuleId::NeutSensitiveNeuted => evaluate_neut_sensitive(ctx),
         AlertRuleId::CapacitorFailure => evaluate_capacitor_failure(ctx),
         AlertRuleId::LogiNeuted => evaluate_logi_neuted(ctx),
+        AlertRuleId::EwarApplied => evaluate_ewar_applied(ctx),
+        AlertRuleId::IncomingDpsSpike => evaluate_incoming_dps_spike(ctx, rule.dps_threshold),
+        AlertRuleId::LowHealthWarning => evaluate_low_health(ctx),
     }
 }
 
@@ -167,6 +205,24 @@ fn evaluate_logi_taking_damage(ctx: &TriggerContext, ignore_vorton: bool) -> Opt
     None
 }
 
+/// Alert when a tracked character's hull is being hit
+fn evaluate_low_health(ctx: &TriggerContext) -> Option<String> {
+    ctx.combat_events
+        .iter()
+        .find(|event| {
+            event.event_type == EventType::Damage
+                && event.incoming
+                && event.damage_layer == Some(DamageLayer::Hull)
+                && ctx.tracked_characters.contains(&event.character)
+        })
+        .map(|event| {
+            format!(
+                "HULL DAMAGE! {} hit by {} for {:.0}",
+                event.character, event.source, event.amount
+            )
+        })
+}
+
 /// Alert when a neut-sensitive character is being neuted
 fn evaluate_neut_sensitive(ctx: &TriggerContext) -> Option<String> {
     for event in ctx.combat_events {
@@ -208,6 +264,53 @@ fn evaluate_logi_neuted(ctx: &TriggerContext) -> Option<String> {
     None
 }
 
+/// Alert when a tracked character gets warp scrambled or webbed
+fn evaluate_ewar_applied(ctx: &TriggerContext) -> Option<String> {
+    for event in ctx.combat_events {
+        let EventType::Ewar { kind } = event.event_type else {
+            continue;
+        };
+        if !event.incoming || !matches!(kind, EwarKind::WarpScramble | EwarKind::StasisWeb) {
+            continue;
+        }
+        if !ctx.tracked_characters.contains(&event.character) {
+            continue;
+        }
+
+        return Some(format!(
+            "{} on {} from {}!",
+            kind.label().to_uppercase(),
+            event.character,
+            event.source
+        ));
+    }
+    None
+}
+
+/// Alert when a character's incoming damage over the context window exceeds
+/// `threshold` DPS
+fn evaluate_incoming_dps_spike(ctx: &TriggerContext, threshold: f32) -> Option<String> {
+    let window_secs = ctx.window.as_secs_f32().max(1.0);
+
+    let mut incoming: HashMap<&str, f32> = HashMap::new();
+    for event in ctx.combat_events {
+        if event.event_type == EventType::Damage && event.incoming {
+            *incoming.entry(event.character.as_str()).or_default() += event.amount;
+        }
+    }
+
+    let (character, dps) = incoming
+        .into_iter()
+        .map(|(character, total)| (character, total / window_secs))
+        .filter(|(_, dps)| *dps > threshold)
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    Some(format!(
+        "INCOMING DPS SPIKE on {}! {:.0} DPS",
+        character, dps
+    ))
+}
+
 /// Alert when a module fails to activate due to insufficient capacitor
 fn evaluate_capacitor_failure(ctx: &TriggerContext) -> Option<String> {
     for event in ctx.notify_events {
@@ -240,6 +343,13 @@ mod tests {
         )
     }
 
+    fn rule(ignore_vorton: bool) -> AlertRuleConfig {
+        AlertRuleConfig {
+            ignore_vorton,
+            ..AlertRuleConfig::default()
+        }
+    }
+
     fn make_combat_event(
         event_type: EventType,
         incoming: bool,
@@ -258,6 +368,10 @@ mod tests {
             incoming,
             character: character.to_string(),
             event_type,
+            hit_quality: None,
+            via_drone: false,
+            self_inflicted: false,
+            damage_layer: None,
         }
     }
 
@@ -281,9 +395,11 @@ mod tests {
             tracked_characters: &tracked,
             logi_characters: &logi,
             neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(1),
         };
 
-        let result = evaluate_trigger(AlertRuleId::EnvironmentalDamage, &ctx, false);
+        let result = evaluate_trigger(AlertRuleId::EnvironmentalDamage, &ctx, &rule(false));
         assert!(result.is_some());
         assert!(result.unwrap().contains("Unstable Abyssal Depths"));
     }
@@ -311,9 +427,11 @@ mod tests {
             tracked_characters: &tracked,
             logi_characters: &logi,
             neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(1),
         };
 
-        let result = evaluate_trigger(AlertRuleId::FriendlyFire, &ctx, true);
+        let result = evaluate_trigger(AlertRuleId::FriendlyFire, &ctx, &rule(true));
         assert!(result.is_some());
         assert!(result.unwrap().contains("Friendly fire"));
     }
@@ -341,9 +459,11 @@ mod tests {
             tracked_characters: &tracked,
             logi_characters: &logi,
             neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(1),
         };
 
-        let result = evaluate_trigger(AlertRuleId::FriendlyFire, &ctx, true);
+        let result = evaluate_trigger(AlertRuleId::FriendlyFire, &ctx, &rule(true));
         assert!(
             result.is_none(),
             "Vorton damage should not trigger friendly fire"
@@ -372,9 +492,11 @@ mod tests {
             tracked_characters: &tracked,
             logi_characters: &logi,
             neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(1),
         };
 
-        let result = evaluate_trigger(AlertRuleId::LogiTakingDamage, &ctx, true);
+        let result = evaluate_trigger(AlertRuleId::LogiTakingDamage, &ctx, &rule(true));
         assert!(result.is_some());
         assert!(result.unwrap().contains("LOGI TAKING DAMAGE"));
     }
@@ -401,9 +523,11 @@ mod tests {
             tracked_characters: &tracked,
             logi_characters: &logi,
             neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(1),
         };
 
-        let result = evaluate_trigger(AlertRuleId::LogiTakingDamage, &ctx, true);
+        let result = evaluate_trigger(AlertRuleId::LogiTakingDamage, &ctx, &rule(true));
         assert!(
             result.is_none(),
             "Vorton damage to logi should not trigger alert when ignore_vorton is true"
@@ -428,9 +552,11 @@ mod tests {
             tracked_characters: &tracked,
             logi_characters: &logi,
             neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(1),
         };
 
-        let result = evaluate_trigger(AlertRuleId::CapacitorFailure, &ctx, false);
+        let result = evaluate_trigger(AlertRuleId::CapacitorFailure, &ctx, &rule(false));
         assert!(result.is_some());
         assert!(result.unwrap().contains("CAP FAILURE"));
     }
@@ -457,9 +583,11 @@ mod tests {
             tracked_characters: &tracked,
             logi_characters: &logi,
             neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(1),
         };
 
-        let result = evaluate_trigger(AlertRuleId::LogiNeuted, &ctx, false);
+        let result = evaluate_trigger(AlertRuleId::LogiNeuted, &ctx, &rule(false));
         assert!(result.is_some());
         assert!(result.unwrap().contains("LOGI NEUTED"));
     }
@@ -487,14 +615,16 @@ mod tests {
             tracked_characters: &tracked,
             logi_characters: &logi,
             neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(1),
         };
 
         // LogiNeuted should fire
-        let result_logi = evaluate_trigger(AlertRuleId::LogiNeuted, &ctx, false);
+        let result_logi = evaluate_trigger(AlertRuleId::LogiNeuted, &ctx, &rule(false));
         assert!(result_logi.is_some(), "Logi alert should fire");
 
         // NeutSensitiveNeuted should NOT fire (because it's a logi char)
-        let result_neut = evaluate_trigger(AlertRuleId::NeutSensitiveNeuted, &ctx, false);
+        let result_neut = evaluate_trigger(AlertRuleId::NeutSensitiveNeuted, &ctx, &rule(false));
         assert!(
             result_neut.is_none(),
             "Neut sensitive alert should NOT fire for logi pilot"
@@ -538,10 +668,12 @@ mod tests {
             tracked_characters: &tracked,
             logi_characters: &logi,
             neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(1),
         };
 
         // NeutSensitiveNeuted should fire for NeutPilot
-        let result_neut = evaluate_trigger(AlertRuleId::NeutSensitiveNeuted, &ctx, false);
+        let result_neut = evaluate_trigger(AlertRuleId::NeutSensitiveNeuted, &ctx, &rule(false));
         assert!(
             result_neut.is_some(),
             "Neut sensitive alert should fire for NeutPilot"
@@ -552,10 +684,145 @@ mod tests {
         );
 
         // LogiNeuted should fire for LogiPilot
-        let result_logi = evaluate_trigger(AlertRuleId::LogiNeuted, &ctx, false);
+        let result_logi = evaluate_trigger(AlertRuleId::LogiNeuted, &ctx, &rule(false));
         assert!(
             result_logi.is_some(),
             "Logi alert should fire for LogiPilot"
         );
     }
+
+    #[test]
+    fn test_ewar_applied_triggers_on_scram_and_web_only() {
+        let (mut combat, notify, mut tracked, logi, neut) = empty_context();
+        tracked.insert("MyPilot".to_string());
+
+        let ewar = |kind: EwarKind| {
+            make_combat_event(
+                EventType::Ewar { kind },
+                true,
+                "Starving Damavik",
+                "MyPilot",
+                "MyPilot",
+                kind.label(),
+                0.0,
+            )
+        };
+        combat.push(ewar(EwarKind::TargetPainter));
+
+        let ctx = TriggerContext {
+            combat_events: &combat,
+            notify_events: &notify,
+            tracked_characters: &tracked,
+            logi_characters: &logi,
+            neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(1),
+        };
+        assert!(evaluate_trigger(AlertRuleId::EwarApplied, &ctx, &rule(true)).is_none());
+
+        combat.push(ewar(EwarKind::WarpScramble));
+        let ctx = TriggerContext {
+            combat_events: &combat,
+            notify_events: &notify,
+            tracked_characters: &tracked,
+            logi_characters: &logi,
+            neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(1),
+        };
+        let result = evaluate_trigger(AlertRuleId::EwarApplied, &ctx, &rule(true)).unwrap();
+        assert!(result.contains("WARP SCRAMBLE on MyPilot"));
+    }
+
+    #[test]
+    fn test_incoming_dps_spike_fires_above_threshold() {
+        let (mut combat, notify, tracked, logi, neut) = empty_context();
+        let hit = |amount| {
+            make_combat_event(
+                EventType::Damage,
+                true,
+                "Striking Kikimora",
+                "MyPilot",
+                "MyPilot",
+                "Entropic Disintegrator",
+                amount,
+            )
+        };
+        let threshold = AlertRuleConfig {
+            dps_threshold: 400.0,
+            ..AlertRuleConfig::default()
+        };
+
+        // 1500 damage over 5s is 300 DPS: below threshold
+        combat.extend([hit(500.0), hit(500.0), hit(500.0)]);
+        let ctx = TriggerContext {
+            combat_events: &combat,
+            notify_events: &notify,
+            tracked_characters: &tracked,
+            logi_characters: &logi,
+            neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(5),
+        };
+        assert!(evaluate_trigger(AlertRuleId::IncomingDpsSpike, &ctx, &threshold).is_none());
+
+        // Burst to 3000 damage over 5s is 600 DPS
+        combat.extend([hit(750.0), hit(750.0)]);
+        let ctx = TriggerContext {
+            combat_events: &combat,
+            notify_events: &notify,
+            tracked_characters: &tracked,
+            logi_characters: &logi,
+            neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(5),
+        };
+        let result = evaluate_trigger(AlertRuleId::IncomingDpsSpike, &ctx, &threshold).unwrap();
+        assert!(result.contains("MyPilot"));
+        assert!(result.contains("600 DPS"));
+    }
+
+    #[test]
+    fn test_low_health_fires_on_tracked_hull_hits() {
+        let (mut combat, notify, mut tracked, logi, neut) = empty_context();
+        tracked.insert("MyPilot".to_string());
+
+        let mut armor_hit = make_combat_event(
+            EventType::Damage,
+            true,
+            "Striking Damavik",
+            "MyPilot",
+            "MyPilot",
+            "Striking Damavik",
+            120.0,
+        );
+        armor_hit.damage_layer = Some(DamageLayer::Armor);
+        combat.push(armor_hit.clone());
+
+        let ctx = TriggerContext {
+            combat_events: &combat,
+            notify_events: &notify,
+            tracked_characters: &tracked,
+            logi_characters: &logi,
+            neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(1),
+        };
+        assert!(evaluate_trigger(AlertRuleId::LowHealthWarning, &ctx, &rule(false)).is_none());
+
+        let mut hull_hit = armor_hit;
+        hull_hit.damage_layer = Some(DamageLayer::Hull);
+        combat.push(hull_hit);
+        let ctx = TriggerContext {
+            combat_events: &combat,
+            notify_events: &notify,
+            tracked_characters: &tracked,
+            logi_characters: &logi,
+            neut_sensitive_characters: &neut,
+            characters_in_abyss: &HashSet::new(),
+            window: Duration::from_secs(1),
+        };
+        let result = evaluate_trigger(AlertRuleId::LowHealthWarning, &ctx, &rule(false)).unwrap();
+        assert!(result.contains("MyPilot"));
+    }
 }
@@ -1,356 +1,261 @@
-use std::collections::HashMap;
+use regex::Regex;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
 use std::time::Duration;
 
-use super::model::{CombatEvent, DpsSample, EntityName, EventType, WeaponName};
+use super::inline_bookmarks::BookmarkType;
+use super::model::{
+    classify_attacker, format_number, AttackerKind, CombatAction, CombatEvent, DpsSample,
+    EntityName, EventType, NumberFormat, QualityCounts, RoomStats, RunSummary, SurvivabilityBand,
+    SurvivabilityScore, TargetHit, WeaponCategory, WeaponName,
+};
+use super::parser::is_drone_weapon;
 
-pub fn compute_dps_series(
-    events: &[CombatEvent],
-    window: Duration,
-    end: Duration,
-) -> Vec<DpsSample> {
-    if events.is_empty() {
-        return Vec::new();
-    }
-
-    let window_millis = window.as_millis().max(1) as u64;
-    let step_millis: u64 = 1_000;
-
-    let max_event_timestamp_millis = events
-        .iter()
-        .map(|event| event.timestamp.as_millis() as u64)
-        .max()
-        .unwrap_or(0);
-    let end_millis = end.as_millis() as u64;
-    let max_millis = std::cmp::max(max_event_timestamp_millis, end_millis);
-
-    // Keep at most this much history in the DPS series.
-    const HISTORY_MILLIS: u64 = 60_000;
+/// Spacing between DPS samples.
+const STEP_MILLIS: u64 = 1_000;
 
-    let (start_millis, slot_count) = if max_millis <= HISTORY_MILLIS {
-        (0, (max_millis / step_millis + 1) as usize)
-    } else {
-        let start = max_millis - HISTORY_MILLIS;
-        let slots = (HISTORY_MILLIS / step_millis + 1) as usize;
-        (start, slots)
-    };
+/// Keep at most this much history in the DPS series.
+const HISTORY_MILLIS: u64 = 60_000;
 
-    let window_seconds = window.as_secs_f32().max(f32::EPSILON);
+fn event_millis(event: &CombatEvent) -> u64 {
+    event.timestamp.as_millis() as u64
+}
 
-    let mut samples = Vec::with_capacity(slot_count);
-    for index in 0..slot_count {
-        let time = Duration::from_millis(start_millis + index as u64 * step_millis);
-        samples.push(DpsSample {
-            time,
-            outgoing_dps: 0.0,
-            incoming_dps: 0.0,
-            outgoing_hps: 0.0,
-            incoming_hps: 0.0,
-            outgoing_cap: 0.0,
-            incoming_cap: 0.0,
-            outgoing_neut: 0.0,
-            incoming_neut: 0.0,
-            outgoing_by_weapon: HashMap::<WeaponName, f32>::new(),
-            outgoing_by_target: HashMap::<EntityName, f32>::new(),
-            incoming_by_source: HashMap::<EntityName, f32>::new(),
-            outgoing_by_character: HashMap::<String, f32>::new(),
-            incoming_by_character: HashMap::<String, f32>::new(),
-            outgoing_by_char_weapon: HashMap::<String, HashMap<WeaponName, f32>>::new(),
-            outgoing_by_char_target: HashMap::<String, HashMap<EntityName, f32>>::new(),
-            combat_actions_by_character: HashMap::<String, Vec<super::model::CombatAction>>::new(),
-        });
+/// Subtract `amount` from a keyed total, dropping the key once it reaches zero.
+fn subtract<K: Hash + Eq>(map: &mut HashMap<K, f32>, key: &K, amount: f32) {
+    if let Some(value) = map.get_mut(key) {
+        *value -= amount;
+        if *value <= 0.0 {
+            map.remove(key);
+        }
     }
+}
 
-    let global_start_cutoff = Duration::from_millis(start_millis.saturating_sub(window_millis));
-
-    let mut start_idx = events.partition_point(|e| e.timestamp < global_start_cutoff);
-    let mut end_idx = start_idx;
+/// Subtract from a per-character nested total, dropping empty characters.
+fn subtract_nested<K: Hash + Eq>(
+    map: &mut HashMap<String, HashMap<K, f32>>,
+    character: &str,
+    key: &K,
+    amount: f32,
+) {
+    if let Some(inner) = map.get_mut(character) {
+        subtract(inner, key, amount);
+        if inner.is_empty() {
+            map.remove(character);
+        }
+    }
+}
 
-    let mut outgoing_sum = 0.0_f32;
-    let mut incoming_sum = 0.0_f32;
-    let mut outgoing_hps_sum = 0.0_f32;
-    let mut incoming_hps_sum = 0.0_f32;
-    let mut outgoing_cap_sum = 0.0_f32;
-    let mut incoming_cap_sum = 0.0_f32;
-    let mut outgoing_neut_sum = 0.0_f32;
-    let mut incoming_neut_sum = 0.0_f32;
-
-    let mut outgoing_by_weapon_damage: HashMap<WeaponName, f32> = HashMap::new();
-    let mut outgoing_by_target_damage: HashMap<EntityName, f32> = HashMap::new();
-    let mut incoming_by_source_damage: HashMap<EntityName, f32> = HashMap::new();
-    let mut incoming_by_character_damage: HashMap<String, f32> = HashMap::new();
-    let mut outgoing_by_character_damage: HashMap<String, f32> = HashMap::new();
-    let mut outgoing_by_char_weapon_damage: HashMap<String, HashMap<WeaponName, f32>> =
-        HashMap::new();
-    let mut outgoing_by_char_target_damage: HashMap<String, HashMap<EntityName, f32>> =
-        HashMap::new();
+/// Running totals for the events currently inside a sliding window.
+#[derive(Default)]
+struct WindowSums {
+    outgoing: f32,
+    incoming: f32,
+    outgoing_hps: f32,
+    incoming_hps: f32,
+    outgoing_cap: f32,
+    incoming_cap: f32,
+    outgoing_neut: f32,
+    incoming_neut: f32,
+    outgoing_by_weapon: HashMap<WeaponName, f32>,
+    outgoing_by_target: HashMap<EntityName, f32>,
+    incoming_by_source: HashMap<EntityName, f32>,
+    incoming_by_character: HashMap<String, f32>,
+    outgoing_by_character: HashMap<String, f32>,
+    outgoing_by_char_weapon: HashMap<String, HashMap<WeaponName, f32>>,
+    outgoing_by_char_target: HashMap<String, HashMap<EntityName, f32>>,
     // Key: (weapon, target, EventType, incoming) - target is "" for incoming events
-    let mut char_actions_map: HashMap<String, HashMap<(String, String, EventType, bool), f32>> =
-        HashMap::new();
+    char_actions: HashMap<String, HashMap<(String, String, EventType, bool), f32>>,
+}
 
-    for (i, sample) in samples.iter_mut().enumerate() {
-        let center_millis = start_millis + i as u64 * step_millis;
-        let window_start_millis = center_millis.saturating_sub(window_millis);
+impl WindowSums {
+    fn action_key(event: &CombatEvent) -> (String, String, EventType, bool) {
+        // For outgoing, include target in key for per-target breakdown
+        let target = if event.incoming {
+            String::new()
+        } else {
+            event.target.clone()
+        };
+        (
+            event.weapon.clone(),
+            target,
+            event.event_type.clone(),
+            event.incoming,
+        )
+    }
 
-        // Add events entering the window (from the future relative to window start)
-        while end_idx < events.len()
-            && events[end_idx].timestamp.as_millis() as u64 <= center_millis
-        {
-            let event = &events[end_idx];
-            if event.incoming {
-                match event.event_type {
-                    EventType::Damage => {
-                        incoming_sum += event.amount;
-                        *incoming_by_source_damage
-                            .entry(event.source.clone())
-                            .or_insert(0.0) += event.amount;
-                        *incoming_by_character_damage
-                            .entry(event.character.clone())
-                            .or_insert(0.0) += event.amount;
-                    }
-                    EventType::Repair => incoming_hps_sum += event.amount,
-                    EventType::Capacitor => incoming_cap_sum += event.amount,
-                    EventType::Neut => incoming_neut_sum += event.amount,
+    fn add(&mut self, event: &CombatEvent) {
+        // EWAR has no magnitude; it would only clutter the combat actions
+        if matches!(event.event_type, EventType::Ewar { .. }) {
+            return;
+        }
+        *self
+            .char_actions
+            .entry(event.character.clone())
+            .or_default()
+            .entry(Self::action_key(event))
+            .or_insert(0.0) += event.amount;
+
+        if event.incoming {
+            match event.event_type {
+                EventType::Damage => {
+                    self.incoming += event.amount;
+                    *self
+                        .incoming_by_source
+                        .entry(event.source.clone())
+                        .or_insert(0.0) += event.amount;
+                    *self
+                        .incoming_by_character
+                        .entry(event.character.clone())
+                        .or_insert(0.0) += event.amount;
                 }
-                *char_actions_map
-                    .entry(event.character.clone())
-                    .or_default()
-                    .entry((
-                        event.weapon.clone(),
-                        String::new(),
-                        event.event_type.clone(),
-                        true,
-                    ))
-                    .or_insert(0.0) += event.amount;
-            } else {
-                // Outgoing logic
-                // For outgoing, include target in key for per-target breakdown
-                *char_actions_map
-                    .entry(event.character.clone())
-                    .or_default()
-                    .entry((
-                        event.weapon.clone(),
-                        event.target.clone(),
-                        event.event_type.clone(),
-                        false,
-                    ))
-                    .or_insert(0.0) += event.amount;
-
-                match event.event_type {
-                    EventType::Damage => {
-                        outgoing_sum += event.amount;
-                        *outgoing_by_weapon_damage
-                            .entry(event.weapon.clone())
-                            .or_insert(0.0) += event.amount;
-                        *outgoing_by_target_damage
-                            .entry(event.target.clone())
-                            .or_insert(0.0) += event.amount;
-                        *outgoing_by_character_damage
-                            .entry(event.character.clone())
-                            .or_insert(0.0) += event.amount;
-                        *outgoing_by_char_weapon_damage
-                            .entry(event.character.clone())
-                            .or_default()
-                            .entry(event.weapon.clone())
-                            .or_insert(0.0) += event.amount;
-                        *outgoing_by_char_target_damage
-                            .entry(event.character.clone())
-                            .or_default()
-                            .entry(event.target.clone())
-                            .or_insert(0.0) += event.amount;
-                    }
-                    EventType::Repair => {
-                        outgoing_hps_sum += event.amount;
-                    }
-                    EventType::Capacitor => {
-                        outgoing_cap_sum += event.amount;
-                    }
-                    EventType::Neut => {
-                        outgoing_neut_sum += event.amount;
-                    }
+                EventType::Repair => self.incoming_hps += event.amount,
+                EventType::Capacitor => self.incoming_cap += event.amount,
+                EventType::Neut => self.incoming_neut += event.amount,
+                EventType::Ewar { .. } => {}
+            }
+        } else {
+            match event.event_type {
+                EventType::Damage => {
+                    self.outgoing += event.amount;
+                    *self
+                        .outgoing_by_weapon
+                        .entry(event.weapon.clone())
+                        .or_insert(0.0) += event.amount;
+                    *self
+                        .outgoing_by_target
+                        .entry(event.target.clone())
+                        .or_insert(0.0) += event.amount;
+                    *self
+                        .outgoing_by_character
+                        .entry(event.character.clone())
+                        .or_insert(0.0) += event.amount;
+                    *self
+                        .outgoing_by_char_weapon
+                        .entry(event.character.clone())
+                        .or_default()
+                        .entry(event.weapon.clone())
+                        .or_insert(0.0) += event.amount;
+                    *self
+                        .outgoing_by_char_target
+                        .entry(event.character.clone())
+                        .or_default()
+                        .entry(event.target.clone())
+                        .or_insert(0.0) += event.amount;
                 }
+                EventType::Repair => self.outgoing_hps += event.amount,
+                EventType::Capacitor => self.outgoing_cap += event.amount,
+                EventType::Neut => self.outgoing_neut += event.amount,
+                EventType::Ewar { .. } => {}
             }
-            end_idx += 1;
         }
+    }
 
-        // Remove events leaving the window (falling behind the start time)
-        while start_idx < end_idx
-            && (events[start_idx].timestamp.as_millis() as u64) < window_start_millis
-        {
-            let event = &events[start_idx];
+    fn remove(&mut self, event: &CombatEvent) {
+        if matches!(event.event_type, EventType::Ewar { .. }) {
+            return;
+        }
+        // Clean up combat actions map - runs for BOTH incoming and outgoing events
+        subtract_nested(
+            &mut self.char_actions,
+            &event.character,
+            &Self::action_key(event),
+            event.amount,
+        );
 
-            // Clean up combat actions map - runs for BOTH incoming and outgoing events
-            if let Some(char_actions) = char_actions_map.get_mut(&event.character) {
-                let target_key = if event.incoming {
-                    String::new()
-                } else {
-                    event.target.clone()
-                };
-                let key = (
-                    event.weapon.clone(),
-                    target_key,
-                    event.event_type.clone(),
-                    event.incoming,
-                );
-                if let Some(val) = char_actions.get_mut(&key) {
-                    *val -= event.amount;
-                    if *val <= 0.0 {
-                        char_actions.remove(&key);
-                    }
-                }
-                if char_actions.is_empty() {
-                    char_actions_map.remove(&event.character);
+        if event.incoming {
+            match event.event_type {
+                EventType::Damage => {
+                    self.incoming -= event.amount;
+                    subtract(&mut self.incoming_by_source, &event.source, event.amount);
+                    subtract(
+                        &mut self.incoming_by_character,
+                        &event.character,
+                        event.amount,
+                    );
                 }
+                EventType::Repair => self.incoming_hps -= event.amount,
+                EventType::Capacitor => self.incoming_cap -= event.amount,
+                EventType::Neut => self.incoming_neut -= event.amount,
+                EventType::Ewar { .. } => {}
             }
-
-            if event.incoming {
-                match event.event_type {
-                    EventType::Damage => {
-                        incoming_sum -= event.amount;
-                        if let Some(value) = incoming_by_source_damage.get_mut(&event.source) {
-                            *value -= event.amount;
-                            if *value <= 0.0 {
-                                incoming_by_source_damage.remove(&event.source);
-                            }
-                        }
-                        if let Some(value) = incoming_by_character_damage.get_mut(&event.character)
-                        {
-                            *value -= event.amount;
-                            if *value <= 0.0 {
-                                incoming_by_character_damage.remove(&event.character);
-                            }
-                        }
-                    }
-                    EventType::Repair => incoming_hps_sum -= event.amount,
-                    EventType::Capacitor => incoming_cap_sum -= event.amount,
-                    EventType::Neut => incoming_neut_sum -= event.amount,
-                }
-            } else {
-                match event.event_type {
-                    EventType::Damage => {
-                        outgoing_sum -= event.amount;
-                        if let Some(value) = outgoing_by_weapon_damage.get_mut(&event.weapon) {
-                            *value -= event.amount;
-                            if *value <= 0.0 {
-                                outgoing_by_weapon_damage.remove(&event.weapon);
-                            }
-                        }
-                        if let Some(value) = outgoing_by_target_damage.get_mut(&event.target) {
-                            *value -= event.amount;
-                            if *value <= 0.0 {
-                                outgoing_by_target_damage.remove(&event.target);
-                            }
-                        }
-                        if let Some(value) = outgoing_by_character_damage.get_mut(&event.character)
-                        {
-                            *value -= event.amount;
-                            if *value <= 0.0 {
-                                outgoing_by_character_damage.remove(&event.character);
-                            }
-                        }
-                        if let Some(char_weapons) =
-                            outgoing_by_char_weapon_damage.get_mut(&event.character)
-                        {
-                            if let Some(damage) = char_weapons.get_mut(&event.weapon) {
-                                *damage -= event.amount;
-                                if *damage <= 0.0 {
-                                    char_weapons.remove(&event.weapon);
-                                }
-                            }
-                            if char_weapons.is_empty() {
-                                outgoing_by_char_weapon_damage.remove(&event.character);
-                            }
-                        }
-                        if let Some(char_targets) =
-                            outgoing_by_char_target_damage.get_mut(&event.character)
-                        {
-                            if let Some(damage) = char_targets.get_mut(&event.target) {
-                                *damage -= event.amount;
-                                if *damage <= 0.0 {
-                                    char_targets.remove(&event.target);
-                                }
-                            }
-                            if char_targets.is_empty() {
-                                outgoing_by_char_target_damage.remove(&event.character);
-                            }
-                        }
-                    }
-                    EventType::Repair => {
-                        outgoing_hps_sum -= event.amount;
-                    }
-                    EventType::Capacitor => {
-                        outgoing_cap_sum -= event.amount;
-                    }
-                    EventType::Neut => {
-                        outgoing_neut_sum -= event.amount;
-                    }
+        } else {
+            match event.event_type {
+                EventType::Damage => {
+                    self.outgoing -= event.amount;
+                    subtract(&mut self.outgoing_by_weapon, &event.weapon, event.amount);
+                    subtract(&mut self.outgoing_by_target, &event.target, event.amount);
+                    subtract(
+                        &mut self.outgoing_by_character,
+                        &event.character,
+                        event.amount,
+                    );
+                    subtract_nested(
+                        &mut self.outgoing_by_char_weapon,
+                        &event.character,
+                        &event.weapon,
+                        event.amount,
+                    );
+                    subtract_nested(
+                        &mut self.outgoing_by_char_target,
+                        &event.character,
+                        &event.target,
+                        event.amount,
+                    );
                 }
+                EventType::Repair => self.outgoing_hps -= event.amount,
+                EventType::Capacitor => self.outgoing_cap -= event.amount,
+                EventType::Neut => self.outgoing_neut -= event.amount,
+                EventType::Ewar { .. } => {}
             }
-            start_idx += 1;
         }
+    }
 
-        sample.outgoing_dps = outgoing_sum / window_seconds;
-        sample.incoming_dps = incoming_sum / window_seconds;
-        sample.outgoing_hps = outgoing_hps_sum / window_seconds;
-        sample.incoming_hps = incoming_hps_sum / window_seconds;
-        sample.outgoing_cap = outgoing_cap_sum / window_seconds;
-        sample.incoming_cap = incoming_cap_sum / window_seconds;
-        sample.outgoing_neut = outgoing_neut_sum / window_seconds;
-        sample.incoming_neut = incoming_neut_sum / window_seconds;
+    fn sample(&self, time: Duration, window_seconds: f32) -> DpsSample {
+        let rate = |map: &HashMap<String, f32>| -> HashMap<String, f32> {
+            map.iter()
+                .map(|(key, damage)| (key.clone(), damage / window_seconds))
+                .collect()
+        };
+        let nested_rate =
+            |map: &HashMap<String, HashMap<String, f32>>| -> HashMap<String, HashMap<String, f32>> {
+                map.iter()
+                    .map(|(character, inner)| (character.clone(), rate(inner)))
+                    .collect()
+            };
 
-        sample.outgoing_by_weapon = outgoing_by_weapon_damage
-            .iter()
-            .map(|(weapon, damage)| (weapon.clone(), damage / window_seconds))
-            .collect();
-        sample.outgoing_by_target = outgoing_by_target_damage
-            .iter()
-            .map(|(target, damage)| (target.clone(), damage / window_seconds))
-            .collect();
-        sample.incoming_by_source = incoming_by_source_damage
-            .iter()
-            .map(|(source, damage)| (source.clone(), damage / window_seconds))
-            .collect();
-        sample.outgoing_by_character = outgoing_by_character_damage
-            .iter()
-            .map(|(character, damage)| (character.clone(), damage / window_seconds))
-            .collect();
-        sample.incoming_by_character = incoming_by_character_damage
-            .iter()
-            .map(|(character, damage)| (character.clone(), damage / window_seconds))
-            .collect();
-        sample.outgoing_by_char_weapon = outgoing_by_char_weapon_damage
-            .iter()
-            .map(|(character, weapons)| {
-                (
-                    character.clone(),
-                    weapons
-                        .iter()
-                        .map(|(weapon, damage)| (weapon.clone(), damage / window_seconds))
-                        .collect(),
-                )
-            })
-            .collect();
-        sample.outgoing_by_char_target = outgoing_by_char_target_damage
-            .iter()
-            .map(|(character, targets)| {
-                (
-                    character.clone(),
-                    targets
-                        .iter()
-                        .map(|(target, damage)| (target.clone(), damage / window_seconds))
-                        .collect(),
-                )
-            })
-            .collect();
+        DpsSample {
+            time,
+            outgoing_dps: self.outgoing / window_seconds,
+            incoming_dps: self.incoming / window_seconds,
+            outgoing_hps: self.outgoing_hps / window_seconds,
+            incoming_hps: self.incoming_hps / window_seconds,
+            outgoing_cap: self.outgoing_cap / window_seconds,
+            incoming_cap: self.incoming_cap / window_seconds,
+            outgoing_neut: self.outgoing_neut / window_seconds,
+            incoming_neut: self.incoming_neut / window_seconds,
+            incoming_npc_dps: 0.0,
+            incoming_player_dps: 0.0,
+            incoming_environmental_dps: 0.0,
+            outgoing_by_weapon: rate(&self.outgoing_by_weapon),
+            outgoing_by_target: rate(&self.outgoing_by_target),
+            incoming_by_source: rate(&self.incoming_by_source),
+            outgoing_by_character: rate(&self.outgoing_by_character),
+            incoming_by_character: rate(&self.incoming_by_character),
+            outgoing_by_char_weapon: nested_rate(&self.outgoing_by_char_weapon),
+            outgoing_by_char_target: nested_rate(&self.outgoing_by_char_target),
+            combat_actions_by_character: self.combat_actions(window_seconds),
+        }
+    }
 
-        // Build combat_actions_by_character, aggregating targets per weapon
-        sample.combat_actions_by_character = char_actions_map
+    /// Build combat_actions_by_character, aggregating targets per weapon
+    fn combat_actions(&self, window_seconds: f32) -> HashMap<String, Vec<CombatAction>> {
+        self.char_actions
             .iter()
             .map(|(character, actions)| {
                 // Group by (weapon, EventType, incoming) and collect targets
-                let mut weapon_groups: HashMap<
-                    (String, EventType, bool),
-                    (f32, Vec<super::model::TargetHit>),
-                > = HashMap::new();
+                let mut weapon_groups: HashMap<(String, EventType, bool), (f32, Vec<TargetHit>)> =
+                    HashMap::new();
                 for ((weapon, target, event_type, incoming), value) in actions {
                     let entry = weapon_groups
                         .entry((weapon.clone(), event_type.clone(), *incoming))
@@ -358,7 +263,7 @@ pub fn compute_dps_series(
                     entry.0 += value;
                     // Only add target breakdown for outgoing events with non-empty target
                     if !incoming && !target.is_empty() {
-                        entry.1.push(super::model::TargetHit {
+                        entry.1.push(TargetHit {
                             target: target.clone(),
                             value: value / window_seconds,
                         });
@@ -375,7 +280,7 @@ pub fn compute_dps_series(
                                     .partial_cmp(&a.value)
                                     .unwrap_or(std::cmp::Ordering::Equal)
                             });
-                            super::model::CombatAction {
+                            CombatAction {
                                 name,
                                 action_type,
                                 value: total / window_seconds,
@@ -386,19 +291,783 @@ pub fn compute_dps_series(
                         .collect(),
                 )
             })
-            .collect();
+            .collect()
+    }
+}
+
+pub fn compute_dps_series(
+    events: &[CombatEvent],
+    window: Duration,
+    end: Duration,
+) -> Vec<DpsSample> {
+    if events.is_empty() {
+        return Vec::new();
+    }
+
+    let window_millis = window.as_millis().max(1) as u64;
+
+    let max_event_timestamp_millis = events.iter().map(event_millis).max().unwrap_or(0);
+    let end_millis = end.as_millis() as u64;
+    let max_millis = std::cmp::max(max_event_timestamp_millis, end_millis);
+
+    let (start_millis, slot_count) = if max_millis <= HISTORY_MILLIS {
+        (0, (max_millis / STEP_MILLIS + 1) as usize)
+    } else {
+        let start = max_millis - HISTORY_MILLIS;
+        let slots = (HISTORY_MILLIS / STEP_MILLIS + 1) as usize;
+        (start, slots)
+    };
+
+    let window_seconds = window.as_secs_f32().max(f32::EPSILON);
+
+    let global_start_cutoff = Duration::from_millis(start_millis.saturating_sub(window_millis));
+
+    let mut start_idx = events.partition_point(|e| e.timestamp < global_start_cutoff);
+    let mut end_idx = start_idx;
+    let mut sums = WindowSums::default();
+
+    let mut samples = Vec::with_capacity(slot_count);
+    for index in 0..slot_count {
+        let center_millis = start_millis + index as u64 * STEP_MILLIS;
+        let window_start_millis = center_millis.saturating_sub(window_millis);
+
+        // Add events entering the window (from the future relative to window start)
+        while end_idx < events.len() && event_millis(&events[end_idx]) <= center_millis {
+            sums.add(&events[end_idx]);
+            end_idx += 1;
+        }
+
+        // Remove events leaving the window (falling behind the start time)
+        while start_idx < end_idx && event_millis(&events[start_idx]) < window_start_millis {
+            sums.remove(&events[start_idx]);
+            start_idx += 1;
+        }
+
+        samples.push(sums.sample(Duration::from_millis(center_millis), window_seconds));
     }
 
     samples
 }
 
+/// Default normalizer pattern: the qualifiers Abyssal NPCs get in front of
+/// their hull name ("Starving Damavik", "Vila Damavik", ...).
+pub const DEFAULT_NPC_QUALIFIERS: &str = r"^(?i)(?:(?:Starving|Vila|Blinding|Anchoring|Ghosting|Harrowing|Renewing|Spearing|Striking|Tangling|Shining|Elite)\s+)+";
+
+/// Collapses NPC name variants into a base type so target breakdowns don't
+/// fragment. Each pattern's matches are removed from the name in order.
+#[derive(Debug, Clone)]
+pub struct NameNormalizer {
+    patterns: Vec<Regex>,
+}
+
+impl NameNormalizer {
+    pub fn new(patterns: &[&str]) -> Result<Self, regex::Error> {
+        Ok(Self {
+            patterns: patterns
+                .iter()
+                .map(|p| Regex::new(p))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn normalize(&self, name: &str) -> String {
+        let mut normalized = name.to_string();
+        for pattern in &self.patterns {
+            normalized = pattern.replace_all(&normalized, "").trim().to_string();
+        }
+        // Never collapse a name to nothing
+        if normalized.is_empty() {
+            name.to_string()
+        } else {
+            normalized
+        }
+    }
+}
+
+impl Default for NameNormalizer {
+    fn default() -> Self {
+        Self::new(&[DEFAULT_NPC_QUALIFIERS]).expect("default NPC pattern is valid")
+    }
+}
+
+/// `compute_dps_series`, optionally aggregating targets by their normalized name.
+/// With `None` this is exactly `compute_dps_series`.
+pub fn compute_dps_series_with(
+    events: &[CombatEvent],
+    window: Duration,
+    end: Duration,
+    normalizer: Option<&NameNormalizer>,
+) -> Vec<DpsSample> {
+    let Some(normalizer) = normalizer else {
+        return compute_dps_series(events, window, end);
+    };
+    let normalized: Vec<CombatEvent> = events
+        .iter()
+        .map(|event| CombatEvent {
+            target: normalizer.normalize(&event.target),
+            ..event.clone()
+        })
+        .collect();
+    compute_dps_series(&normalized, window, end)
+}
+
+/// Gamelogs don't record damage types, so they're inferred from the attacker's
+/// name. First match wins; names are compared case-insensitively.
+const DAMAGE_PROFILES: &[(&str, DamageProfile)] = &[
+    // Triglavian ships
+    ("damavik", DamageProfile::new(0.0, 0.5, 0.0, 0.5)),
+    ("kikimora", DamageProfile::new(0.0, 0.5, 0.0, 0.5)),
+    ("vedmak", DamageProfile::new(0.0, 0.5, 0.0, 0.5)),
+    ("rodiva", DamageProfile::new(0.0, 0.5, 0.0, 0.5)),
+    ("leshak", DamageProfile::new(0.0, 0.5, 0.0, 0.5)),
+    ("drekavac", DamageProfile::new(0.0, 0.5, 0.0, 0.5)),
+    ("triglavian", DamageProfile::new(0.0, 0.5, 0.0, 0.5)),
+    // Pirate factions
+    ("guristas", DamageProfile::new(0.0, 0.4, 0.6, 0.0)),
+    ("serpentis", DamageProfile::new(0.0, 0.6, 0.4, 0.0)),
+    ("angel", DamageProfile::new(0.0, 0.0, 0.4, 0.6)),
+    ("blood raider", DamageProfile::new(0.6, 0.4, 0.0, 0.0)),
+    ("sansha", DamageProfile::new(0.6, 0.4, 0.0, 0.0)),
+    ("lucid", DamageProfile::new(0.6, 0.4, 0.0, 0.0)),
+    ("sleeper", DamageProfile::new(0.5, 0.5, 0.0, 0.0)),
+    // Drones
+    ("rogue drone", DamageProfile::new(0.0, 0.0, 0.4, 0.6)),
+    ("tessella", DamageProfile::new(0.0, 0.0, 0.4, 0.6)),
+    ("overmind", DamageProfile::new(0.0, 0.0, 0.4, 0.6)),
+];
+
+/// Weapon name fragments per category, checked in order; first match wins.
+/// Names are compared case-insensitively. Drones are recognized by name first.
+const WEAPON_CATEGORIES: &[(&str, WeaponCategory)] = &[
+    ("smartbomb", WeaponCategory::Smartbomb),
+    ("missile", WeaponCategory::Missile),
+    ("rocket", WeaponCategory::Missile),
+    ("torpedo", WeaponCategory::Missile),
+    ("launcher", WeaponCategory::Missile),
+    ("laser", WeaponCategory::Turret),
+    ("beam", WeaponCategory::Turret),
+    ("pulse", WeaponCategory::Turret),
+    ("blaster", WeaponCategory::Turret),
+    ("railgun", WeaponCategory::Turret),
+    ("autocannon", WeaponCategory::Turret),
+    ("artillery", WeaponCategory::Turret),
+    ("disintegrator", WeaponCategory::Turret),
+    ("vorton", WeaponCategory::Turret),
+];
+
+/// Category of a weapon, from its name.
+pub fn weapon_category(weapon: &str) -> WeaponCategory {
+    if is_drone_weapon(weapon) {
+        return WeaponCategory::Drone;
+    }
+    let name = weapon.to_lowercase();
+    WEAPON_CATEGORIES
+        .iter()
+        .find(|(key, _)| name.contains(key))
+        .map_or(WeaponCategory::Other, |(_, category)| *category)
+}
+
+/// Roll a sample's `outgoing_by_weapon` up into weapon categories.
+pub fn weapon_category_breakdown(sample: &DpsSample) -> HashMap<WeaponCategory, f32> {
+    let mut breakdown = HashMap::new();
+    for (weapon, dps) in &sample.outgoing_by_weapon {
+        *breakdown.entry(weapon_category(weapon)).or_insert(0.0) += dps;
+    }
+    breakdown
+}
+
+/// Exponential moving average over `outgoing_dps`/`incoming_dps` for smoother charts.
+/// `alpha` is the weight of the newest sample, in (0, 1]; 1 (or anything outside
+/// that range) returns the samples unchanged. Other fields are left raw.
+pub fn smooth_series(samples: &[DpsSample], alpha: f32) -> Vec<DpsSample> {
+    if !(alpha > 0.0 && alpha < 1.0) {
+        return samples.to_vec();
+    }
+
+    let mut smoothed: Vec<DpsSample> = Vec::with_capacity(samples.len());
+    for sample in samples {
+        let mut sample = sample.clone();
+        if let Some(prev) = smoothed.last() {
+            sample.outgoing_dps =
+                prev.outgoing_dps + alpha * (sample.outgoing_dps - prev.outgoing_dps);
+            sample.incoming_dps =
+                prev.incoming_dps + alpha * (sample.incoming_dps - prev.incoming_dps);
+        }
+        smoothed.push(sample);
+    }
+    smoothed
+}
+
+/// Drop drone damage, for a weapons-only view. Drone hits are attributed to the
+/// controlling character, so by default they are folded into its totals.
+pub fn exclude_drones(events: &[CombatEvent]) -> Vec<CombatEvent> {
+    events.iter().filter(|e| !e.via_drone).cloned().collect()
+}
+
+/// Damage profile for an attacker, falling back to an even split.
+pub fn damage_profile_for(attacker: &str) -> DamageProfile {
+    let name = attacker.to_lowercase();
+    DAMAGE_PROFILES
+        .iter()
+        .find(|(key, _)| name.contains(key))
+        .map(|(_, profile)| *profile)
+        .unwrap_or(DamageProfile::UNIFORM)
+}
+
+/// DPS series with incoming damage scaled by what would get through `resists`,
+/// given each attacker's inferred damage profile. Slots line up with
+/// `compute_dps_series` for the same arguments; only incoming damage fields differ.
+pub fn effective_incoming(
+    events: &[CombatEvent],
+    window: Duration,
+    end: Duration,
+    resists: ResistProfile,
+) -> Vec<DpsSample> {
+    let scaled: Vec<CombatEvent> = events
+        .iter()
+        .map(|event| {
+            let mut event = event.clone();
+            if event.incoming && event.event_type == EventType::Damage {
+                event.amount *= damage_profile_for(&event.source).multiplier(&resists);
+            }
+            event
+        })
+        .collect();
+    compute_dps_series(&scaled, window, end)
+}
+
+/// Hit-quality histogram per weapon for outgoing damage, to judge application.
+/// Lines without a quality suffix (disabled in the EVE client) are not counted.
+pub fn weapon_quality_histogram(events: &[CombatEvent]) -> HashMap<WeaponName, QualityCounts> {
+    let mut histogram: HashMap<WeaponName, QualityCounts> = HashMap::new();
+    for event in events
+        .iter()
+        .filter(|e| !e.incoming && e.event_type == EventType::Damage)
+    {
+        if let Some(quality) = event.hit_quality {
+            histogram
+                .entry(event.weapon.clone())
+                .or_default()
+                .record(quality);
+        }
+    }
+    histogram
+}
+
+/// Dealing at least this many times the damage received counts as safe
+const SAFE_RATIO: f32 = 3.0;
+/// Below this ratio (taking more than dealing) counts as dangerous
+const DANGEROUS_RATIO: f32 = 1.0;
+
+/// Classify an outgoing vs incoming damage exchange. Works with totals or rates.
+pub fn survivability(outgoing: f32, incoming: f32) -> SurvivabilityScore {
+    if incoming <= 0.0 {
+        return SurvivabilityScore {
+            ratio: None,
+            band: SurvivabilityBand::Safe,
+        };
+    }
+    let ratio = outgoing / incoming;
+    let band = if ratio >= SAFE_RATIO {
+        SurvivabilityBand::Safe
+    } else if ratio >= DANGEROUS_RATIO {
+        SurvivabilityBand::Tense
+    } else {
+        SurvivabilityBand::Dangerous
+    };
+    SurvivabilityScore {
+        ratio: Some(ratio),
+        band,
+    }
+}
+
+/// Survivability of the current window, from a sample's outgoing vs incoming DPS.
+pub fn room_score(sample: &DpsSample) -> SurvivabilityScore {
+    survivability(sample.outgoing_dps, sample.incoming_dps)
+}
+
+/// Trailing window used to measure DPS against a target for `ttk_estimate`
+const TTK_WINDOW_MILLIS: u64 = 5_000;
+
+/// Estimated time until `target` dies, given its total HP. Logs don't report HP,
+/// so the caller supplies it. Uses outgoing damage dealt so far and the recent DPS
+/// against the target, measured up to the latest event. `None` if the target isn't
+/// currently being shot.
+pub fn ttk_estimate(events: &[CombatEvent], target: &str, assumed_hp: f32) -> Option<Duration> {
+    let now = events.iter().map(event_millis).max()?;
+    let hits: Vec<&CombatEvent> = events
+        .iter()
+        .filter(|e| !e.incoming && e.event_type == EventType::Damage && e.target == target)
+        .collect();
+
+    let dealt: f32 = hits.iter().map(|e| e.amount).sum();
+    let remaining = assumed_hp - dealt;
+    if remaining <= 0.0 {
+        return Some(Duration::ZERO);
+    }
+
+    // Each hit stands for one step of fire, like the DPS series buckets
+    let window_start = (now + STEP_MILLIS).saturating_sub(TTK_WINDOW_MILLIS);
+    let recent: Vec<&CombatEvent> = hits
+        .iter()
+        .copied()
+        .filter(|e| event_millis(e) >= window_start)
+        .collect();
+    let first = recent.iter().map(|e| event_millis(e)).min()?;
+    let recent_damage: f32 = recent.iter().map(|e| e.amount).sum();
+    let dps = recent_damage / ((now - first + STEP_MILLIS) as f32 / 1000.0);
+    if dps <= 0.0 {
+        return None;
+    }
+    Some(Duration::from_secs_f32(remaining / dps))
+}
+
+/// Downsample a series to at most `max_points` for charting. The series is split
+/// into even buckets and each bucket keeps its busiest sample, so spikes survive.
+pub fn decimate_series(samples: &[DpsSample], max_points: usize) -> Vec<DpsSample> {
+    if max_points == 0 {
+        return Vec::new();
+    }
+    if samples.len() <= max_points {
+        return samples.to_vec();
+    }
+
+    let intensity = |s: &DpsSample| s.outgoing_dps.max(s.incoming_dps);
+    (0..max_points)
+        .filter_map(|bucket| {
+            let start = bucket * samples.len() / max_points;
+            let end = (bucket + 1) * samples.len() / max_points;
+            samples[start..end]
+                .iter()
+                .max_by(|a, b| intensity(a).total_cmp(&intensity(b)))
+                .cloned()
+        })
+        .collect()
+}
+
+/// Whether `entity` is `name`, possibly decorated like "Name[CORP](Ship)".
+fn entity_is(entity: &str, name: &str) -> bool {
+    entity
+        .strip_prefix(name)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['[', ' ', '(']))
+}
+
+/// Whether any of `names` is the event's source or target.
+pub fn involves_any<S: AsRef<str>>(event: &CombatEvent, names: &[S]) -> bool {
+    names.iter().any(|name| {
+        entity_is(&event.source, name.as_ref()) || entity_is(&event.target, name.as_ref())
+    })
+}
+
+/// Drop events between third parties (fleet broadcasts, nearby combat), keeping
+/// only those a tracked character dealt or received.
+pub fn retain_tracked_involvement<S: AsRef<str>>(events: &mut Vec<CombatEvent>, names: &[S]) {
+    events.retain(|event| involves_any(event, names));
+}
+
+/// Every outgoing hit on `target` as (time, amount), in time order. Finer-grained
+/// than the DPS series, for reconstructing exactly how a kill went.
+pub fn target_timeline(events: &[CombatEvent], target: &str) -> Vec<(Duration, f32)> {
+    let mut hits: Vec<(Duration, f32)> = events
+        .iter()
+        .filter(|e| !e.incoming && e.event_type == EventType::Damage && e.target == target)
+        .map(|e| (e.timestamp, e.amount))
+        .collect();
+    hits.sort_by_key(|(time, _)| *time);
+    hits
+}
+
+/// Replace character and entity names in `events` with pseudonyms ("Pilot1",
+/// "NPC3") for sharing. `mapping` (real name -> pseudonym) is reused and
+/// extended, so one export stays consistent and can be reversed locally.
+pub fn anonymize(events: &mut [CombatEvent], mapping: &mut HashMap<String, String>) {
+    let mut counts: HashMap<&'static str, usize> = HashMap::new();
+    for pseudonym in mapping.values() {
+        let prefix = if pseudonym.starts_with("NPC") {
+            "NPC"
+        } else {
+            "Pilot"
+        };
+        *counts.entry(prefix).or_default() += 1;
+    }
+    let characters: Vec<String> = events.iter().map(|e| e.character.clone()).collect();
+    let no_overrides = HashMap::new();
+
+    let mut pseudonym = |name: &str| -> String {
+        if let Some(existing) = mapping.get(name) {
+            return existing.clone();
+        }
+        let prefix = if characters.iter().any(|c| c == name)
+            || classify_attacker(name, &no_overrides) == AttackerKind::Player
+        {
+            "Pilot"
+        } else {
+            "NPC"
+        };
+        let count = counts.entry(prefix).or_default();
+        *count += 1;
+        let alias = format!("{}{}", prefix, count);
+        mapping.insert(name.to_string(), alias.clone());
+        alias
+    };
+
+    for event in events.iter_mut() {
+        event.character = pseudonym(&event.character);
+        event.source = pseudonym(&event.source);
+        event.target = pseudonym(&event.target);
+    }
+}
+
+/// How many targets a run summary lists
+const RUN_SUMMARY_TOP_TARGETS: usize = 3;
+
+/// Summarize one character's combat between `start` and `end` (inclusive).
+/// `events` must be sorted by timestamp; peak DPS uses a `window`-second average.
+pub fn run_summary(
+    character: &str,
+    events: &[CombatEvent],
+    start: Duration,
+    end: Duration,
+    window: Duration,
+) -> RunSummary {
+    let mut total_outgoing = 0.0_f32;
+    let mut total_incoming = 0.0_f32;
+    let mut by_target: HashMap<&str, f32> = HashMap::new();
+    let mut accumulator = DpsAccumulator::new(window);
+
+    for event in events.iter().filter(|e| {
+        e.character == character
+            && e.event_type == EventType::Damage
+            && e.timestamp >= start
+            && e.timestamp <= end
+    }) {
+        if event.incoming {
+            total_incoming += event.amount;
+        } else {
+            total_outgoing += event.amount;
+            *by_target.entry(&event.target).or_insert(0.0) += event.amount;
+        }
+        accumulator.push(event.clone());
+    }
+
+    // Walk the whole run, not just the last minute
+    let mut samples = accumulator.advance(start);
+    samples.extend(accumulator.advance(end));
+    let peak_dps = samples
+        .iter()
+        .map(|sample| sample.outgoing_dps)
+        .fold(0.0_f32, f32::max);
+
+    let mut top_targets: Vec<TargetHit> = by_target
+        .into_iter()
+        .map(|(target, value)| TargetHit {
+            target: target.to_string(),
+            value,
+        })
+        .collect();
+    top_targets.sort_by(|a, b| {
+        b.value
+            .partial_cmp(&a.value)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.target.cmp(&b.target))
+    });
+    top_targets.truncate(RUN_SUMMARY_TOP_TARGETS);
+
+    RunSummary {
+        character: character.to_string(),
+        duration_secs: end.saturating_sub(start).as_secs(),
+        total_outgoing,
+        total_incoming,
+        peak_dps,
+        top_targets,
+    }
+}
+
+/// Split combat into rooms using ROOM_START/ROOM_END bookmarks, given as
+/// (time, kind) on the events' timeline. A ROOM_START without a matching
+/// ROOM_END is ignored. `events` must be sorted by timestamp; peaks use a
+/// `window`-second average.
+pub fn segment_by_rooms(
+    events: &[CombatEvent],
+    bookmarks: &[(Duration, BookmarkType)],
+    window: Duration,
+) -> Vec<RoomStats> {
+    let mut sorted: Vec<&(Duration, BookmarkType)> = bookmarks.iter().collect();
+    sorted.sort_by_key(|(time, _)| *time);
+
+    let mut spans = Vec::new();
+    let mut open: Option<Duration> = None;
+    for (time, kind) in sorted {
+        match kind {
+            BookmarkType::RoomStart => open = Some(*time),
+            BookmarkType::RoomEnd => {
+                if let Some(start) = open.take() {
+                    spans.push((start, *time));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    spans
+        .into_iter()
+        .enumerate()
+        .map(|(index, (start, end))| {
+            let mut stats = RoomStats {
+                index,
+                start,
+                end,
+                duration_secs: end.saturating_sub(start).as_secs(),
+                total_outgoing: 0.0,
+                total_incoming: 0.0,
+                peak_outgoing_dps: 0.0,
+                peak_incoming_dps: 0.0,
+            };
+            let mut accumulator = DpsAccumulator::new(window);
+            for event in events.iter().filter(|e| {
+                e.event_type == EventType::Damage && e.timestamp >= start && e.timestamp <= end
+            }) {
+                if event.incoming {
+                    stats.total_incoming += event.amount;
+                } else {
+                    stats.total_outgoing += event.amount;
+                }
+                accumulator.push(event.clone());
+            }
+
+            let mut samples = accumulator.advance(start);
+            samples.extend(accumulator.advance(end));
+            for sample in &samples {
+                stats.peak_outgoing_dps = stats.peak_outgoing_dps.max(sample.outgoing_dps);
+                stats.peak_incoming_dps = stats.peak_incoming_dps.max(sample.incoming_dps);
+            }
+            stats
+        })
+        .collect()
+}
+
+/// Compact, paste-friendly fleet breakdown of a sample: one line per character
+/// with outgoing DPS and top target, highest DPS first.
+///
+/// ```text
+/// Fleet DPS: 450.0
+/// PilotA: 300.0 DPS -> Enemy1
+/// PilotB: 150.0 DPS -> Enemy2
+/// ```
+pub fn fleet_summary(sample: &DpsSample, style: NumberFormat) -> String {
+    let mut rows: Vec<(&String, f32)> = sample
+        .outgoing_by_character
+        .iter()
+        .map(|(character, dps)| (character, *dps))
+        .collect();
+    // Highest DPS first; names break ties so the output is stable
+    rows.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.0.cmp(b.0))
+    });
+
+    let number = |value: f32| format_number(value as f64, style);
+    let mut lines = vec![format!("Fleet DPS: {}", number(sample.outgoing_dps))];
+    for (character, dps) in rows {
+        let top_target = sample
+            .outgoing_by_char_target
+            .get(character)
+            .and_then(|targets| {
+                targets.iter().max_by(|a, b| {
+                    a.1.partial_cmp(b.1)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                        .then_with(|| b.0.cmp(a.0))
+                })
+            })
+            .map(|(target, _)| target.as_str());
+
+        lines.push(match top_target {
+            Some(target) => format!("{}: {} DPS -> {}", character, number(dps), target),
+            None => format!("{}: {} DPS", character, number(dps)),
+        });
+    }
+    lines.join("\n")
+}
+
+/// Markdown tables of a run summary, for pasting into chat (e.g. Discord).
+///
+/// ```text
+/// **Run: PilotA** (12m 05s)
+///
+/// | Stat | Value |
+/// | --- | --- |
+/// | Damage dealt | 120000 |
+/// ...
+///
+/// | Target | Damage |
+/// | --- | --- |
+/// | Starving Damavik | 40000 |
+/// ```
+pub fn run_summary_markdown(summary: &RunSummary) -> String {
+    let escape = |text: &str| text.replace('|', "\\|");
+    let mut lines = vec![
+        format!(
+            "**Run: {}** ({}m {:02}s)",
+            escape(&summary.character),
+            summary.duration_secs / 60,
+            summary.duration_secs % 60
+        ),
+        String::new(),
+        "| Stat | Value |".to_string(),
+        "| --- | --- |".to_string(),
+        format!("| Damage dealt | {:.0} |", summary.total_outgoing),
+        format!("| Damage taken | {:.0} |", summary.total_incoming),
+        format!("| Peak DPS | {:.1} |", summary.peak_dps),
+    ];
+    if !summary.top_targets.is_empty() {
+        lines.push(String::new());
+        lines.push("| Target | Damage |".to_string());
+        lines.push("| --- | --- |".to_string());
+        for hit in &summary.top_targets {
+            lines.push(format!("| {} | {:.0} |", escape(&hit.target), hit.value));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Incremental counterpart to `compute_dps_series` for the live hot path.
+///
+/// Keeps the sliding-window sums between calls, so each `advance` only does work
+/// for events that entered or left the window since the previous call instead of
+/// rescanning the whole history. Samples land on the same 1s grid as
+/// `compute_dps_series`; events that arrive late (older than the last sample) are
+/// folded into the current window but don't rewrite samples already returned.
+pub struct DpsAccumulator {
+    window: Duration,
+    sums: WindowSums,
+    /// Events inside the current window, sorted by timestamp
+    in_window: VecDeque<CombatEvent>,
+    /// Events newer than the cursor, sorted by timestamp
+    pending: VecDeque<CombatEvent>,
+    /// Time (ms) the window currently ends at
+    cursor: Option<u64>,
+    /// Next grid point (ms) to emit
+    next_slot: Option<u64>,
+}
+
+impl DpsAccumulator {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            sums: WindowSums::default(),
+            in_window: VecDeque::new(),
+            pending: VecDeque::new(),
+            cursor: None,
+            next_slot: None,
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    fn window_millis(&self) -> u64 {
+        self.window.as_millis().max(1) as u64
+    }
+
+    pub fn push(&mut self, event: CombatEvent) {
+        let millis = event_millis(&event);
+        match self.cursor {
+            Some(cursor) if millis <= cursor => {
+                // Late arrival for time we've already covered
+                if millis < cursor.saturating_sub(self.window_millis()) {
+                    return;
+                }
+                self.sums.add(&event);
+                let index = self
+                    .in_window
+                    .partition_point(|e| e.timestamp <= event.timestamp);
+                self.in_window.insert(index, event);
+            }
+            _ => {
+                let index = self
+                    .pending
+                    .partition_point(|e| e.timestamp <= event.timestamp);
+                self.pending.insert(index, event);
+            }
+        }
+    }
+
+    /// Slide the window so it ends at `millis`.
+    fn move_to(&mut self, millis: u64) {
+        while self
+            .pending
+            .front()
+            .is_some_and(|event| event_millis(event) <= millis)
+        {
+            let event = self.pending.pop_front().unwrap();
+            self.sums.add(&event);
+            self.in_window.push_back(event);
+        }
+
+        let window_start = millis.saturating_sub(self.window_millis());
+        while self
+            .in_window
+            .front()
+            .is_some_and(|event| event_millis(event) < window_start)
+        {
+            let event = self.in_window.pop_front().unwrap();
+            self.sums.remove(&event);
+        }
+
+        self.cursor = Some(millis);
+    }
+
+    /// Advance to `end` and return only the samples that are new since the last call.
+    /// If `end` falls between grid points, a final sample at exactly `end` is appended
+    /// so callers always see the current rate.
+    pub fn advance(&mut self, end: Duration) -> Vec<DpsSample> {
+        let end_millis = end.as_millis() as u64;
+        if self.cursor.is_some_and(|cursor| end_millis <= cursor) {
+            return Vec::new();
+        }
+        // Like compute_dps_series, there is no series until the first event arrives
+        if self.cursor.is_none() && self.pending.is_empty() {
+            return Vec::new();
+        }
+
+        let window_seconds = self.window.as_secs_f32().max(f32::EPSILON);
+        // First call: start where compute_dps_series would for this end time
+        let mut next_slot = self.next_slot.unwrap_or_else(|| {
+            end_millis
+                .saturating_sub(HISTORY_MILLIS)
+                .div_ceil(STEP_MILLIS)
+                * STEP_MILLIS
+        });
+
+        let mut samples = Vec::new();
+        while next_slot <= end_millis {
+            self.move_to(next_slot);
+            samples.push(
+                self.sums
+                    .sample(Duration::from_millis(next_slot), window_seconds),
+            );
+            next_slot += STEP_MILLIS;
+        }
+        self.next_slot = Some(next_slot);
+
+        if self.cursor != Some(end_millis) {
+            self.move_to(end_millis);
+            samples.push(self.sums.sample(end, window_seconds));
+        }
+
+        samples
+    }
+}
+
 #[cfg(test)]
-mod tests {
-    use super::super::model::CombatEvent;
+pub(crate) mod tests {
+    use super::super::model::{CombatEvent, HitQuality};
     use super::*;
     use std::time::Duration;
 
-    fn make_event(
+    pub(crate) fn make_event(
         seconds: u64,
         amount: f32,
         incoming: bool,
@@ -414,9 +1083,20 @@ mod tests {
             incoming,
             character: source.to_string(),
             event_type: EventType::Damage,
+            hit_quality: None,
+            via_drone: false,
+            self_inflicted: false,
+            damage_layer: None,
         }
     }
 
+    /// The one-second DPS sample at the last event of `events`
+    pub(crate) fn last_sample(events: &[CombatEvent]) -> DpsSample {
+        compute_dps_series(events, Duration::from_secs(1), Duration::from_secs(1))
+            .pop()
+            .unwrap()
+    }
+
     #[test]
     fn keeps_slot_for_max_timestamp_even_if_unsorted() {
         let mut events = vec![
@@ -450,6 +1130,10 @@ mod tests {
                 incoming: false,
                 character: "PilotA".to_string(),
                 event_type: EventType::Damage,
+                hit_quality: None,
+                via_drone: false,
+                self_inflicted: false,
+                damage_layer: None,
             },
             CombatEvent {
                 timestamp: Duration::from_secs(1),
@@ -460,6 +1144,10 @@ mod tests {
                 incoming: false,
                 character: "PilotB".to_string(),
                 event_type: EventType::Damage,
+                hit_quality: None,
+                via_drone: false,
+                self_inflicted: false,
+                damage_layer: None,
             },
         ];
         events.sort_by_key(|event| event.timestamp.as_millis());
@@ -504,6 +1192,10 @@ mod tests {
                 incoming: false,
                 character: "Pilot".to_string(),
                 event_type: EventType::Repair,
+                hit_quality: None,
+                via_drone: false,
+                self_inflicted: false,
+                damage_layer: None,
             },
         ];
 
@@ -526,6 +1218,10 @@ mod tests {
                 incoming: false,
                 character: "Pilot".to_string(),
                 event_type: EventType::Damage,
+                hit_quality: None,
+                via_drone: false,
+                self_inflicted: false,
+                damage_layer: None,
             },
             CombatEvent {
                 timestamp: Duration::from_secs(1),
@@ -536,6 +1232,10 @@ mod tests {
                 incoming: false,
                 character: "Pilot".to_string(),
                 event_type: EventType::Repair,
+                hit_quality: None,
+                via_drone: false,
+                self_inflicted: false,
+                damage_layer: None,
             },
             CombatEvent {
                 timestamp: Duration::from_secs(1),
@@ -546,6 +1246,10 @@ mod tests {
                 incoming: false,
                 character: "Pilot".to_string(),
                 event_type: EventType::Neut,
+                hit_quality: None,
+                via_drone: false,
+                self_inflicted: false,
+                damage_layer: None,
             },
         ];
 
@@ -580,6 +1284,10 @@ mod tests {
             incoming: true,
             character: "Pilot".to_string(),
             event_type: EventType::Damage,
+            hit_quality: None,
+            via_drone: false,
+            self_inflicted: false,
+            damage_layer: None,
         }];
 
         // Sample at t=3s (2 seconds after event, window is 1s)
@@ -598,4 +1306,521 @@ mod tests {
             "Incoming DPS should be 0 after window expires"
         );
     }
+
+    fn assert_samples_match(incremental: &DpsSample, full: &DpsSample) {
+        assert_eq!(incremental.time, full.time);
+        assert_eq!(incremental.outgoing_dps, full.outgoing_dps);
+        assert_eq!(incremental.incoming_dps, full.incoming_dps);
+        assert_eq!(incremental.outgoing_hps, full.outgoing_hps);
+        assert_eq!(incremental.outgoing_by_target, full.outgoing_by_target);
+        assert_eq!(incremental.incoming_by_source, full.incoming_by_source);
+        assert_eq!(
+            incremental.outgoing_by_char_weapon,
+            full.outgoing_by_char_weapon
+        );
+        assert_eq!(
+            incremental.combat_actions_by_character.len(),
+            full.combat_actions_by_character.len()
+        );
+    }
+
+    #[test]
+    fn accumulator_matches_full_recompute() {
+        let window = Duration::from_secs(5);
+        let mut events = Vec::new();
+        for second in 0..90u64 {
+            events.push(make_event(
+                second,
+                10.0 + second as f32,
+                false,
+                "Pilot",
+                "Enemy",
+            ));
+            if second % 3 == 0 {
+                events.push(make_event(second, 25.0, true, "Rat", "Pilot"));
+            }
+            if second % 7 == 0 {
+                events.push(CombatEvent {
+                    event_type: EventType::Repair,
+                    ..make_event(second, 40.0, false, "Pilot", "Buddy")
+                });
+            }
+        }
+
+        // Feed events in batches and advance like the live overlay would
+        let mut accumulator = DpsAccumulator::new(window);
+        let mut incremental: Vec<DpsSample> = Vec::new();
+        let mut fed = 0;
+        for end_secs in (10..=90u64).step_by(10) {
+            while fed < events.len() && events[fed].timestamp.as_secs() <= end_secs {
+                accumulator.push(events[fed].clone());
+                fed += 1;
+            }
+            let tail = accumulator.advance(Duration::from_secs(end_secs));
+            // Only new samples come back
+            if let (Some(prev), Some(first)) = (incremental.last(), tail.first()) {
+                assert!(first.time > prev.time);
+            }
+            incremental.extend(tail);
+        }
+
+        // First advance at 10s started from 0, so the whole 0..=90 series is available
+        assert_eq!(incremental.len(), 91);
+
+        let full = compute_dps_series(&events, window, Duration::from_secs(90));
+        assert_eq!(full.len(), 61);
+        let tail = &incremental[incremental.len() - full.len()..];
+        for (inc, full) in tail.iter().zip(&full) {
+            assert_samples_match(inc, full);
+        }
+    }
+
+    #[test]
+    fn accumulator_emits_current_sample_between_grid_points() {
+        let mut accumulator = DpsAccumulator::new(Duration::from_secs(1));
+        assert!(accumulator.advance(Duration::from_secs(1)).is_empty());
+        accumulator.push(make_event(1, 100.0, false, "Pilot", "Enemy"));
+
+        let samples = accumulator.advance(Duration::from_millis(1_500));
+        let times: Vec<u128> = samples.iter().map(|s| s.time.as_millis()).collect();
+        assert_eq!(times, vec![0, 1_000, 1_500]);
+        assert!(samples[2].outgoing_dps > 0.0);
+
+        // Nothing new until time moves forward
+        assert!(accumulator.advance(Duration::from_millis(1_500)).is_empty());
+
+        let samples = accumulator.advance(Duration::from_millis(3_000));
+        let times: Vec<u128> = samples.iter().map(|s| s.time.as_millis()).collect();
+        assert_eq!(times, vec![2_000, 3_000]);
+        assert_eq!(samples[0].outgoing_dps, 100.0);
+        assert_eq!(samples[1].outgoing_dps, 0.0);
+    }
+
+    #[test]
+    fn fleet_summary_sorts_by_dps() {
+        let events = vec![
+            make_event(1, 50.0, false, "PilotB", "Enemy2"),
+            make_event(1, 100.0, false, "PilotA", "Enemy1"),
+            make_event(1, 20.0, false, "PilotA", "Enemy3"),
+            make_event(1, 10.0, false, "PilotB", "Enemy1"),
+        ];
+
+        let samples = compute_dps_series(&events, Duration::from_secs(1), Duration::from_secs(1));
+        let summary = fleet_summary(&samples[1], NumberFormat::Plain);
+
+        assert_eq!(
+            summary,
+            "Fleet DPS: 180.0\nPilotA: 120.0 DPS -> Enemy1\nPilotB: 60.0 DPS -> Enemy2"
+        );
+    }
+
+    #[test]
+    fn effective_incoming_applies_resists_by_attacker() {
+        let events = vec![
+            make_event(1, 100.0, true, "Guristas Heavy Missile Battery", "Pilot"),
+            make_event(1, 100.0, false, "Pilot", "Enemy"),
+        ];
+        let resists = ResistProfile {
+            kinetic: 50.0,
+            ..ResistProfile::default()
+        };
+
+        let raw = compute_dps_series(&events, Duration::from_secs(1), Duration::from_secs(1));
+        let effective = effective_incoming(
+            &events,
+            Duration::from_secs(1),
+            Duration::from_secs(1),
+            resists,
+        );
+
+        assert_eq!(raw.len(), effective.len());
+        assert_eq!(raw[1].incoming_dps, 100.0);
+        // Guristas: 60% kinetic at 50% resist + 40% thermal unresisted
+        assert!((effective[1].incoming_dps - 70.0).abs() < 1e-3);
+        // Outgoing is untouched
+        assert_eq!(effective[1].outgoing_dps, raw[1].outgoing_dps);
+    }
+
+    #[test]
+    fn unknown_attacker_uses_uniform_profile() {
+        assert_eq!(damage_profile_for("Some Rat"), DamageProfile::UNIFORM);
+        let resists = ResistProfile {
+            em: 100.0,
+            ..ResistProfile::default()
+        };
+        assert!((DamageProfile::UNIFORM.multiplier(&resists) - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn normalizer_collapses_npc_variants() {
+        let normalizer = NameNormalizer::default();
+        assert_eq!(normalizer.normalize("Starving Damavik"), "Damavik");
+        assert_eq!(normalizer.normalize("Vila Damavik"), "Damavik");
+        assert_eq!(
+            normalizer.normalize("Lucid Deepwatcher"),
+            "Lucid Deepwatcher"
+        );
+
+        let events = vec![
+            make_event(1, 100.0, false, "Pilot", "Starving Damavik"),
+            make_event(1, 50.0, false, "Pilot", "Vila Damavik"),
+        ];
+        let window = Duration::from_secs(1);
+        let end = Duration::from_secs(1);
+
+        // Opt-in: exact names are kept by default
+        let exact = compute_dps_series_with(&events, window, end, None);
+        assert_eq!(exact[1].outgoing_by_target.len(), 2);
+
+        let merged = compute_dps_series_with(&events, window, end, Some(&normalizer));
+        assert_eq!(merged[1].outgoing_by_target.len(), 1);
+        assert_eq!(merged[1].outgoing_by_target.get("Damavik"), Some(&150.0));
+    }
+
+    #[test]
+    fn normalizer_accepts_custom_patterns() {
+        let normalizer = NameNormalizer::new(&[r"\s*\(.*\)$"]).unwrap();
+        assert_eq!(normalizer.normalize("Drone (Wave 2)"), "Drone");
+        assert!(NameNormalizer::new(&["("]).is_err());
+    }
+
+    #[test]
+    fn weapon_quality_histogram_counts_per_weapon() {
+        let with_quality = |weapon: &str, quality: HitQuality| {
+            let mut event = make_event(1, 100.0, false, "Pilot", "Enemy");
+            event.weapon = weapon.to_string();
+            event.hit_quality = Some(quality);
+            event
+        };
+        let mut incoming = with_quality("Blaster", HitQuality::Wrecks);
+        incoming.incoming = true;
+
+        let events = vec![
+            with_quality("Blaster", HitQuality::Hits),
+            with_quality("Blaster", HitQuality::Hits),
+            with_quality("Blaster", HitQuality::Smashes),
+            with_quality("Blaster", HitQuality::Glances),
+            with_quality("Missile", HitQuality::Grazes),
+            with_quality("Missile", HitQuality::Penetrates),
+            with_quality("Missile", HitQuality::Wrecks),
+            make_event(2, 100.0, false, "Pilot", "Enemy"),
+            incoming,
+        ];
+
+        let histogram = weapon_quality_histogram(&events);
+        assert_eq!(histogram.len(), 2);
+        assert_eq!(
+            histogram["Blaster"],
+            QualityCounts {
+                hits: 2,
+                smashes: 1,
+                glances: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            histogram["Missile"],
+            QualityCounts {
+                grazes: 1,
+                penetrates: 1,
+                wrecks: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn smooth_series_approaches_step_monotonically() {
+        // With a 1s window (inclusive on both ends), one hit every 2s is a flat 100 DPS
+        let events: Vec<CombatEvent> = (10..=30)
+            .step_by(2)
+            .map(|second| make_event(second, 100.0, false, "Pilot", "Enemy"))
+            .collect();
+        let raw = compute_dps_series(&events, Duration::from_secs(1), Duration::from_secs(30));
+
+        let smoothed = smooth_series(&raw, 0.3);
+        assert_eq!(smoothed.len(), raw.len());
+
+        // Raw steps from 0 to 100 at t=10; the average climbs towards it without overshooting
+        let step = raw.iter().position(|s| s.outgoing_dps > 0.0).unwrap();
+        assert!(smoothed[..step].iter().all(|s| s.outgoing_dps == 0.0));
+        for pair in smoothed[step..].windows(2) {
+            assert!(pair[1].outgoing_dps > pair[0].outgoing_dps);
+            assert!(pair[1].outgoing_dps <= 100.0);
+        }
+        assert!(smoothed.last().unwrap().outgoing_dps > 99.0);
+        assert!(smoothed[step].outgoing_dps < 100.0);
+
+        // Per-target data stays raw; alpha=1 is a no-op
+        assert_eq!(
+            smoothed[step].outgoing_by_target,
+            raw[step].outgoing_by_target
+        );
+        let unchanged = smooth_series(&raw, 1.0);
+        assert!(unchanged
+            .iter()
+            .zip(&raw)
+            .all(|(a, b)| a.outgoing_dps == b.outgoing_dps));
+    }
+
+    #[test]
+    fn room_score_bands_by_exchange() {
+        let score_for = |events: &[CombatEvent]| {
+            let samples =
+                compute_dps_series(events, Duration::from_secs(5), Duration::from_secs(5));
+            room_score(samples.last().unwrap())
+        };
+
+        let lopsided = score_for(&[
+            make_event(1, 1000.0, false, "Pilot", "Enemy"),
+            make_event(2, 1000.0, false, "Pilot", "Enemy"),
+            make_event(3, 100.0, true, "Enemy", "Pilot"),
+        ]);
+        assert_eq!(lopsided.band, SurvivabilityBand::Safe);
+        assert_eq!(lopsided.ratio, Some(20.0));
+
+        let even = score_for(&[
+            make_event(1, 500.0, false, "Pilot", "Enemy"),
+            make_event(2, 500.0, true, "Enemy", "Pilot"),
+        ]);
+        assert_eq!(even.band, SurvivabilityBand::Tense);
+        assert_eq!(even.ratio, Some(1.0));
+
+        assert_eq!(
+            survivability(100.0, 400.0).band,
+            SurvivabilityBand::Dangerous
+        );
+        assert_eq!(survivability(100.0, 0.0).ratio, None);
+    }
+
+    #[test]
+    fn exclude_drones_keeps_weapon_damage() {
+        let mut drone = make_event(1, 40.0, false, "Pilot", "Enemy");
+        drone.weapon = "Hobgoblin II".to_string();
+        drone.via_drone = true;
+        let events = vec![make_event(1, 100.0, false, "Pilot", "Enemy"), drone];
+
+        let all = compute_dps_series(&events, Duration::from_secs(1), Duration::from_secs(1));
+        let weapons_only = compute_dps_series(
+            &exclude_drones(&events),
+            Duration::from_secs(1),
+            Duration::from_secs(1),
+        );
+        assert_eq!(all.last().unwrap().outgoing_by_character["Pilot"], 140.0);
+        assert_eq!(
+            weapons_only.last().unwrap().outgoing_by_character["Pilot"],
+            100.0
+        );
+    }
+
+    #[test]
+    fn ttk_uses_recent_dps_against_target() {
+        // 100 DPS for 4s: 400 of 1000 HP dealt
+        let mut events: Vec<CombatEvent> = (0..4)
+            .map(|t| make_event(t, 100.0, false, "Pilot", "Damavik"))
+            .collect();
+        events.push(make_event(3, 500.0, false, "Pilot", "Leshak"));
+
+        let ttk = ttk_estimate(&events, "Damavik", 1000.0).unwrap();
+        assert!((ttk.as_secs_f32() - 6.0).abs() < 0.01, "{:?}", ttk);
+
+        assert_eq!(
+            ttk_estimate(&events, "Damavik", 300.0),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(ttk_estimate(&events, "Kikimora", 1000.0), None);
+    }
+
+    #[test]
+    fn weapon_categories_roll_up_sample_weapons() {
+        let fired = |weapon: &str, amount: f32| CombatEvent {
+            weapon: weapon.to_string(),
+            ..make_event(1, amount, false, "Pilot", "Enemy")
+        };
+        let events = vec![
+            fired("Small Focused Beam Laser II", 100.0),
+            fired("Heavy Pulse Laser II", 50.0),
+            fired("Scourge Light Missile", 40.0),
+            fired("Rocket Launcher II", 10.0),
+            fired("Hornet EC-300", 20.0),
+            fired("Mystery Gadget", 5.0),
+        ];
+        let samples = compute_dps_series(&events, Duration::from_secs(1), Duration::from_secs(1));
+        let sample = &samples[1];
+
+        let breakdown = weapon_category_breakdown(sample);
+        assert_eq!(breakdown[&WeaponCategory::Turret], 150.0);
+        assert_eq!(breakdown[&WeaponCategory::Missile], 50.0);
+        assert_eq!(breakdown[&WeaponCategory::Drone], 20.0);
+        assert_eq!(breakdown[&WeaponCategory::Other], 5.0);
+        assert!(!breakdown.contains_key(&WeaponCategory::Smartbomb));
+    }
+
+    #[test]
+    fn run_summary_markdown_renders_tables() {
+        let summary = RunSummary {
+            character: "PilotA".to_string(),
+            duration_secs: 725,
+            total_outgoing: 120_000.0,
+            total_incoming: 8_000.4,
+            peak_dps: 950.3,
+            top_targets: vec![TargetHit {
+                target: "Starving Damavik".to_string(),
+                value: 40_000.0,
+            }],
+        };
+
+        let markdown = run_summary_markdown(&summary);
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines[0], "**Run: PilotA** (12m 05s)");
+        assert!(lines.contains(&"| Stat | Value |"));
+        assert!(lines.contains(&"| Damage dealt | 120000 |"));
+        assert!(lines.contains(&"| Peak DPS | 950.3 |"));
+        assert!(lines.contains(&"| Target | Damage |"));
+        assert_eq!(*lines.last().unwrap(), "| Starving Damavik | 40000 |");
+    }
+
+    #[test]
+    fn target_timeline_keeps_only_hits_on_target_in_order() {
+        let events = vec![
+            make_event(5, 300.0, false, "Pilot", "Damavik"),
+            make_event(1, 100.0, false, "Pilot", "Damavik"),
+            make_event(2, 999.0, false, "Pilot", "Kikimora"),
+            make_event(3, 50.0, true, "Damavik", "Pilot"),
+            CombatEvent {
+                event_type: EventType::Neut,
+                ..make_event(4, 80.0, false, "Pilot", "Damavik")
+            },
+            make_event(3, 200.0, false, "Buddy", "Damavik"),
+        ];
+
+        let timeline = target_timeline(&events, "Damavik");
+        assert_eq!(
+            timeline,
+            vec![
+                (Duration::from_secs(1), 100.0),
+                (Duration::from_secs(3), 200.0),
+                (Duration::from_secs(5), 300.0),
+            ]
+        );
+        assert!(target_timeline(&events, "Leshak").is_empty());
+    }
+
+    #[test]
+    fn tracked_involvement_drops_third_party_events() {
+        let mut events = vec![
+            make_event(1, 100.0, false, "Pilot", "Damavik"),
+            make_event(2, 40.0, true, "Kikimora", "Pilot[CORP](Gila)"),
+            make_event(3, 500.0, false, "Stranger", "Leshak"),
+            make_event(4, 70.0, false, "Pilotina", "Leshak"),
+        ];
+
+        retain_tracked_involvement(&mut events, &["Pilot"]);
+        let times: Vec<u64> = events.iter().map(|e| e.timestamp.as_secs()).collect();
+        assert_eq!(times, vec![1, 2]);
+    }
+
+    #[test]
+    fn decimate_series_keeps_global_max() {
+        let base = compute_dps_series(
+            &[make_event(0, 10.0, false, "Pilot", "Damavik")],
+            Duration::from_secs(1),
+            Duration::ZERO,
+        )
+        .pop()
+        .unwrap();
+        // Flat 10 DPS for 1000 seconds with a single spike
+        let series: Vec<DpsSample> = (0..1000u64)
+            .map(|second| DpsSample {
+                time: Duration::from_secs(second),
+                outgoing_dps: if second == 437 { 5000.0 } else { 10.0 },
+                ..base.clone()
+            })
+            .collect();
+        assert_eq!(series.len(), 1000);
+
+        let decimated = decimate_series(&series, 100);
+        assert_eq!(decimated.len(), 100);
+        assert!(decimated.iter().any(|s| s.outgoing_dps == 5000.0));
+        assert!(decimated.windows(2).all(|pair| pair[0].time < pair[1].time));
+        assert_eq!(decimate_series(&series[..50], 100).len(), 50);
+    }
+
+    #[test]
+    fn segments_stats_by_room_bookmarks() {
+        let bookmark = |secs: u64, kind: BookmarkType| (Duration::from_secs(secs), kind);
+        // Deliberately out of order
+        let bookmarks = vec![
+            bookmark(100, BookmarkType::RoomStart),
+            bookmark(130, BookmarkType::RoomEnd),
+            bookmark(10, BookmarkType::RoomStart),
+            bookmark(50, BookmarkType::RoomEnd),
+            bookmark(200, BookmarkType::RoomStart),
+        ];
+        let events = vec![
+            make_event(20, 100.0, false, "Pilot", "Enemy"),
+            make_event(21, 30.0, true, "Rat", "Pilot"),
+            make_event(70, 999.0, false, "Pilot", "Between"),
+            make_event(110, 400.0, false, "Pilot", "Boss"),
+            make_event(111, 400.0, false, "Pilot", "Boss"),
+            make_event(112, 200.0, true, "Boss", "Pilot"),
+            make_event(210, 50.0, false, "Pilot", "Unclosed"),
+        ];
+
+        let rooms = segment_by_rooms(&events, &bookmarks, Duration::from_secs(5));
+
+        assert_eq!(rooms.len(), 2);
+        assert_eq!(rooms[0].duration_secs, 40);
+        assert_eq!(rooms[0].total_outgoing, 100.0);
+        assert_eq!(rooms[0].total_incoming, 30.0);
+        assert_eq!(rooms[1].index, 1);
+        assert_eq!(rooms[1].duration_secs, 30);
+        assert_eq!(rooms[1].total_outgoing, 800.0);
+        assert_eq!(rooms[1].total_incoming, 200.0);
+        assert!(rooms[1].peak_outgoing_dps > rooms[0].peak_outgoing_dps);
+        assert!(rooms[1].peak_incoming_dps > rooms[0].peak_incoming_dps);
+    }
+
+    #[test]
+    fn anonymize_uses_stable_distinct_pseudonyms() {
+        let hit = |source: &str, target: &str, incoming: bool, character: &str| CombatEvent {
+            character: character.to_string(),
+            ..make_event(0, 100.0, incoming, source, target)
+        };
+        let mut events = vec![
+            hit(
+                "Felix Allistar",
+                "Starving Damavik",
+                false,
+                "Felix Allistar",
+            ),
+            hit("Starving Damavik", "Felix Allistar", true, "Felix Allistar"),
+            hit("Wing Mate", "Lucid Deepwatcher", false, "Wing Mate"),
+        ];
+        let mut mapping = HashMap::new();
+        anonymize(&mut events, &mut mapping);
+
+        assert_eq!(events[0].source, "Pilot1");
+        assert_eq!(events[0].character, "Pilot1");
+        assert_eq!(events[0].target, "NPC1");
+        // Same names, same pseudonyms
+        assert_eq!(events[1].source, "NPC1");
+        assert_eq!(events[1].target, "Pilot1");
+        // Distinct names differ
+        assert_eq!(events[2].source, "Pilot2");
+        assert_eq!(events[2].target, "NPC2");
+        assert_eq!(mapping["Felix Allistar"], "Pilot1");
+        assert_eq!(mapping.len(), 4);
+
+        // A later batch keeps extending the same mapping
+        let mut more = vec![hit(
+            "Felix Allistar",
+            "Ephialtes Lancer",
+            false,
+            "Felix Allistar",
+        )];
+        anonymize(&mut more, &mut mapping);
+        assert_eq!(more[0].source, "Pilot1");
+        assert_eq!(more[0].target, "NPC3");
+    }
 }
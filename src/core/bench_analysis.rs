@@ -1,7 +1,8 @@
 #[cfg(test)]
 mod tests {
     use crate::core::analysis;
-    use crate::core::model::{CombatEvent, EventType};
+    use crate::core::model::{demo_events, CombatEvent, DemoProfile, EventType};
+    use crate::core::state::EngineState;
     use std::time::{Duration, Instant};
 
     fn generate_large_history(count: usize) -> Vec<CombatEvent> {
@@ -16,11 +17,86 @@ mod tests {
                 incoming: false,
                 character: "Char1".to_string(),
                 event_type: EventType::Damage,
+                hit_quality: None,
+                via_drone: false,
+                self_inflicted: false,
+                damage_layer: None,
             });
         }
         events
     }
 
+    /// Demo combat tiled back to back until `count` events, sorted like the engine keeps them
+    fn generate_demo_history(count: usize) -> Vec<CombatEvent> {
+        let batch = demo_events(DemoProfile::BurstDps);
+        let span = batch.iter().map(|e| e.timestamp).max().unwrap() + Duration::from_secs(1);
+
+        let mut events = Vec::with_capacity(count);
+        let mut offset = Duration::ZERO;
+        while events.len() < count {
+            events.extend(
+                batch
+                    .iter()
+                    .take(count - events.len())
+                    .map(|e| CombatEvent {
+                        timestamp: e.timestamp + offset,
+                        ..e.clone()
+                    }),
+            );
+            offset += span;
+        }
+        events.sort_by_key(|e| e.timestamp);
+        events
+    }
+
+    /// Time `iterations` series computations ending at the last event.
+    /// Kept separate from the assertions so a criterion bench can reuse it.
+    fn time_dps_series(events: &[CombatEvent], window: Duration, iterations: u32) -> Duration {
+        let last_ts = events.last().map(|e| e.timestamp).unwrap_or_default();
+        let start = Instant::now();
+        for _ in 0..iterations {
+            std::hint::black_box(analysis::compute_dps_series(
+                std::hint::black_box(events),
+                window,
+                last_ts,
+            ));
+        }
+        start.elapsed()
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, ignore)]
+    fn benchmark_compute_dps_series_scaling() {
+        let windows = [1, 5, 30].map(Duration::from_secs);
+
+        for count in [10_000, 100_000, 1_000_000] {
+            let events = generate_demo_history(count);
+            assert_eq!(events.len(), count);
+
+            for window in windows {
+                let elapsed = time_dps_series(&events, window, 100);
+                println!(
+                    "{:>9} events, {:>2}s window: {:?} per call",
+                    count,
+                    window.as_secs(),
+                    elapsed / 100
+                );
+
+                // Only the history tail is scanned, so cost must not grow with
+                // total event count. Fail loudly if the hot path goes quadratic.
+                if count == 100_000 {
+                    assert!(
+                        elapsed < Duration::from_millis(1000),
+                        "compute_dps_series too slow for {} events ({}s window): {:?}",
+                        count,
+                        window.as_secs(),
+                        elapsed
+                    );
+                }
+            }
+        }
+    }
+
     #[test]
     #[cfg_attr(debug_assertions, ignore)]
     fn benchmark_compute_dps_series_performance() {
@@ -51,4 +127,59 @@ mod tests {
             duration
         );
     }
+
+    #[test]
+    #[cfg_attr(debug_assertions, ignore)]
+    fn benchmark_sorted_insert_vs_sort_per_call() {
+        let event_count = 50_000;
+        let window = Duration::from_secs(5);
+
+        // Two characters whose events arrive interleaved slightly out of order
+        let mut arrivals = generate_large_history(event_count);
+        for pair in arrivals.chunks_mut(2) {
+            if let [a, b] = pair {
+                b.character = "Char2".to_string();
+                std::mem::swap(a, b);
+            }
+        }
+
+        // Before: append in arrival order, re-sort the full buffer on each series call
+        let start = Instant::now();
+        let mut unsorted: Vec<CombatEvent> = Vec::with_capacity(event_count);
+        let mut before_last = Vec::new();
+        for (i, event) in arrivals.iter().enumerate() {
+            unsorted.push(event.clone());
+            if i % 100 == 0 {
+                unsorted.sort_by_key(|e| e.timestamp);
+                before_last = analysis::compute_dps_series(&unsorted, window, event.timestamp);
+            }
+        }
+        let before = start.elapsed();
+
+        // After: EngineState keeps the buffer sorted on insert
+        let start = Instant::now();
+        let mut state = EngineState::new();
+        let mut after_last = Vec::new();
+        for (i, event) in arrivals.iter().enumerate() {
+            state.push_event(event.clone());
+            if i % 100 == 0 {
+                after_last = state.dps_series(window, event.timestamp);
+            }
+        }
+        let after = start.elapsed();
+
+        println!(
+            "{} events, series every 100 pushes: sort-per-call {:?}, sorted insert {:?}",
+            event_count, before, after
+        );
+
+        assert!(state
+            .events()
+            .windows(2)
+            .all(|pair| pair[0].timestamp <= pair[1].timestamp));
+        assert_eq!(before_last.len(), after_last.len());
+        for (b, a) in before_last.iter().zip(&after_last) {
+            assert_eq!(b.outgoing_dps, a.outgoing_dps);
+        }
+    }
 }
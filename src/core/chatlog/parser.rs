@@ -16,21 +16,70 @@ pub struct LocationChange {
     pub location: String,
 }
 
+/// Location names that signal the Abyss when no markers are configured.
+/// EN clients show "Unknown"; some clients/localizations show "Abyssal Deadspace".
+pub const DEFAULT_ABYSS_MARKERS: &[&str] = &["Unknown", "Abyssal"];
+
+/// Default Abyss markers as owned strings (for settings).
+pub fn default_abyss_markers() -> Vec<String> {
+    DEFAULT_ABYSS_MARKERS
+        .iter()
+        .map(|m| m.to_string())
+        .collect()
+}
+
+/// Returns true if a location name contains any of the markers as whole words
+/// (case-insensitive): "Abyssal" matches "Abyssal Deadspace" but not "Abyssalia".
+pub fn is_abyss_location<S: AsRef<str>>(location: &str, markers: &[S]) -> bool {
+    let words: Vec<String> = location.split_whitespace().map(str::to_lowercase).collect();
+    markers.iter().any(|m| {
+        let marker: Vec<String> = m
+            .as_ref()
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect();
+        !marker.is_empty() && words.windows(marker.len()).any(|w| w == marker.as_slice())
+    })
+}
+
 impl LocationChange {
     /// Returns true if this location change represents entering the Abyss.
     pub fn is_abyss_entry(&self) -> bool {
-        self.location == "Unknown"
+        self.is_abyss_entry_with(DEFAULT_ABYSS_MARKERS)
     }
 
     /// Returns true if this location change represents exiting the Abyss.
     pub fn is_abyss_exit(&self) -> bool {
         !self.is_abyss_entry()
     }
+
+    /// Like `is_abyss_entry`, using a custom set of entry-location markers.
+    pub fn is_abyss_entry_with<S: AsRef<str>>(&self, markers: &[S]) -> bool {
+        is_abyss_location(&self.location, markers)
+    }
+
+    /// Like `is_abyss_exit`, using a custom set of entry-location markers.
+    pub fn is_abyss_exit_with<S: AsRef<str>>(&self, markers: &[S]) -> bool {
+        !self.is_abyss_entry_with(markers)
+    }
 }
 
-/// Parser for Local chat log lines.
+/// A plain chat message from any channel (Local, Fleet, intel channels, ...).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChatMessage {
+    /// Timestamp of the message (Duration from epoch, matching location changes)
+    pub timestamp: Duration,
+    /// Name of the speaker ("EVE System" for system messages)
+    pub speaker: String,
+    /// Message text
+    pub text: String,
+}
+
+/// Parser for chat log lines.
 pub struct ChatlogParser {
     location_regex: Regex,
+    message_regex: Regex,
+    session_regex: Regex,
 }
 
 impl ChatlogParser {
@@ -40,7 +89,22 @@ impl ChatlogParser {
             r"^\s*\[\s*(\d{4}\.\d{2}\.\d{2}\s+\d{2}:\d{2}:\d{2})\s*\]\s*EVE System\s*>\s*Channel changed to Local\s*:\s*(.+)$"
         ).expect("Invalid location regex");
 
-        Self { location_regex }
+        // Pattern: [ 2026.01.03 11:40:12 ] Speaker Name > message text
+        let message_regex = Regex::new(
+            r"^\s*\[\s*(\d{4}\.\d{2}\.\d{2}\s+\d{2}:\d{2}:\d{2})\s*\]\s*(.+?)\s*>\s?(.*)$",
+        )
+        .expect("Invalid message regex");
+
+        // Pattern: Session started: 2026.01.03 11:26:27
+        let session_regex =
+            Regex::new(r"^\s*Session started:\s*(\d{4}\.\d{2}\.\d{2}\s+\d{2}:\d{2}:\d{2})\s*$")
+                .expect("Invalid session regex");
+
+        Self {
+            location_regex,
+            message_regex,
+            session_regex,
+        }
     }
 
     /// Parse a single line for a location change event.
@@ -49,20 +113,57 @@ impl ChatlogParser {
         let line = line.trim().trim_start_matches('\u{feff}');
         let caps = self.location_regex.captures(line)?;
 
-        let time_str = caps.get(1)?.as_str();
+        let timestamp = parse_chat_timestamp(caps.get(1)?.as_str())?;
         let location = caps.get(2)?.as_str().trim().to_string();
 
-        // Parse timestamp to Duration (from epoch, like combat events)
-        let naive = NaiveDateTime::parse_from_str(time_str, "%Y.%m.%d %H:%M:%S").ok()?;
-        let dt = Utc.from_utc_datetime(&naive);
-        let timestamp = Duration::from_secs(dt.timestamp() as u64);
-
         Some(LocationChange {
             timestamp,
             location,
         })
     }
 
+    /// Parse a single line as a plain chat message from any channel.
+    pub fn parse_message(&self, line: &str) -> Option<ChatMessage> {
+        // Strip BOM and trim whitespace
+        let line = line.trim().trim_start_matches('\u{feff}');
+        let caps = self.message_regex.captures(line)?;
+
+        let timestamp = parse_chat_timestamp(caps.get(1)?.as_str())?;
+        let speaker = caps.get(2)?.as_str().trim().to_string();
+        let text = caps.get(3)?.as_str().trim().to_string();
+
+        Some(ChatMessage {
+            timestamp,
+            speaker,
+            text,
+        })
+    }
+
+    /// Parse a header line marking the start of a client session.
+    pub fn parse_session_start(&self, line: &str) -> Option<Duration> {
+        // Strip BOM and trim whitespace
+        let line = line.trim().trim_start_matches('\u{feff}');
+        let caps = self.session_regex.captures(line)?;
+        parse_chat_timestamp(caps.get(1)?.as_str())
+    }
+
+    /// Parse all session start times from a list of lines (one per log file
+    /// when several logs are read back to back).
+    pub fn parse_session_starts(&self, lines: &[String]) -> Vec<Duration> {
+        lines
+            .iter()
+            .filter_map(|line| self.parse_session_start(line))
+            .collect()
+    }
+
+    /// Parse all chat messages from a list of lines.
+    pub fn parse_messages(&self, lines: &[String]) -> Vec<ChatMessage> {
+        lines
+            .iter()
+            .filter_map(|line| self.parse_message(line))
+            .collect()
+    }
+
     /// Parse all location changes from a list of lines.
     pub fn parse_lines(&self, lines: &[String]) -> Vec<LocationChange> {
         lines
@@ -72,6 +173,13 @@ impl ChatlogParser {
     }
 }
 
+/// Parse a chat timestamp to a Duration (from epoch, like combat events)
+fn parse_chat_timestamp(time_str: &str) -> Option<Duration> {
+    let naive = NaiveDateTime::parse_from_str(time_str, "%Y.%m.%d %H:%M:%S").ok()?;
+    let dt = Utc.from_utc_datetime(&naive);
+    Some(Duration::from_secs(dt.timestamp() as u64))
+}
+
 impl Default for ChatlogParser {
     fn default() -> Self {
         Self::new()
@@ -87,22 +195,105 @@ pub struct AbyssRun {
     pub exit_time: Option<Duration>,
     /// Location before entering (e.g., "Torrinos")
     pub origin_location: Option<String>,
+    /// The client session ended while inside (logged out or crashed)
+    #[serde(default)]
+    pub abandoned: bool,
+}
+
+impl AbyssRun {
+    /// Time spent in the Abyss (None if the run has no exit)
+    pub fn duration(&self) -> Option<Duration> {
+        self.exit_time
+            .map(|exit| exit.saturating_sub(self.entry_time))
+    }
+
+    /// Returns true if the run has an exit; runs without one were abandoned
+    /// (logged out inside) or are still in progress.
+    pub fn is_completed(&self) -> bool {
+        self.exit_time.is_some()
+    }
+}
+
+/// Aggregate statistics over a set of Abyss runs.
+/// Durations only consider completed runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AbyssRunStats {
+    pub count: usize,
+    pub completed: usize,
+    pub abandoned: usize,
+    pub avg_duration: Option<Duration>,
+    pub fastest: Option<Duration>,
+    pub slowest: Option<Duration>,
+}
+
+/// Summarize a set of Abyss runs.
+pub fn summarize_runs(runs: &[AbyssRun]) -> AbyssRunStats {
+    let durations: Vec<Duration> = runs.iter().filter_map(AbyssRun::duration).collect();
+    let total: Duration = durations.iter().sum();
+
+    AbyssRunStats {
+        count: runs.len(),
+        completed: durations.len(),
+        abandoned: runs.iter().filter(|run| run.abandoned).count(),
+        avg_duration: u32::try_from(durations.len())
+            .ok()
+            .filter(|n| *n > 0)
+            .map(|n| total / n),
+        fastest: durations.iter().min().copied(),
+        slowest: durations.iter().max().copied(),
+    }
 }
 
 /// Detect Abyss runs from a sequence of location changes.
 pub fn detect_abyss_runs(changes: &[LocationChange]) -> Vec<AbyssRun> {
+    detect_abyss_runs_with(changes, DEFAULT_ABYSS_MARKERS)
+}
+
+/// Detect Abyss runs using a custom set of entry-location markers.
+pub fn detect_abyss_runs_with<S: AsRef<str>>(
+    changes: &[LocationChange],
+    markers: &[S],
+) -> Vec<AbyssRun> {
+    detect_abyss_runs_with_sessions(changes, &[], markers)
+}
+
+/// Detect Abyss runs, closing any run still open at a new session start as
+/// abandoned instead of pairing it with the first location of the next session.
+pub fn detect_abyss_runs_with_sessions<S: AsRef<str>>(
+    changes: &[LocationChange],
+    session_starts: &[Duration],
+    markers: &[S],
+) -> Vec<AbyssRun> {
     let mut runs = Vec::new();
     let mut current_run: Option<AbyssRun> = None;
     let mut last_known_location: Option<String> = None;
 
+    // A session started after the run was entered and no later than `until`
+    let session_between = |entry: Duration, until: Option<Duration>| {
+        session_starts
+            .iter()
+            .any(|start| *start > entry && until.is_none_or(|until| *start <= until))
+    };
+
     for change in changes {
-        if change.is_abyss_entry() {
+        if current_run
+            .as_ref()
+            .is_some_and(|run| session_between(run.entry_time, Some(change.timestamp)))
+        {
+            if let Some(mut run) = current_run.take() {
+                run.abandoned = true;
+                runs.push(run);
+            }
+        }
+
+        if change.is_abyss_entry_with(markers) {
             // Starting a new run
             if current_run.is_none() {
                 current_run = Some(AbyssRun {
                     entry_time: change.timestamp,
                     exit_time: None,
                     origin_location: last_known_location.clone(),
+                    abandoned: false,
                 });
             }
         } else {
@@ -116,7 +307,8 @@ pub fn detect_abyss_runs(changes: &[LocationChange]) -> Vec<AbyssRun> {
     }
 
     // If there's an unclosed run, include it
-    if let Some(run) = current_run {
+    if let Some(mut run) = current_run {
+        run.abandoned = session_between(run.entry_time, None);
         runs.push(run);
     }
 
@@ -167,6 +359,38 @@ mod tests {
         assert!(parser.parse_line(line).is_none());
     }
 
+    #[test]
+    fn test_parse_fleet_message() {
+        let parser = ChatlogParser::new();
+
+        let line = "[ 2026.01.03 11:40:12 ] Felix Allistar > hostiles in D-PNP9, 3x Gila";
+        let msg = parser.parse_message(line).expect("Should parse message");
+        assert_eq!(msg.speaker, "Felix Allistar");
+        assert_eq!(msg.text, "hostiles in D-PNP9, 3x Gila");
+        assert_eq!(msg.timestamp, Duration::from_secs(1_767_440_412));
+
+        // Not a location change
+        assert!(parser.parse_line(line).is_none());
+    }
+
+    #[test]
+    fn test_location_detection_unaffected_by_messages() {
+        let parser = ChatlogParser::new();
+
+        let line = "[ 2026.01.03 11:26:33 ] EVE System > Channel changed to Local : Torrinos";
+        let change = parser.parse_line(line).expect("Should parse location");
+        assert_eq!(change.location, "Torrinos");
+
+        let msg = parser
+            .parse_message(line)
+            .expect("System lines are messages too");
+        assert_eq!(msg.speaker, "EVE System");
+        assert_eq!(msg.text, "Channel changed to Local : Torrinos");
+
+        // Header lines are not messages
+        assert!(parser.parse_message("  Channel Name:    Fleet").is_none());
+    }
+
     #[test]
     fn test_detect_abyss_runs() {
         let changes = vec![
@@ -223,6 +447,54 @@ mod tests {
         assert_eq!(runs[0].origin_location, Some("Jita".to_string()));
     }
 
+    #[test]
+    fn test_detect_run_with_abyssal_deadspace_location() {
+        let changes = vec![
+            LocationChange {
+                timestamp: Duration::from_secs(100),
+                location: "Torrinos".to_string(),
+            },
+            LocationChange {
+                timestamp: Duration::from_secs(200),
+                location: "Abyssal Deadspace".to_string(),
+            },
+            LocationChange {
+                timestamp: Duration::from_secs(800),
+                location: "Torrinos".to_string(),
+            },
+        ];
+
+        assert!(changes[1].is_abyss_entry());
+        assert!(changes[2].is_abyss_exit());
+
+        let runs = detect_abyss_runs(&changes);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].entry_time, Duration::from_secs(200));
+        assert_eq!(runs[0].exit_time, Some(Duration::from_secs(800)));
+        assert_eq!(runs[0].origin_location, Some("Torrinos".to_string()));
+
+        // A custom marker set that doesn't include "Abyssal" sees no run
+        let runs = detect_abyss_runs_with(&changes, &["Unknown"]);
+        assert!(runs.is_empty());
+    }
+
+    #[test]
+    fn test_abyss_markers_match_whole_words() {
+        assert!(is_abyss_location(
+            "Abyssal Deadspace",
+            DEFAULT_ABYSS_MARKERS
+        ));
+        assert!(is_abyss_location("unknown", DEFAULT_ABYSS_MARKERS));
+        assert!(is_abyss_location(
+            "Abyssal Deadspace",
+            &["abyssal deadspace"]
+        ));
+        assert!(!is_abyss_location("Unknownia", DEFAULT_ABYSS_MARKERS));
+        assert!(!is_abyss_location("Abyssalia Prime", DEFAULT_ABYSS_MARKERS));
+        assert!(!is_abyss_location("Deadspace", &["Abyssal Deadspace"]));
+        assert!(!is_abyss_location("Unknown", &[" "]));
+    }
+
     #[test]
     fn test_parse_full_log_sample() {
         let parser = ChatlogParser::new();
@@ -243,4 +515,72 @@ mod tests {
         let runs = detect_abyss_runs(&changes);
         assert_eq!(runs.len(), 2);
     }
+
+    #[test]
+    fn test_summarize_runs() {
+        let parser = ChatlogParser::new();
+        let lines: Vec<String> = vec![
+            "[ 2026.01.03 11:26:33 ] EVE System > Channel changed to Local : Torrinos",
+            "[ 2026.01.03 11:30:05 ] EVE System > Channel changed to Local : Unknown",
+            "[ 2026.01.03 11:39:03 ] EVE System > Channel changed to Local : Torrinos",
+            "[ 2026.01.03 11:40:02 ] EVE System > Channel changed to Local : Unknown",
+            "[ 2026.01.03 11:53:19 ] EVE System > Channel changed to Local : Torrinos",
+            "[ 2026.01.03 11:55:00 ] EVE System > Channel changed to Local : Unknown",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        let runs = detect_abyss_runs(&parser.parse_lines(&lines));
+        assert_eq!(runs[0].duration(), Some(Duration::from_secs(538)));
+        assert!(!runs[2].is_completed());
+        assert_eq!(runs[2].duration(), None);
+
+        let stats = summarize_runs(&runs);
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.completed, 2);
+        assert_eq!(stats.fastest, Some(Duration::from_secs(538)));
+        assert_eq!(stats.slowest, Some(Duration::from_secs(797)));
+        assert_eq!(stats.avg_duration, Some(Duration::from_millis(667_500)));
+    }
+
+    #[test]
+    fn test_summarize_no_runs() {
+        let stats = summarize_runs(&[]);
+        assert_eq!(stats, AbyssRunStats::default());
+    }
+
+    #[test]
+    fn test_run_abandoned_at_session_boundary() {
+        let parser = ChatlogParser::new();
+        let lines: Vec<String> = [
+            "          Session started: 2026.01.03 11:00:00",
+            "[ 2026.01.03 11:00:05 ] EVE System > Channel changed to Local : Torrinos",
+            "[ 2026.01.03 11:05:00 ] EVE System > Channel changed to Local : Unknown",
+            "          Session started: 2026.01.03 12:30:00",
+            "[ 2026.01.03 12:30:05 ] EVE System > Channel changed to Local : Jita",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let changes = parser.parse_lines(&lines);
+        let sessions = parser.parse_session_starts(&lines);
+        assert_eq!(sessions.len(), 2);
+
+        let runs = detect_abyss_runs_with_sessions(&changes, &sessions, DEFAULT_ABYSS_MARKERS);
+        assert_eq!(runs.len(), 1);
+        assert!(runs[0].abandoned);
+        assert!(runs[0].exit_time.is_none());
+        assert_eq!(runs[0].origin_location, Some("Torrinos".to_string()));
+
+        let stats = summarize_runs(&runs);
+        assert_eq!(stats.abandoned, 1);
+        assert_eq!(stats.completed, 0);
+
+        // Without session info the next session's location looks like an exit
+        let naive = detect_abyss_runs(&changes);
+        assert!(!naive[0].abandoned);
+        assert!(naive[0].exit_time.is_some());
+    }
 }
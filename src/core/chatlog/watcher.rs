@@ -6,7 +6,7 @@ use std::collections::HashMap;
 use std::io;
 use std::path::{Path, PathBuf};
 
-use super::parser::{ChatlogParser, LocationChange};
+use super::parser::{self, ChatlogParser, LocationChange};
 use crate::core::discovery;
 use crate::core::log_io::LogTailer;
 
@@ -51,9 +51,11 @@ impl LocalChatlogTracker {
         self.last_location.as_deref()
     }
 
-    /// Check if currently in the Abyss (last location was "Unknown").
-    pub fn is_in_abyss(&self) -> bool {
-        self.last_location.as_deref() == Some("Unknown")
+    /// Check if currently in the Abyss (last location matches one of `markers`).
+    pub fn is_in_abyss<S: AsRef<str>>(&self, markers: &[S]) -> bool {
+        self.last_location
+            .as_deref()
+            .is_some_and(|loc| parser::is_abyss_location(loc, markers))
     }
 
     /// Rewind to the start of the file (for full replay).
@@ -148,12 +150,12 @@ impl ChatlogWatcher {
             .and_then(|t| t.last_location())
     }
 
-    /// Check if a character is currently in the Abyss.
-    pub fn is_in_abyss(&self, character_id: u64) -> bool {
+    /// Check if a character is currently in the Abyss, given the configured
+    /// Abyss location markers.
+    pub fn is_in_abyss<S: AsRef<str>>(&self, character_id: u64, markers: &[S]) -> bool {
         self.trackers
             .get(&character_id)
-            .map(|t| t.is_in_abyss())
-            .unwrap_or(false)
+            .is_some_and(|t| t.is_in_abyss(markers))
     }
 
     /// Get all tracked character IDs.
@@ -216,7 +218,7 @@ mod tests {
         let changes = tracker.read_location_changes().unwrap();
         assert_eq!(changes.len(), 1);
         assert_eq!(changes[0].location, "Torrinos");
-        assert!(!tracker.is_in_abyss());
+        assert!(!tracker.is_in_abyss(parser::DEFAULT_ABYSS_MARKERS));
 
         // Add more lines
         let mut file = std::fs::OpenOptions::new()
@@ -234,7 +236,49 @@ mod tests {
         let changes = tracker.read_location_changes().unwrap();
         assert_eq!(changes.len(), 1);
         assert_eq!(changes[0].location, "Unknown");
-        assert!(tracker.is_in_abyss());
+        assert!(tracker.is_in_abyss(parser::DEFAULT_ABYSS_MARKERS));
+
+        // Configured markers replace the defaults
+        assert!(!tracker.is_in_abyss(&["Abyssal Deadspace"]));
+        writeln!(
+            file,
+            "[ 2026.01.03 11:31:00 ] EVE System > Channel changed to Local : Abyssal Deadspace"
+        )
+        .unwrap();
+        file.sync_all().unwrap();
+        tracker.read_location_changes().unwrap();
+        assert!(tracker.is_in_abyss(&["Abyssal Deadspace"]));
+        assert!(!tracker.is_in_abyss(&["Unknown"]));
+    }
+
+    #[test]
+    fn test_half_written_line_is_parsed_once() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Local_20260103_112630_12345.txt");
+        create_local_chatlog(&path, "TestChar");
+
+        let mut tracker =
+            LocalChatlogTracker::new("TestChar".to_string(), 12345, path.clone()).unwrap();
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+
+        // The client flushes the first half of the line
+        write!(
+            file,
+            "[ 2026.01.03 11:30:05 ] EVE System > Channel changed to Lo"
+        )
+        .unwrap();
+        file.sync_all().unwrap();
+        assert!(tracker.read_location_changes().unwrap().is_empty());
+
+        writeln!(file, "cal : Unknown").unwrap();
+        file.sync_all().unwrap();
+        let changes = tracker.read_location_changes().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].location, "Unknown");
+        assert!(tracker.read_location_changes().unwrap().is_empty());
     }
 
     #[test]
@@ -265,7 +309,8 @@ mod tests {
 
         // Check last location
         assert_eq!(watcher.last_location(12345), Some("Torrinos"));
-        assert!(!watcher.is_in_abyss(12345));
+        assert!(!watcher.is_in_abyss(12345, parser::DEFAULT_ABYSS_MARKERS));
+        assert!(watcher.is_in_abyss(12345, &["Torrinos"]));
 
         // Stop tracking
         assert!(watcher.stop_tracking(12345));
@@ -1,21 +1,225 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::io;
+use std::io::{self, Write};
 use std::path::PathBuf;
 
 use super::alerts::engine::AlertEngineConfig;
+use super::chatlog::parser::default_abyss_markers;
+use super::model::{default_environmental_sources, AttackerKind, DpsSample, NumberFormat, TzMode};
+use super::parser::LocaleConfig;
 
 /// Application settings with alert configuration.
 /// NOTE: TypeScript mirror types are in ui/src/types.ts
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Settings {
+    /// Primary gamelog directory (always the first entry of `gamelog_dirs`)
     pub gamelog_dir: PathBuf,
+    /// Every gamelog directory to scan, e.g. one per EVE install when multiboxing.
+    /// Older configs without this field are migrated from `gamelog_dir` on load.
+    #[serde(default)]
+    pub gamelog_dirs: Vec<PathBuf>,
     pub dps_window_seconds: u64,
     /// Alert system configuration
     #[serde(default)]
     pub alert_settings: AlertEngineConfig,
+    /// Local location names (substring match) that signal being inside the Abyss
+    #[serde(default = "default_abyss_markers")]
+    pub abyss_location_markers: Vec<String>,
+    /// Time zone for timestamps shown in the UI (bookmarks, replay)
+    #[serde(default)]
+    pub display_timezone: TzMode,
+    /// How DPS and damage totals are written (plain, thousands separators, k/M)
+    #[serde(default)]
+    pub number_format: NumberFormat,
+    /// Per-character RGBA colors for charts; unset characters get a default
+    #[serde(default)]
+    pub character_colors: HashMap<String, [u8; 4]>,
+    /// Clear the peak DPS readouts when a character enters the Abyss
+    #[serde(default = "default_true")]
+    pub reset_peaks_on_run_start: bool,
+    /// Stream combat events and DPS samples as JSON lines to this file (`-` for stdout)
+    #[serde(default)]
+    pub jsonl_sink: Option<PathBuf>,
+    /// Ignore combat where no tracked character is the source or target
+    #[serde(default)]
+    pub tracked_only_combat: bool,
+    /// How much DPS history the charts show
+    #[serde(default = "default_chart_history_seconds")]
+    pub chart_history_seconds: u64,
+    /// Which damage directions the charts plot
+    #[serde(default)]
+    pub chart_lines: ChartLines,
+    /// Attacker names whose NPC/player classification is forced
+    #[serde(default)]
+    pub attacker_overrides: HashMap<String, AttackerKind>,
+    /// On startup, track the most recently active character if nothing is tracked yet
+    #[serde(default)]
+    pub auto_track_latest: bool,
+    /// RGBA color for outgoing damage and highlights
+    #[serde(default = "default_accent_color")]
+    pub accent_color: [u8; 4],
+    /// RGBA color for incoming damage and danger readouts
+    #[serde(default = "default_incoming_color")]
+    pub incoming_color: [u8; 4],
+    #[serde(default)]
+    pub theme: Theme,
+    /// DPS window lengths (seconds) that `cycle_dps_window` steps through
+    #[serde(default = "default_window_presets")]
+    pub window_presets: Vec<u64>,
+    /// Seconds after the last combat event before the DPS clock stops; 0 never stops
+    #[serde(default = "default_idle_timeout_seconds")]
+    pub idle_timeout_seconds: u64,
+    /// Incoming damage sources (substring match) treated as Abyss hazards, not attackers
+    #[serde(default = "default_environmental_sources")]
+    pub environmental_sources: Vec<String>,
+    /// Overlay is locked in place: title-bar drags and resizing are ignored
+    #[serde(default)]
+    pub locked: bool,
+    /// How much of the overlay is shown, whatever the window size
+    #[serde(default)]
+    pub display_mode: DisplayMode,
+    /// Raw character name -> pilot whose stats it is reported under
+    #[serde(default)]
+    pub character_aliases: HashMap<String, String>,
+    /// Combat marker and direction words of the game client's language
+    #[serde(default)]
+    pub locale: LocaleConfig,
+}
+
+/// Whether a drag on the overlay should move the window
+pub fn should_handle_drag(locked: bool) -> bool {
+    !locked
+}
+
+/// Which damage directions the charts plot
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChartLines {
+    #[serde(default = "default_true")]
+    pub show_outgoing: bool,
+    #[serde(default = "default_true")]
+    pub show_incoming: bool,
+}
+
+impl Default for ChartLines {
+    fn default() -> Self {
+        Self {
+            show_outgoing: true,
+            show_incoming: true,
+        }
+    }
+}
+
+/// One line of the DPS chart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ChartSeries {
+    Outgoing,
+    Incoming,
+}
+
+/// The chart points to plot for `sample`, leaving out hidden directions.
+pub fn chart_points(lines: ChartLines, sample: &DpsSample) -> Vec<(ChartSeries, f32)> {
+    let mut points = Vec::new();
+    if lines.show_outgoing {
+        points.push((ChartSeries::Outgoing, sample.outgoing_dps));
+    }
+    if lines.show_incoming {
+        points.push((ChartSeries::Incoming, sample.incoming_dps));
+    }
+    points
+}
+
+/// How much the overlay shows
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DisplayMode {
+    #[default]
+    Full,
+    /// Totals and one row per character, no weapon lists
+    Compact,
+    /// The OUT/IN totals row and nothing else
+    NumbersOnly,
+}
+
+/// Parts of the overlay a display mode shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Sections {
+    /// OUT/IN totals row
+    pub totals: bool,
+    /// Per-character rows
+    pub character_rows: bool,
+    /// Weapon and target lists under each character
+    pub action_details: bool,
+}
+
+pub fn visible_sections(mode: DisplayMode) -> Sections {
+    match mode {
+        DisplayMode::Full => Sections {
+            totals: true,
+            character_rows: true,
+            action_details: true,
+        },
+        DisplayMode::Compact => Sections {
+            totals: true,
+            character_rows: true,
+            action_details: false,
+        },
+        DisplayMode::NumbersOnly => Sections {
+            totals: true,
+            character_rows: false,
+            action_details: false,
+        },
+    }
+}
+
+/// Overall color scheme of the windows
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Theme {
+    Light,
+    #[default]
+    Dark,
+}
+
+fn default_true() -> bool {
+    true
 }
 
+fn default_chart_history_seconds() -> u64 {
+    120
+}
+
+fn default_idle_timeout_seconds() -> u64 {
+    15
+}
+
+fn default_window_presets() -> Vec<u64> {
+    vec![3, 5, 10, 30]
+}
+
+fn default_accent_color() -> [u8; 4] {
+    [0x00, 0xe5, 0xff, 0xff]
+}
+
+fn default_incoming_color() -> [u8; 4] {
+    [0xff, 0x4d, 0x4d, 0xff]
+}
+
+/// Allowed range for `dps_window_seconds`
+const DPS_WINDOW_SECS: std::ops::RangeInclusive<u64> = 1..=300;
+/// Allowed range for `chart_history_seconds`
+const CHART_HISTORY_SECS: std::ops::RangeInclusive<u64> = 10..=3600;
+
+/// Default character colors, picked by name hash
+const CHARACTER_PALETTE: &[[u8; 4]] = &[
+    [0x4e, 0xa8, 0xde, 0xff], // blue
+    [0xf2, 0x8e, 0x2b, 0xff], // orange
+    [0x59, 0xc2, 0x6b, 0xff], // green
+    [0xe1, 0x57, 0x59, 0xff], // red
+    [0xb0, 0x7a, 0xe0, 0xff], // purple
+    [0xed, 0xc9, 0x48, 0xff], // yellow
+    [0x76, 0xd7, 0xc4, 0xff], // teal
+    [0xff, 0x9d, 0xa7, 0xff], // pink
+];
+
 impl Default for Settings {
     fn default() -> Self {
         // Try to guess the default EVE log path, or fallback to something safe
@@ -27,13 +231,142 @@ impl Default for Settings {
         let default_path = PathBuf::from(home).join("Documents/EVE/logs/Gamelogs");
 
         Self {
+            gamelog_dirs: vec![default_path.clone()],
             gamelog_dir: default_path,
             dps_window_seconds: 5,
             alert_settings: AlertEngineConfig::default_enabled(),
+            abyss_location_markers: default_abyss_markers(),
+            display_timezone: TzMode::default(),
+            number_format: NumberFormat::default(),
+            character_colors: HashMap::new(),
+            reset_peaks_on_run_start: true,
+            jsonl_sink: None,
+            tracked_only_combat: false,
+            chart_history_seconds: default_chart_history_seconds(),
+            chart_lines: ChartLines::default(),
+            attacker_overrides: HashMap::new(),
+            auto_track_latest: false,
+            accent_color: default_accent_color(),
+            incoming_color: default_incoming_color(),
+            theme: Theme::default(),
+            window_presets: default_window_presets(),
+            idle_timeout_seconds: default_idle_timeout_seconds(),
+            environmental_sources: default_environmental_sources(),
+            locked: false,
+            display_mode: DisplayMode::default(),
+            character_aliases: HashMap::new(),
+            locale: LocaleConfig::default(),
         }
     }
 }
 
+impl Settings {
+    /// Keep `gamelog_dirs` consistent with the primary `gamelog_dir`, which is what
+    /// the directory picker edits.
+    pub fn normalize_gamelog_dirs(&mut self) {
+        match self.gamelog_dirs.first_mut() {
+            Some(first) => *first = self.gamelog_dir.clone(),
+            None => self.gamelog_dirs.push(self.gamelog_dir.clone()),
+        }
+        let mut seen = std::collections::HashSet::new();
+        self.gamelog_dirs.retain(|dir| seen.insert(dir.clone()));
+    }
+
+    /// `self` with the fields present in `partial` replaced. Dialogs send only
+    /// the fields they edit; everything else is kept.
+    pub fn merged_with(&self, partial: serde_json::Value) -> Result<Settings, String> {
+        let serde_json::Value::Object(fields) = partial else {
+            return Err("Settings must be a JSON object".to_string());
+        };
+        let mut merged = serde_json::to_value(self).map_err(|e| e.to_string())?;
+        if let serde_json::Value::Object(current) = &mut merged {
+            current.extend(fields);
+        }
+        serde_json::from_value(merged).map_err(|e| e.to_string())
+    }
+
+    /// Check settings coming from the UI before they are applied and persisted.
+    /// Out-of-range values are clamped; an empty gamelog directory is rejected.
+    pub fn validate(&mut self) -> Result<(), String> {
+        if self.gamelog_dir.as_os_str().is_empty() {
+            return Err("Gamelog directory must not be empty".to_string());
+        }
+        if !self.gamelog_dir.is_dir() {
+            println!(
+                "Warning: gamelog directory {} does not exist",
+                self.gamelog_dir.display()
+            );
+        }
+
+        self.dps_window_seconds = self
+            .dps_window_seconds
+            .clamp(*DPS_WINDOW_SECS.start(), *DPS_WINDOW_SECS.end());
+        self.chart_history_seconds = self
+            .chart_history_seconds
+            .clamp(*CHART_HISTORY_SECS.start(), *CHART_HISTORY_SECS.end());
+
+        for preset in &mut self.window_presets {
+            *preset = (*preset).clamp(*DPS_WINDOW_SECS.start(), *DPS_WINDOW_SECS.end());
+        }
+        self.window_presets.sort_unstable();
+        self.window_presets.dedup();
+        if self.window_presets.is_empty() {
+            self.window_presets = default_window_presets();
+        }
+
+        // A fully transparent color would hide the character; fall back to the default
+        self.character_colors
+            .retain(|name, color| !name.trim().is_empty() && color[3] > 0);
+        Ok(())
+    }
+
+    /// Switch the DPS window to the next larger preset, wrapping around to the
+    /// smallest. Returns the new window length in seconds.
+    pub fn cycle_dps_window(&mut self) -> u64 {
+        let current = self.dps_window_seconds;
+        if let Some(next) = self
+            .window_presets
+            .iter()
+            .copied()
+            .filter(|&preset| preset > current)
+            .min()
+            .or_else(|| self.window_presets.iter().copied().min())
+        {
+            self.dps_window_seconds = next;
+        }
+        self.dps_window_seconds
+    }
+
+    /// Step the DPS window to the next larger (`forward`) or smaller preset,
+    /// stopping at either end. Returns the new window length in seconds.
+    pub fn step_dps_window(&mut self, forward: bool) -> u64 {
+        let current = self.dps_window_seconds;
+        let presets = self.window_presets.iter().copied();
+        let next = if forward {
+            presets.filter(|&preset| preset > current).min()
+        } else {
+            presets.filter(|&preset| preset < current).max()
+        };
+        if let Some(next) = next {
+            self.dps_window_seconds = next;
+        }
+        self.dps_window_seconds
+    }
+
+    /// RGBA color for a character: the configured one, or a palette color chosen
+    /// by a hash of the name that stays the same across runs and builds.
+    pub fn color_for(&self, name: &str) -> [u8; 4] {
+        if let Some(color) = self.character_colors.get(name) {
+            return *color;
+        }
+        // FNV-1a; std's hasher isn't guaranteed stable between releases
+        let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+        CHARACTER_PALETTE[(hash % CHARACTER_PALETTE.len() as u64) as usize]
+    }
+}
+
 pub struct ConfigManager {
     config_path: PathBuf,
 }
@@ -45,30 +378,60 @@ impl ConfigManager {
         }
     }
 
+    /// Load settings, falling back to the previous save if the file is unreadable
+    /// (e.g. truncated by a crash), then to defaults.
     pub fn load(&self) -> Settings {
-        if self.config_path.exists() {
-            if let Ok(content) = fs::read_to_string(&self.config_path) {
-                if let Ok(settings) = serde_json::from_str(&content) {
+        for path in [self.config_path.clone(), self.backup_path()] {
+            if let Ok(content) = fs::read_to_string(&path) {
+                if let Ok(mut settings) = serde_json::from_str::<Settings>(&content) {
+                    settings.normalize_gamelog_dirs();
                     return settings;
                 }
+                println!("Ignoring unparseable settings file {}", path.display());
             }
         }
         Settings::default()
     }
 
+    /// Write settings atomically: the new file is written next to the old one and
+    /// renamed over it, and the previous version is copied to a `.bak` backup.
     pub fn save(&self, settings: &Settings) -> io::Result<()> {
         // Ensure directory exists
         if let Some(parent) = self.config_path.parent() {
             fs::create_dir_all(parent)?;
         }
         let content = serde_json::to_string_pretty(settings)?;
-        fs::write(&self.config_path, content)
+
+        let tmp_path = self.config_path.with_extension("json.tmp");
+        {
+            let mut file = fs::File::create(&tmp_path)?;
+            file.write_all(content.as_bytes())?;
+            file.sync_all()?;
+        }
+        // Copy rather than move the old file, so settings.json is never missing
+        if self.config_path.exists() {
+            fs::copy(&self.config_path, self.backup_path())?;
+        }
+        fs::rename(&tmp_path, &self.config_path)
+    }
+
+    /// Folder where users can drop their own alert sounds (`<name>.wav`/`.ogg`)
+    pub fn sounds_dir(&self) -> PathBuf {
+        self.config_path
+            .parent()
+            .map(|dir| dir.join("sounds"))
+            .unwrap_or_else(|| PathBuf::from("sounds"))
+    }
+
+    fn backup_path(&self) -> PathBuf {
+        self.config_path.with_extension("json.bak")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::analysis::tests::{last_sample, make_event};
     use tempfile::tempdir;
 
     #[test]
@@ -82,7 +445,7 @@ mod tests {
         let new_settings = Settings {
             gamelog_dir: PathBuf::from("/tmp/logs"),
             dps_window_seconds: 10,
-            alert_settings: AlertEngineConfig::default_enabled(),
+            ..Settings::default()
         };
 
         manager.save(&new_settings).unwrap();
@@ -91,4 +454,265 @@ mod tests {
         assert_eq!(loaded.gamelog_dir, PathBuf::from("/tmp/logs"));
         assert_eq!(loaded.dps_window_seconds, 10);
     }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let dir = tempdir().unwrap();
+        let manager = ConfigManager::new(dir.path().to_path_buf());
+        fs::write(
+            dir.path().join("settings.json"),
+            r#"{"gamelog_dir": "/tmp/logs", "dps_window_seconds": 7}"#,
+        )
+        .unwrap();
+
+        let loaded = manager.load();
+        assert_eq!(loaded.dps_window_seconds, 7);
+        assert_eq!(loaded.abyss_location_markers, default_abyss_markers());
+        assert_eq!(loaded.display_timezone, TzMode::Utc);
+        // Old single-directory configs migrate to a one-entry list
+        assert_eq!(loaded.gamelog_dirs, vec![PathBuf::from("/tmp/logs")]);
+    }
+
+    #[test]
+    fn test_primary_dir_leads_gamelog_dirs() {
+        let mut settings = Settings {
+            gamelog_dir: PathBuf::from("/new/primary"),
+            gamelog_dirs: vec![
+                PathBuf::from("/old/primary"),
+                PathBuf::from("/wine2/Gamelogs"),
+                PathBuf::from("/new/primary"),
+            ],
+            ..Settings::default()
+        };
+        settings.normalize_gamelog_dirs();
+        assert_eq!(
+            settings.gamelog_dirs,
+            vec![
+                PathBuf::from("/new/primary"),
+                PathBuf::from("/wine2/Gamelogs")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_incoming_color_round_trips() {
+        let dir = tempdir().unwrap();
+        let manager = ConfigManager::new(dir.path().to_path_buf());
+
+        let settings = Settings {
+            incoming_color: [0x33, 0x66, 0xff, 0xff],
+            theme: Theme::Light,
+            ..Settings::default()
+        };
+        manager.save(&settings).unwrap();
+
+        let loaded = manager.load();
+        assert_eq!(loaded.incoming_color, [0x33, 0x66, 0xff, 0xff]);
+        assert_eq!(loaded.accent_color, default_accent_color());
+        assert_eq!(loaded.theme, Theme::Light);
+    }
+
+    #[test]
+    fn test_cycle_dps_window_wraps_through_presets() {
+        let mut settings = Settings {
+            dps_window_seconds: 5,
+            window_presets: vec![3, 5, 10, 30],
+            ..Settings::default()
+        };
+        assert_eq!(settings.cycle_dps_window(), 10);
+        assert_eq!(settings.cycle_dps_window(), 30);
+        assert_eq!(settings.cycle_dps_window(), 3);
+        assert_eq!(settings.dps_window_seconds, 3);
+
+        // A window that isn't a preset moves to the next one up
+        settings.dps_window_seconds = 7;
+        assert_eq!(settings.cycle_dps_window(), 10);
+    }
+
+    #[test]
+    fn test_step_dps_window_stops_at_ends() {
+        let mut settings = Settings {
+            dps_window_seconds: 5,
+            window_presets: vec![3, 5, 10, 30],
+            ..Settings::default()
+        };
+        assert_eq!(settings.step_dps_window(true), 10);
+        assert_eq!(settings.step_dps_window(true), 30);
+        assert_eq!(settings.step_dps_window(true), 30);
+        assert_eq!(settings.step_dps_window(false), 10);
+
+        settings.dps_window_seconds = 4;
+        assert_eq!(settings.step_dps_window(false), 3);
+        assert_eq!(settings.step_dps_window(false), 3);
+        assert_eq!(settings.dps_window_seconds, 3);
+    }
+
+    #[test]
+    fn test_character_colors_default_and_override() {
+        let mut settings = Settings::default();
+
+        let default = settings.color_for("Pilot One");
+        assert_eq!(settings.color_for("Pilot One"), default);
+        assert!(CHARACTER_PALETTE.contains(&default));
+
+        settings
+            .character_colors
+            .insert("Pilot One".to_string(), [1, 2, 3, 255]);
+        assert_eq!(settings.color_for("Pilot One"), [1, 2, 3, 255]);
+    }
+
+    #[test]
+    fn test_locked_overlay_ignores_drags() {
+        let settings = Settings::default();
+        assert!(!settings.locked);
+        assert!(should_handle_drag(settings.locked));
+        assert!(!should_handle_drag(true));
+
+        // Older settings files have no `locked` field
+        let json = serde_json::to_value(&settings).unwrap();
+        let mut map = json.as_object().unwrap().clone();
+        map.remove("locked");
+        let loaded: Settings = serde_json::from_value(map.into()).unwrap();
+        assert!(!loaded.locked);
+    }
+
+    #[test]
+    fn test_visible_sections_per_display_mode() {
+        assert_eq!(Settings::default().display_mode, DisplayMode::Full);
+        assert_eq!(
+            visible_sections(DisplayMode::Full),
+            Sections {
+                totals: true,
+                character_rows: true,
+                action_details: true,
+            }
+        );
+        assert_eq!(
+            visible_sections(DisplayMode::Compact),
+            Sections {
+                totals: true,
+                character_rows: true,
+                action_details: false,
+            }
+        );
+        assert_eq!(
+            visible_sections(DisplayMode::NumbersOnly),
+            Sections {
+                totals: true,
+                character_rows: false,
+                action_details: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_chart_points_follow_chart_lines() {
+        let hit = |amount, incoming| make_event(0, amount, incoming, "Pilot", "Starving Damavik");
+        let sample = last_sample(&[hit(300.0, false), hit(120.0, true)]);
+        let lines = |show_outgoing, show_incoming| ChartLines {
+            show_outgoing,
+            show_incoming,
+        };
+
+        assert_eq!(Settings::default().chart_lines, lines(true, true));
+        assert_eq!(
+            chart_points(lines(true, true), &sample),
+            vec![
+                (ChartSeries::Outgoing, 300.0),
+                (ChartSeries::Incoming, 120.0)
+            ]
+        );
+        assert_eq!(
+            chart_points(lines(true, false), &sample),
+            vec![(ChartSeries::Outgoing, 300.0)]
+        );
+        assert_eq!(
+            chart_points(lines(false, true), &sample),
+            vec![(ChartSeries::Incoming, 120.0)]
+        );
+        assert!(chart_points(lines(false, false), &sample).is_empty());
+    }
+
+    #[test]
+    fn test_validate_clamps_dps_window() {
+        let mut settings = Settings {
+            dps_window_seconds: 0,
+            ..Settings::default()
+        };
+        settings.validate().unwrap();
+        assert_eq!(settings.dps_window_seconds, 1);
+
+        settings.dps_window_seconds = 3600;
+        settings.validate().unwrap();
+        assert_eq!(settings.dps_window_seconds, 300);
+
+        settings.gamelog_dir = PathBuf::new();
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_corrupt_settings_fall_back_to_backup() {
+        let dir = tempdir().unwrap();
+        let manager = ConfigManager::new(dir.path().to_path_buf());
+
+        let first = Settings {
+            dps_window_seconds: 12,
+            ..Settings::default()
+        };
+        manager.save(&first).unwrap();
+        manager
+            .save(&Settings {
+                dps_window_seconds: 20,
+                ..Settings::default()
+            })
+            .unwrap();
+        assert!(!dir.path().join("settings.json.tmp").exists());
+        assert!(dir.path().join("settings.json.bak").exists());
+        assert_eq!(manager.load().dps_window_seconds, 20);
+
+        // Simulate a crash that truncated the primary file
+        fs::write(dir.path().join("settings.json"), r#"{"gamelog_dir": "/tm"#).unwrap();
+
+        let loaded = manager.load();
+        assert_eq!(loaded.dps_window_seconds, 12);
+    }
+
+    #[test]
+    fn test_partial_save_keeps_other_fields() {
+        let mut current = Settings::default();
+        current.gamelog_dirs = vec![PathBuf::from("/logs/a"), PathBuf::from("/logs/b")];
+        current
+            .character_colors
+            .insert("Pilot One".to_string(), [1, 2, 3, 255]);
+        current
+            .attacker_overrides
+            .insert("Rogue Drone".to_string(), AttackerKind::Npc);
+        current.locked = true;
+        current
+            .character_aliases
+            .insert("Pilot Alt".to_string(), "Pilot One".to_string());
+        current.theme = Theme::Light;
+
+        // The fields the settings modal sends
+        let modal = serde_json::json!({
+            "gamelog_dir": "/logs/a",
+            "dps_window_seconds": 10,
+            "number_format": "Suffixed",
+            "display_mode": "Compact",
+            "alert_settings": AlertEngineConfig::default(),
+        });
+        let merged = current.merged_with(modal).unwrap();
+
+        assert_eq!(merged.dps_window_seconds, 10);
+        assert_eq!(merged.number_format, NumberFormat::Suffixed);
+        assert_eq!(merged.display_mode, DisplayMode::Compact);
+        assert_eq!(merged.gamelog_dirs, current.gamelog_dirs);
+        assert_eq!(merged.character_colors, current.character_colors);
+        assert_eq!(merged.attacker_overrides, current.attacker_overrides);
+        assert!(merged.locked);
+        assert_eq!(merged.character_aliases, current.character_aliases);
+        assert_eq!(merged.theme, Theme::Light);
+
+        assert!(current.merged_with(serde_json::json!([])).is_err());
+    }
 }
@@ -1,14 +1,37 @@
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex};
 use std::time::{Duration, SystemTime};
 
-use super::chatlog::parser::LocationChange;
+use serde::Serialize;
+
+use super::analysis;
+use super::chatlog::parser::{self as chatlog_parser, LocationChange};
 use super::chatlog::watcher::ChatlogWatcher;
+use super::config::{ConfigManager, Settings};
 use super::discovery;
-use super::model::{CombatEvent, DpsSample, NotifyEvent};
+use super::inline_bookmarks::BookmarkWriter;
+use super::log_io::{JsonlRecord, JsonlSink};
+use super::model::{CombatEvent, DpsSample, EventType, KillEvent, NotifyEvent, RunSummary};
+use super::parser::LocaleConfig;
 use super::state::EngineState;
 use super::watcher::LogWatcher;
 
+/// How much combat history the live engine keeps. The overlay only ever
+/// looks at the last minute, but run summaries need the whole run, and the
+/// Abyss timer caps a run at 20 minutes.
+const LIVE_RETENTION: Duration = Duration::from_secs(30 * 60);
+
+/// Default for `Coordinator::set_idle_timeout`
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(15);
+
+fn live_engine() -> EngineState {
+    let mut engine = EngineState::new();
+    engine.set_retention(LIVE_RETENTION);
+    engine
+}
+
 /// A location change event with character context.
 #[derive(Debug, Clone)]
 pub struct CharacterLocationChange {
@@ -18,6 +41,69 @@ pub struct CharacterLocationChange {
     pub change: LocationChange,
 }
 
+/// Why some tracked logs can't be read right now. Reported on every tick
+/// until the path is back.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum TrackingError {
+    /// A configured gamelog directory doesn't exist (deleted, drive unmounted)
+    LogDirMissing(PathBuf),
+    /// A tracked gamelog file doesn't exist
+    LogFileMissing(PathBuf),
+}
+
+/// One tracked character's current state, for a fleet roster
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CharacterStatus {
+    pub character: String,
+    /// From the latest DPS sample; 0 when the character isn't in it
+    pub outgoing_dps: f32,
+    pub incoming_dps: f32,
+    pub in_abyss: bool,
+    /// Log timestamp of the character's newest combat event, if any
+    pub last_seen: Option<Duration>,
+}
+
+/// One entry of a merged gamelog + chatlog timeline
+#[derive(Debug, Clone, Serialize)]
+pub enum TimelineEntry {
+    Combat(CombatEvent),
+    Location(LocationChange),
+}
+
+impl TimelineEntry {
+    pub fn timestamp(&self) -> Duration {
+        match self {
+            TimelineEntry::Combat(event) => event.timestamp,
+            TimelineEntry::Location(change) => change.timestamp,
+        }
+    }
+}
+
+/// Combine combat events and location changes into one chronological stream.
+/// On equal timestamps combat comes first, and each input keeps its own order.
+pub fn merged_timeline(
+    gamelog_events: Vec<CombatEvent>,
+    location_changes: Vec<LocationChange>,
+) -> Vec<TimelineEntry> {
+    let mut timeline: Vec<TimelineEntry> = gamelog_events
+        .into_iter()
+        .map(TimelineEntry::Combat)
+        .chain(location_changes.into_iter().map(TimelineEntry::Location))
+        .collect();
+    timeline.sort_by_key(TimelineEntry::timestamp);
+    timeline
+}
+
+/// Whether a tracked character is in an Abyss run right now
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunStatus {
+    pub in_abyss: bool,
+    /// When the current run started (seconds since the Unix epoch), if one is open
+    pub entered_at_secs: Option<u64>,
+    /// Seconds since `entered_at_secs`; 0 outside a run
+    pub elapsed_secs: u64,
+}
+
 pub struct CoordinatorOutput {
     pub dps_sample: Option<DpsSample>,
     pub logs: Vec<String>,
@@ -27,13 +113,22 @@ pub struct CoordinatorOutput {
     pub new_combat_events: Vec<CombatEvent>,
     /// New notify events since last tick (for alert evaluation)
     pub new_notify_events: Vec<NotifyEvent>,
+    /// Stats for Abyss runs that closed this tick
+    pub run_summaries: Vec<RunSummary>,
+    /// Peak DPS since the last reset (see `set_reset_peaks_on_run_start`)
+    pub peak_outgoing_dps: f32,
+    pub peak_incoming_dps: f32,
+    /// Missing log directories/files; empty when everything is readable
+    pub tracking_errors: Vec<TrackingError>,
+    /// Epoch seconds of recent inline bookmarks, for session markers on the charts
+    pub session_markers: Vec<u64>,
 }
 
 pub struct Coordinator {
     watcher: LogWatcher,
     chatlog_watcher: ChatlogWatcher,
     engine: EngineState,
-    log_dir: PathBuf,
+    log_dirs: Vec<PathBuf>,
 
     // State for time tracking
     last_event_timestamp: Option<Duration>,
@@ -41,23 +136,166 @@ pub struct Coordinator {
     current_tracked_set: HashSet<PathBuf>,
 
     /// Maps gamelog path -> (character_name, character_id) for chatlog tracking
-    tracked_characters: std::collections::HashMap<PathBuf, (String, u64)>,
+    tracked_characters: HashMap<PathBuf, (String, u64)>,
+
+    /// While paused, combat events and kills are buffered here instead of reaching the engine
+    paused: bool,
+    paused_events: Vec<CombatEvent>,
+    paused_kills: Vec<KillEvent>,
+    /// Last computed sample, repeated while paused
+    last_sample: Option<DpsSample>,
+
+    /// Location names that mean "in the Abyss"
+    abyss_markers: Vec<String>,
+    /// Open runs: character_id -> entry time (chatlog timestamp, since the Unix epoch)
+    open_runs: HashMap<u64, Duration>,
+    /// Whether a run start clears the engine's peak DPS
+    reset_peaks_on_run_start: bool,
+    /// Only count combat a tracked character dealt or received
+    tracked_only: bool,
+    /// Newest combat event timestamp per character
+    last_seen: HashMap<String, Duration>,
+    /// The DPS clock stops this long after the last event; zero never stops it
+    idle_timeout: Duration,
+    /// Configured JSON-lines output path, and the open sink if it could be opened
+    jsonl_path: Option<PathBuf>,
+    jsonl_sink: Option<JsonlSink>,
 }
 
 impl Coordinator {
-    pub fn new(log_dir: PathBuf) -> Self {
+    pub fn new(log_dirs: Vec<PathBuf>) -> Self {
         Self {
             watcher: LogWatcher::new(),
             chatlog_watcher: ChatlogWatcher::new(),
-            engine: EngineState::new(),
-            log_dir,
+            engine: live_engine(),
+            log_dirs,
             last_event_timestamp: None,
             last_event_wallclock: None,
             current_tracked_set: HashSet::new(),
-            tracked_characters: std::collections::HashMap::new(),
+            tracked_characters: HashMap::new(),
+            paused: false,
+            paused_events: Vec::new(),
+            paused_kills: Vec::new(),
+            last_sample: None,
+            abyss_markers: chatlog_parser::default_abyss_markers(),
+            open_runs: HashMap::new(),
+            reset_peaks_on_run_start: true,
+            tracked_only: false,
+            last_seen: HashMap::new(),
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            jsonl_path: None,
+            jsonl_sink: None,
+        }
+    }
+
+    pub fn set_reset_peaks_on_run_start(&mut self, enabled: bool) {
+        self.reset_peaks_on_run_start = enabled;
+    }
+
+    /// Stop advancing the DPS clock once no event has arrived for `timeout`,
+    /// so the series holds after combat ends. Zero keeps it running.
+    pub fn set_idle_timeout(&mut self, timeout: Duration) {
+        self.idle_timeout = timeout;
+    }
+
+    /// Drop combat between third parties before it reaches the engine.
+    pub fn set_tracked_only(&mut self, enabled: bool) {
+        self.tracked_only = enabled;
+    }
+
+    /// Stream new combat events and DPS samples as JSON lines to `path` (`-` for
+    /// stdout), or stop streaming with `None`. Only reopens when the path changes.
+    pub fn set_jsonl_sink(&mut self, path: Option<&Path>) {
+        if self.jsonl_path.as_deref() == path {
+            return;
+        }
+        self.jsonl_path = path.map(Path::to_path_buf);
+        self.jsonl_sink = path.and_then(|path| match JsonlSink::open(path) {
+            Ok(sink) => Some(sink),
+            Err(e) => {
+                println!("Failed to open JSON-lines sink {}: {}", path.display(), e);
+                None
+            }
+        });
+    }
+
+    /// Write this tick's events and new sample to the JSON-lines sink, if any.
+    /// A failing sink is dropped until the path is reconfigured.
+    fn write_jsonl(&mut self, events: &[CombatEvent], sample: Option<&DpsSample>) {
+        let Some(sink) = self.jsonl_sink.as_mut() else {
+            return;
+        };
+        let mut records: Vec<JsonlRecord> = events.iter().map(JsonlRecord::Event).collect();
+        records.extend(sample.map(JsonlRecord::Sample));
+        if records.is_empty() {
+            return;
+        }
+        if let Err(e) = sink.write_all(&records) {
+            println!(
+                "Failed to write JSON-lines sink {}: {}",
+                sink.path().display(),
+                e
+            );
+            self.jsonl_sink = None;
         }
     }
 
+    /// Write queued bookmarks, then flush and close the JSON-lines sink before
+    /// the app exits.
+    pub fn shutdown(&mut self, bookmarks: &mut BookmarkWriter) {
+        if let Err(e) = bookmarks.flush() {
+            println!("Failed to write bookmarks on exit: {}", e);
+        }
+        if let Some(mut sink) = self.jsonl_sink.take() {
+            if let Err(e) = sink.flush() {
+                println!(
+                    "Failed to flush JSON-lines sink {}: {}",
+                    sink.path().display(),
+                    e
+                );
+            }
+        }
+        self.jsonl_path = None;
+    }
+
+    /// Focus outgoing DPS on one target, or count every target with `None`.
+    pub fn set_focus_target(&mut self, target: Option<&str>) {
+        if self.engine.focus_target() != target {
+            self.engine.set_focus_target(target.map(str::to_string));
+        }
+    }
+
+    /// Set the location markers used to tell Abyss entries from exits.
+    pub fn set_abyss_markers(&mut self, markers: &[String]) {
+        if self.abyss_markers != markers {
+            self.abyss_markers = markers.to_vec();
+        }
+    }
+
+    /// Set the combat tokens of the game client's language.
+    pub fn set_locale(&mut self, locale: &LocaleConfig) {
+        self.watcher.set_locale(locale);
+    }
+
+    /// Freeze the live numbers without losing data. While paused, logs are still
+    /// read (and events still reported for alerts) but combat events are buffered
+    /// and the DPS sample stays at its last value. Resuming flushes the buffer.
+    pub fn set_paused(&mut self, paused: bool) {
+        if self.paused && !paused {
+            for event in self.paused_events.drain(..) {
+                self.engine.push_event(event);
+            }
+            for kill in self.paused_kills.drain(..) {
+                self.engine.push_kill(kill);
+            }
+        }
+        self.paused = paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
     pub fn tick(
         &mut self,
         active_paths: &HashSet<PathBuf>,
@@ -67,6 +305,7 @@ impl Coordinator {
         let mut location_changes = Vec::new();
         let mut new_combat_events = Vec::new();
         let new_notify_events;
+        let mut run_summaries = Vec::new();
 
         // 1. Update Tracked Paths
         if *active_paths != self.current_tracked_set {
@@ -77,14 +316,18 @@ impl Coordinator {
                 .next()
                 .is_some();
             if removed {
-                self.engine = EngineState::new();
+                self.engine = live_engine();
+                self.paused_events.clear();
+                self.paused_kills.clear();
+                self.last_sample = None;
+                self.open_runs.clear();
                 self.last_event_timestamp = None;
                 self.last_event_wallclock = None;
             }
 
             let msgs = self
                 .watcher
-                .update_active_paths(active_paths, &self.log_dir);
+                .update_active_paths(active_paths, &self.log_dirs);
             logs.extend(msgs);
 
             // Start/stop chatlog tracking for each character
@@ -94,8 +337,28 @@ impl Coordinator {
         }
 
         // 2. Poll Combat and Notify Events
-        let (combat_events, notify_events, poll_msgs) = self.watcher.read_events();
+        let (mut combat_events, notify_events, poll_msgs) = self.watcher.read_events();
         logs.extend(poll_msgs);
+        let mut kills = self.watcher.take_kill_events();
+        let session_markers = self.watcher.marker_times();
+        if self.tracked_only {
+            let names = self.watcher.character_names();
+            analysis::retain_tracked_involvement(&mut combat_events, &names);
+        }
+
+        let mut tracking_errors: Vec<TrackingError> = self
+            .log_dirs
+            .iter()
+            .filter(|dir| !dir.exists())
+            .cloned()
+            .map(TrackingError::LogDirMissing)
+            .collect();
+        tracking_errors.extend(
+            self.watcher
+                .missing_paths()
+                .into_iter()
+                .map(TrackingError::LogFileMissing),
+        );
 
         // Store for alert evaluation
         new_notify_events = notify_events;
@@ -107,48 +370,123 @@ impl Coordinator {
                     self.last_event_timestamp
                         .map_or(event.timestamp, |prev| prev.max(event.timestamp)),
                 );
-                self.engine.push_event(event.clone());
+                let seen = self.last_seen.entry(event.character.clone()).or_default();
+                *seen = (*seen).max(event.timestamp);
+                if self.paused {
+                    self.paused_events.push(event.clone());
+                } else {
+                    self.engine.push_event(event.clone());
+                }
             }
             self.last_event_wallclock = Some(now_wallclock);
             new_combat_events = combat_events;
         }
 
+        // Kills pass the same gates as combat: only ships a tracked character
+        // shot at count, and they wait out a pause
+        if self.tracked_only {
+            kills.retain(|kill| {
+                self.engine
+                    .events()
+                    .iter()
+                    .chain(&self.paused_events)
+                    .any(|event| {
+                        !event.incoming
+                            && event.event_type == EventType::Damage
+                            && event.target == kill.victim
+                    })
+            });
+        }
+        for kill in kills {
+            logs.push(format!("{} destroyed {}", kill.character, kill.victim));
+            if self.paused {
+                self.paused_kills.push(kill);
+            } else {
+                self.engine.push_kill(kill);
+            }
+        }
+
         // 3. Poll Location Changes from Chatlogs
         let all_changes = self.chatlog_watcher.read_all_changes();
         for (char_id, changes) in all_changes {
             // Find the gamelog path for this character
-            if let Some((gamelog_path, (char_name, _))) = self
+            let Some((gamelog_path, char_name)) = self
                 .tracked_characters
                 .iter()
                 .find(|(_, (_, id))| *id == char_id)
-            {
-                for change in changes {
-                    logs.push(format!("{} moved to: {}", char_name, change.location));
-                    location_changes.push(CharacterLocationChange {
-                        character_name: char_name.clone(),
-                        character_id: char_id,
-                        gamelog_path: gamelog_path.clone(),
-                        change,
-                    });
+                .map(|(path, (name, _))| (path.clone(), name.clone()))
+            else {
+                continue;
+            };
+
+            for change in changes {
+                logs.push(format!("{} moved to: {}", char_name, change.location));
+
+                // Track run boundaries so a closing run can be summarized
+                if change.is_abyss_entry_with(&self.abyss_markers) {
+                    if !self.open_runs.contains_key(&char_id) {
+                        self.open_runs.insert(char_id, change.timestamp);
+                        if self.reset_peaks_on_run_start {
+                            self.engine.reset_peaks();
+                        }
+                    }
+                } else if let Some(entered_at) = self.open_runs.remove(&char_id) {
+                    run_summaries.push(self.summarize_run(
+                        &gamelog_path,
+                        &char_name,
+                        entered_at,
+                        change.timestamp,
+                        dps_window,
+                    ));
                 }
+
+                location_changes.push(CharacterLocationChange {
+                    character_name: char_name.clone(),
+                    character_id: char_id,
+                    gamelog_path: gamelog_path.clone(),
+                    change,
+                });
             }
         }
 
-        // 4. Compute DPS
+        // 4. Compute DPS (frozen while paused)
+        if self.paused {
+            self.write_jsonl(&new_combat_events, None);
+            return CoordinatorOutput {
+                dps_sample: self.last_sample.clone(),
+                logs,
+                location_changes,
+                new_combat_events,
+                new_notify_events,
+                run_summaries,
+                peak_outgoing_dps: self.engine.peak_outgoing(),
+                peak_incoming_dps: self.engine.peak_incoming(),
+                tracking_errors,
+                session_markers,
+            };
+        }
+
         let end_time = match (self.last_event_timestamp, self.last_event_wallclock) {
             (Some(timestamp), Some(seen_at)) => {
-                if let Ok(elapsed) = SystemTime::now().duration_since(seen_at) {
+                let elapsed = SystemTime::now()
+                    .duration_since(seen_at)
+                    .unwrap_or_default();
+                if self.idle_timeout.is_zero() {
                     timestamp + elapsed
                 } else {
-                    timestamp
+                    timestamp + elapsed.min(self.idle_timeout)
                 }
             }
             (Some(timestamp), None) => timestamp,
             (None, _) => Duration::from_secs(0),
         };
 
-        let samples = self.engine.dps_series(dps_window, end_time);
-        let dps_sample = samples.into_iter().last();
+        let new_sample = self.engine.dps_tail(dps_window, end_time).pop();
+        self.write_jsonl(&new_combat_events, new_sample.as_ref());
+        if let Some(sample) = new_sample {
+            self.last_sample = Some(sample);
+        }
+        let dps_sample = self.last_sample.clone();
 
         CoordinatorOutput {
             dps_sample,
@@ -156,24 +494,124 @@ impl Coordinator {
             location_changes,
             new_combat_events,
             new_notify_events,
+            run_summaries,
+            peak_outgoing_dps: self.engine.peak_outgoing(),
+            peak_incoming_dps: self.engine.peak_incoming(),
+            tracking_errors,
+            session_markers,
         }
     }
 
-    /// Update chatlog tracking based on active gamelog paths
-    fn update_chatlog_tracking(&mut self, active_paths: &HashSet<PathBuf>, logs: &mut Vec<String>) {
-        // Derive chatlog dir from log_dir (gamelog dir)
-        let chatlog_dir = discovery::derive_chatlog_dir(&self.log_dir);
+    /// Run state of the character tracked through `gamelog_path`, or `None` if
+    /// that log isn't tracked or has no Local chat log.
+    pub fn run_status(&self, gamelog_path: &Path) -> Option<RunStatus> {
+        let (_, char_id) = self.tracked_characters.get(gamelog_path)?;
+        let in_abyss = self
+            .chatlog_watcher
+            .is_in_abyss(*char_id, &self.abyss_markers);
+        let entered_at = self.open_runs.get(char_id).filter(|_| in_abyss);
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        Some(RunStatus {
+            in_abyss,
+            entered_at_secs: entered_at.map(|at| at.as_secs()),
+            elapsed_secs: entered_at.map_or(0, |at| now.saturating_sub(*at).as_secs()),
+        })
+    }
 
-        if !chatlog_dir.exists() {
-            return;
+    /// Current DPS, Abyss state and last activity of every tracked character,
+    /// sorted by name.
+    pub fn fleet_status(&self) -> Vec<CharacterStatus> {
+        let sample = self.last_sample.as_ref();
+        self.watcher
+            .character_names()
+            .into_iter()
+            .map(|character| {
+                let in_abyss = self
+                    .tracked_characters
+                    .values()
+                    .find(|(name, _)| *name == character)
+                    .is_some_and(|(_, char_id)| {
+                        self.chatlog_watcher
+                            .is_in_abyss(*char_id, &self.abyss_markers)
+                    });
+                let dps = |by_character: fn(&DpsSample) -> &HashMap<String, f32>| {
+                    sample
+                        .and_then(|s| by_character(s).get(&character).copied())
+                        .unwrap_or(0.0)
+                };
+                CharacterStatus {
+                    outgoing_dps: dps(|s| &s.outgoing_by_character),
+                    incoming_dps: dps(|s| &s.incoming_by_character),
+                    in_abyss,
+                    last_seen: self.last_seen.get(&character).copied(),
+                    character,
+                }
+            })
+            .collect()
+    }
+
+    /// Combat stats for a run, given its chatlog entry/exit times (since the Unix epoch).
+    fn summarize_run(
+        &self,
+        gamelog_path: &Path,
+        character: &str,
+        entered_at: Duration,
+        exited_at: Duration,
+        window: Duration,
+    ) -> RunSummary {
+        let duration_secs = exited_at.saturating_sub(entered_at).as_secs();
+
+        // Event timestamps are relative to the log's base time, which is only
+        // known once the log has produced a combat line
+        let Some(base) = self
+            .watcher
+            .base_time(gamelog_path)
+            .map(|base| Duration::from_secs(base.and_utc().timestamp().max(0) as u64))
+        else {
+            return RunSummary {
+                duration_secs,
+                ..analysis::run_summary(character, &[], Duration::ZERO, Duration::ZERO, window)
+            };
+        };
+        let start = entered_at.saturating_sub(base);
+        let end = exited_at.saturating_sub(base);
+
+        // Include events buffered while paused
+        let mut events = self.engine.events_in(start, end).to_vec();
+        events.extend(
+            self.paused_events
+                .iter()
+                .filter(|event| event.timestamp >= start && event.timestamp <= end)
+                .cloned(),
+        );
+        events.sort_by_key(|event| event.timestamp);
+
+        RunSummary {
+            duration_secs,
+            ..analysis::run_summary(character, &events, start, end, window)
         }
+    }
 
+    /// Update chatlog tracking based on active gamelog paths
+    fn update_chatlog_tracking(&mut self, active_paths: &HashSet<PathBuf>, logs: &mut Vec<String>) {
         // Track new characters
         for gamelog_path in active_paths {
             if self.tracked_characters.contains_key(gamelog_path) {
                 continue;
             }
 
+            // Derive chatlog dir from the gamelog's own directory, since each
+            // configured gamelog dir may belong to a different EVE install
+            let Some(gamelog_dir) = gamelog_path.parent() else {
+                continue;
+            };
+            let chatlog_dir = discovery::derive_chatlog_dir(gamelog_dir);
+            if !chatlog_dir.exists() {
+                continue;
+            }
+
             // Extract character info from gamelog header
             if let Ok(Some(header)) =
                 discovery::extract_header(gamelog_path, discovery::LogType::Gamelog)
@@ -216,14 +654,19 @@ impl Coordinator {
         for path in to_remove {
             if let Some((_, char_id)) = self.tracked_characters.remove(&path) {
                 self.chatlog_watcher.stop_tracking(char_id);
+                self.open_runs.remove(&char_id);
             }
         }
     }
 
     pub fn replay_logs(&mut self) {
-        self.engine = EngineState::new();
+        self.engine = live_engine();
+        self.paused_events.clear();
+        self.paused_kills.clear();
+        self.last_sample = None;
         self.last_event_timestamp = None;
         self.last_event_wallclock = None;
+        self.last_seen.clear();
         self.watcher.rewind_all();
     }
 
@@ -233,6 +676,24 @@ impl Coordinator {
     }
 }
 
+/// The app's exit sequence. `stop_loop` hands the live loop a channel to signal
+/// once it has shut down, returning false if the loop is already gone; after
+/// that signal (or `timeout`) the settings are written.
+pub fn shutdown_and_save(
+    stop_loop: impl FnOnce(mpsc::Sender<()>) -> bool,
+    timeout: Duration,
+    settings: &Mutex<Settings>,
+    config: &ConfigManager,
+) -> io::Result<()> {
+    let (done_tx, done_rx) = mpsc::channel();
+    if stop_loop(done_tx) && done_rx.recv_timeout(timeout).is_err() {
+        println!("Background loop did not stop within {:?}", timeout);
+    }
+
+    let settings = settings.lock().unwrap().clone();
+    config.save(&settings)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -240,51 +701,410 @@ mod tests {
     use std::io::Write;
     use tempfile::tempdir;
 
+    const WINDOW: Duration = Duration::from_secs(5);
+
+    /// A coordinator watching `dir`, with one TestChar gamelog (header only) active
+    struct LiveFixture {
+        log_path: PathBuf,
+        file: File,
+        coord: Coordinator,
+        active_paths: HashSet<PathBuf>,
+    }
+
+    impl LiveFixture {
+        fn new(dir: &Path, file_name: &str) -> Self {
+            let log_path = dir.join(file_name);
+            let mut file = File::create(&log_path).unwrap();
+            writeln!(file, "  Listener: TestChar").unwrap();
+            writeln!(file, "  Session Started: 2025.01.01 12:00:00").unwrap();
+            Self {
+                coord: Coordinator::new(vec![dir.to_path_buf()]),
+                active_paths: [log_path.clone()].into_iter().collect(),
+                log_path,
+                file,
+            }
+        }
+
+        /// Append lines to the gamelog, visible to the next tick
+        fn write(&mut self, lines: &[&str]) {
+            for line in lines {
+                writeln!(self.file, "{}", line).unwrap();
+            }
+            self.file.sync_all().unwrap();
+        }
+
+        fn tick(&mut self) -> CoordinatorOutput {
+            self.coord.tick(&self.active_paths, WINDOW)
+        }
+    }
+
     #[test]
     fn test_coordinator_flow() {
         let dir = tempdir().unwrap();
-        let log_path = dir.path().join("20250101_120000_12345.txt");
+        let mut fx = LiveFixture::new(dir.path(), "20250101_120000_12345.txt");
+
+        // Tick 1: Start tracking
+        let output = fx.tick();
+        assert!(output.logs.iter().any(|m| m.contains("Started tracking")));
+        assert!(output.dps_sample.is_none()); // No events yet
+
+        // Tick 2: Read event
+        fx.write(&["[ 2025.01.01 12:01:00 ] (combat) 100 from TestChar to Enemy [ Gun ]"]);
+        let output = fx.tick();
+
+        let sample = output.dps_sample.unwrap();
+        assert!(sample.outgoing_dps > 0.0);
+
+        // Check character isolation
+        let char_dps = sample.outgoing_by_character.get("TestChar").unwrap();
+        assert!(*char_dps > 0.0);
+    }
+
+    #[test]
+    fn test_paused_ticks_freeze_sample_and_resume_catches_up() {
+        let dir = tempdir().unwrap();
+        let mut fx = LiveFixture::new(dir.path(), "20250101_120000_1.txt");
+        fx.tick();
 
-        let mut file = File::create(&log_path).unwrap();
+        fx.write(&["[ 2025.01.01 12:01:00 ] (combat) 100 from TestChar to Enemy [ Gun ]"]);
+        let before = fx.tick().dps_sample.unwrap();
+
+        fx.coord.set_paused(true);
+        fx.write(&[
+            "[ 2025.01.01 12:01:01 ] (combat) 900 from TestChar to Enemy [ Gun ]",
+            "[ 2025.01.01 12:01:01 ] (notify) Enemy has been destroyed.",
+        ]);
+
+        // Paused: the new event is read (and reported) but the sample doesn't move
+        let paused = fx.tick();
+        assert_eq!(paused.new_combat_events.len(), 1);
+        assert_eq!(fx.coord.engine.kill_count(), 0);
+        let frozen = paused.dps_sample.unwrap();
+        assert_eq!(frozen.time, before.time);
+        assert_eq!(frozen.outgoing_dps, before.outgoing_dps);
+
+        // Resume: buffered damage shows up
+        fx.coord.set_paused(false);
+        let resumed = fx.tick().dps_sample.unwrap();
+        assert!(resumed.outgoing_dps > before.outgoing_dps);
+        assert_eq!(resumed.outgoing_dps, 1000.0 / WINDOW.as_secs_f32());
+        assert_eq!(fx.coord.engine.kill_count(), 1);
+    }
+
+    #[test]
+    fn test_idle_timeout_freezes_sample_time() {
+        let dir = tempdir().unwrap();
+        let mut fx = LiveFixture::new(dir.path(), "20250101_120000_1.txt");
+        fx.coord.set_idle_timeout(Duration::from_millis(20));
+        fx.tick();
+
+        fx.write(&["[ 2025.01.01 12:01:00 ] (combat) 100 from TestChar to Enemy [ Gun ]"]);
+        fx.tick();
+
+        std::thread::sleep(Duration::from_millis(50));
+        let first = fx.tick().dps_sample.unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        let second = fx.tick().dps_sample.unwrap();
+        assert_eq!(first.time, second.time);
+        assert!(first.time <= Duration::from_secs(60) + Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_run_exit_emits_summary() {
+        let root = tempdir().unwrap();
+        let gamelog_dir = root.path().join("Gamelogs");
+        let chatlog_dir = root.path().join("Chatlogs");
+        std::fs::create_dir_all(&gamelog_dir).unwrap();
+        std::fs::create_dir_all(&chatlog_dir).unwrap();
+
+        let mut fx = LiveFixture::new(&gamelog_dir, "20250101_120000_12345.txt");
+        let mut chatlog =
+            File::create(chatlog_dir.join("Local_20250101_120000_12345.txt")).unwrap();
+        writeln!(chatlog, "  Channel Name:    Local").unwrap();
+        writeln!(chatlog, "  Listener:        TestChar").unwrap();
+        writeln!(chatlog, "  Session started: 2025.01.01 12:00:00").unwrap();
+        fx.tick();
+
+        // Enter the Abyss, fight, then leave
         writeln!(
-            file,
-            "------------------------------------------------------------"
+            chatlog,
+            "[ 2025.01.01 12:00:30 ] EVE System > Channel changed to Local : Unknown"
         )
         .unwrap();
-        writeln!(file, "  Gamelog").unwrap();
-        writeln!(file, "  Listener: TestChar").unwrap();
-        writeln!(file, "  Session Started: 2025.01.01 12:00:00").unwrap();
+        chatlog.sync_all().unwrap();
+        fx.write(&[
+            "[ 2025.01.01 12:01:00 ] (combat) 100 to Enemy1 - Gun - Hits",
+            "[ 2025.01.01 12:01:01 ] (combat) 300 to Enemy2 - Gun - Smashes",
+            "[ 2025.01.01 12:01:02 ] (combat) 40 from Rat - Hits",
+        ]);
+        let output = fx.tick();
+        assert_eq!(output.location_changes.len(), 1);
+        assert!(output.run_summaries.is_empty());
+
         writeln!(
-            file,
-            "------------------------------------------------------------"
+            chatlog,
+            "[ 2025.01.01 12:05:00 ] EVE System > Channel changed to Local : Jita"
         )
         .unwrap();
+        chatlog.sync_all().unwrap();
+        let output = fx.tick();
+
+        assert_eq!(output.run_summaries.len(), 1);
+        let summary = &output.run_summaries[0];
+        assert_eq!(summary.character, "TestChar");
+        assert_eq!(summary.duration_secs, 270);
+        assert_eq!(summary.total_outgoing, 400.0);
+        assert_eq!(summary.total_incoming, 40.0);
+        assert!(summary.peak_dps > 0.0);
+        let targets: Vec<&str> = summary
+            .top_targets
+            .iter()
+            .map(|t| t.target.as_str())
+            .collect();
+        assert_eq!(targets, vec!["Enemy2", "Enemy1"]);
+    }
 
-        let mut coord = Coordinator::new(dir.path().to_path_buf());
+    #[test]
+    fn test_missing_log_reports_error_and_recovers() {
+        let dir = tempdir().unwrap();
+        let log_path = dir.path().join("20250101_120000_1.txt");
+        let write_log = |event_line: &str| {
+            let mut file = File::create(&log_path).unwrap();
+            writeln!(file, "  Listener: TestChar").unwrap();
+            writeln!(file, "  Session Started: 2025.01.01 12:00:00").unwrap();
+            writeln!(file, "{}", event_line).unwrap();
+            file.sync_all().unwrap();
+        };
+        write_log("[ 2025.01.01 12:00:30 ] (combat) 100 to Starving Damavik - Gun - Hits");
+
+        let mut coord = Coordinator::new(vec![dir.path().to_path_buf()]);
         let mut active_paths = HashSet::new();
         active_paths.insert(log_path.clone());
+        let window = Duration::from_secs(5);
+        assert!(coord.tick(&active_paths, window).tracking_errors.is_empty());
 
-        // Tick 1: Start tracking
-        let output = coord.tick(&active_paths, Duration::from_secs(5));
-        assert!(output.logs.iter().any(|m| m.contains("Started tracking")));
-        assert!(output.dps_sample.is_none()); // No events yet
+        std::fs::remove_file(&log_path).unwrap();
+        let output = coord.tick(&active_paths, window);
+        assert_eq!(
+            output.tracking_errors,
+            vec![TrackingError::LogFileMissing(log_path.clone())]
+        );
+        // Still reported while it stays missing
+        let output = coord.tick(&active_paths, window);
+        assert_eq!(output.tracking_errors.len(), 1);
+
+        // File comes back (shorter than before): reopened and read from the start
+        write_log("[ 2025.01.01 12:01:00 ] (combat) 200 to Enemy - Gun - Hits");
+        let output = coord.tick(&active_paths, window);
+        assert!(output.tracking_errors.is_empty());
+        assert!(output.logs.iter().any(|m| m.contains("Reopened")));
+        assert_eq!(output.new_combat_events.len(), 1);
+        assert_eq!(output.new_combat_events[0].amount, 200.0);
+    }
+
+    #[test]
+    fn test_run_status_reports_open_run() {
+        let root = tempdir().unwrap();
+        let gamelog_dir = root.path().join("Gamelogs");
+        let chatlog_dir = root.path().join("Chatlogs");
+        std::fs::create_dir_all(&gamelog_dir).unwrap();
+        std::fs::create_dir_all(&chatlog_dir).unwrap();
+
+        let mut fx = LiveFixture::new(&gamelog_dir, "20250101_120000_777.txt");
+        let mut chatlog = File::create(chatlog_dir.join("Local_20250101_120000_777.txt")).unwrap();
+        writeln!(chatlog, "  Listener:        TestChar").unwrap();
+        writeln!(chatlog, "  Session started: 2025.01.01 12:00:00").unwrap();
+        fx.tick();
+
+        let status = fx.coord.run_status(&fx.log_path).unwrap();
+        assert!(!status.in_abyss);
+        assert_eq!(status.entered_at_secs, None);
 
-        // Write event
         writeln!(
-            file,
-            "[ 2025.01.01 12:01:00 ] (combat) 100 from TestChar to Enemy [ Gun ]"
+            chatlog,
+            "[ 2025.01.01 12:00:30 ] EVE System > Channel changed to Local : Unknown"
         )
         .unwrap();
-        file.sync_all().unwrap();
+        chatlog.sync_all().unwrap();
+        fx.tick();
 
-        // Tick 2: Read event
-        let output = coord.tick(&active_paths, Duration::from_secs(5));
+        let status = fx.coord.run_status(&fx.log_path).unwrap();
+        assert!(status.in_abyss);
+        assert_eq!(status.entered_at_secs, Some(1_735_732_830));
+        assert!(status.elapsed_secs > 0);
 
-        let sample = output.dps_sample.unwrap();
-        assert!(sample.outgoing_dps > 0.0);
+        assert!(fx
+            .coord
+            .run_status(&gamelog_dir.join("untracked.txt"))
+            .is_none());
+    }
 
-        // Check character isolation
-        let char_dps = sample.outgoing_by_character.get("TestChar").unwrap();
-        assert!(*char_dps > 0.0);
+    #[test]
+    fn test_fleet_status_lists_each_tracked_character() {
+        let root = tempdir().unwrap();
+        let gamelog_dir = root.path().join("Gamelogs");
+        let chatlog_dir = root.path().join("Chatlogs");
+        std::fs::create_dir_all(&gamelog_dir).unwrap();
+        std::fs::create_dir_all(&chatlog_dir).unwrap();
+
+        let mut active_paths = HashSet::new();
+        let mut gamelogs = Vec::new();
+        let mut chatlogs = Vec::new();
+        for (name, id) in [("Alpha", 111), ("Bravo", 222)] {
+            let log_path = gamelog_dir.join(format!("20250101_120000_{}.txt", id));
+            let mut gamelog = File::create(&log_path).unwrap();
+            writeln!(gamelog, "  Listener: {}", name).unwrap();
+            writeln!(gamelog, "  Session Started: 2025.01.01 12:00:00").unwrap();
+            let mut chatlog =
+                File::create(chatlog_dir.join(format!("Local_20250101_120000_{}.txt", id)))
+                    .unwrap();
+            writeln!(chatlog, "  Listener:        {}", name).unwrap();
+            writeln!(chatlog, "  Session started: 2025.01.01 12:00:00").unwrap();
+            active_paths.insert(log_path);
+            gamelogs.push(gamelog);
+            chatlogs.push(chatlog);
+        }
+
+        let mut coord = Coordinator::new(vec![gamelog_dir.clone()]);
+        let window = Duration::from_secs(5);
+        coord.tick(&active_paths, window);
+
+        // Alpha jumps into the Abyss and shoots; Bravo stays in Jita
+        writeln!(
+            chatlogs[0],
+            "[ 2025.01.01 12:00:30 ] EVE System > Channel changed to Local : Unknown"
+        )
+        .unwrap();
+        writeln!(
+            chatlogs[1],
+            "[ 2025.01.01 12:00:30 ] EVE System > Channel changed to Local : Jita"
+        )
+        .unwrap();
+        writeln!(
+            gamelogs[0],
+            "[ 2025.01.01 12:01:00 ] (combat) 500 to Enemy - Gun - Hits"
+        )
+        .unwrap();
+        coord.tick(&active_paths, window);
+
+        let roster = coord.fleet_status();
+        assert_eq!(roster.len(), 2);
+        assert_eq!(roster[0].character, "Alpha");
+        assert!(roster[0].in_abyss);
+        assert!(roster[0].outgoing_dps > 0.0);
+        assert!(roster[0].last_seen.is_some());
+        assert_eq!(roster[1].character, "Bravo");
+        assert!(!roster[1].in_abyss);
+        assert_eq!(roster[1].outgoing_dps, 0.0);
+        assert_eq!(roster[1].last_seen, None);
+    }
+
+    #[test]
+    fn test_jsonl_sink_writes_events_and_samples() {
+        let dir = tempdir().unwrap();
+        let mut fx = LiveFixture::new(dir.path(), "20250101_120000_1.txt");
+        let sink_path = dir.path().join("out.jsonl");
+        fx.coord.set_jsonl_sink(Some(&sink_path));
+        fx.tick();
+
+        fx.write(&[
+            "[ 2025.01.01 12:01:00 ] (combat) 100 from TestChar to Enemy [ Gun ]",
+            "[ 2025.01.01 12:01:01 ] (combat) 100 from TestChar to Enemy [ Gun ]",
+            "[ 2025.01.01 12:01:02 ] (combat) 100 from TestChar to Enemy [ Gun ]",
+        ]);
+        fx.tick();
+
+        let content = std::fs::read_to_string(&sink_path).unwrap();
+        let records: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let count = |kind: &str| records.iter().filter(|r| r["type"] == kind).count();
+        assert_eq!(count("event"), 3);
+        assert!(count("sample") >= 1);
+        assert_eq!(records[0]["amount"], 100.0);
+    }
+
+    #[test]
+    fn test_merged_timeline_interleaves_by_timestamp() {
+        let hit = |secs: u64| CombatEvent {
+            timestamp: Duration::from_secs(secs),
+            source: "Pilot".to_string(),
+            target: "Enemy".to_string(),
+            weapon: "Gun".to_string(),
+            amount: 100.0,
+            incoming: false,
+            character: "Pilot".to_string(),
+            event_type: crate::core::model::EventType::Damage,
+            hit_quality: None,
+            via_drone: false,
+            self_inflicted: false,
+            damage_layer: None,
+        };
+        let jump = LocationChange {
+            timestamp: Duration::from_secs(20),
+            location: "Unknown".to_string(),
+        };
+
+        let timeline = merged_timeline(vec![hit(10), hit(30)], vec![jump]);
+
+        let times: Vec<u64> = timeline.iter().map(|e| e.timestamp().as_secs()).collect();
+        assert_eq!(times, vec![10, 20, 30]);
+        assert!(matches!(timeline[0], TimelineEntry::Combat(_)));
+        assert!(matches!(&timeline[1], TimelineEntry::Location(c) if c.location == "Unknown"));
+        assert!(matches!(timeline[2], TimelineEntry::Combat(_)));
+    }
+
+    #[test]
+    fn test_shutdown_flushes_sink_bookmarks_and_config() {
+        use crate::core::inline_bookmarks;
+
+        let dir = tempdir().unwrap();
+        let mut fx = LiveFixture::new(dir.path(), "20250101_120000_1.txt");
+        let sink_path = dir.path().join("out.jsonl");
+        fx.coord.set_jsonl_sink(Some(&sink_path));
+        fx.tick();
+        fx.write(&["[ 2025.01.01 12:01:00 ] (combat) 100 from TestChar to Enemy [ Gun ]"]);
+        fx.tick();
+
+        // Queued long enough that only the shutdown flush writes it
+        let mut bookmarks = BookmarkWriter::new(Duration::from_secs(3600));
+        bookmarks.add_run_start(&fx.log_path);
+
+        // The live loop runs on its own thread, as in the app
+        let (loop_tx, loop_rx) = mpsc::channel::<mpsc::Sender<()>>();
+        let live = std::thread::spawn(move || {
+            let done = loop_rx.recv().unwrap();
+            fx.coord.shutdown(&mut bookmarks);
+            done.send(()).unwrap();
+            fx
+        });
+
+        let settings = Mutex::new(Settings {
+            dps_window_seconds: 17,
+            ..Settings::default()
+        });
+        let config = ConfigManager::new(dir.path().join("config"));
+        shutdown_and_save(
+            |done| loop_tx.send(done).is_ok(),
+            Duration::from_secs(2),
+            &settings,
+            &config,
+        )
+        .unwrap();
+        let mut fx = live.join().unwrap();
+
+        assert_eq!(config.load().dps_window_seconds, 17);
+        assert!(std::fs::read_to_string(&sink_path)
+            .unwrap()
+            .contains("\"amount\":100.0"));
+        let written = inline_bookmarks::read_bookmarks(&fx.log_path).unwrap();
+        assert_eq!(written.len(), 1);
+
+        // Further ticks write nothing once shut down
+        let len = std::fs::metadata(&sink_path).unwrap().len();
+        fx.write(&["[ 2025.01.01 12:01:01 ] (combat) 100 from TestChar to Enemy [ Gun ]"]);
+        fx.tick();
+        assert_eq!(std::fs::metadata(&sink_path).unwrap().len(), len);
     }
 }
@@ -42,12 +42,15 @@ pub struct LogHeader {
 /// Extract character ID from filename patterns like:
 /// - `20260103_221507_2114264203.txt` (Gamelog with ID)
 /// - `Local_20260103_174237_2112699440.txt` (Chatlog with ID)
+/// - `Fleet_20260103_174237_2112699440.txt` (any other channel)
 fn extract_character_id_from_filename(filename: &str) -> Option<u64> {
     let name = filename.strip_suffix(".txt").unwrap_or(filename);
     let parts: Vec<&str> = name.split('_').collect();
+    let is_date = |s: &str| s.len() == 8 && s.chars().all(|c| c.is_ascii_digit());
 
-    // Chatlog pattern: ["Local", "20260103", "174237", "2112699440"] - 4 parts
-    if parts.len() >= 4 && parts[0] == "Local" {
+    // Chatlog pattern: ["Local", "20260103", "174237", "2112699440"] - channel name,
+    // which may itself contain underscores, followed by date, time and ID
+    if parts.len() >= 4 && is_date(parts[parts.len() - 3]) {
         return parts.last().and_then(|s| s.parse().ok());
     }
 
@@ -96,7 +99,10 @@ pub fn extract_header(path: &Path, log_type: LogType) -> io::Result<Option<LogHe
 
         // Character name: "Listener: CharName"
         if let Some(rest) = trimmed.strip_prefix("Listener:") {
-            character = Some(rest.trim().to_string());
+            let name = rest.trim();
+            if !name.is_empty() {
+                character = Some(name.to_string());
+            }
         }
 
         // Session time - try both formats
@@ -235,7 +241,8 @@ pub fn scan_logs_dir(
             }
         }
 
-        if let Some(header) = extract_header(&path, log_type)? {
+        // Stray or half-written files without a usable header are skipped, not fatal
+        if let Ok(Some(header)) = extract_header(&path, log_type) {
             logs.push(header);
         }
     }
@@ -263,7 +270,16 @@ pub fn derive_chatlog_dir(gamelog_dir: &Path) -> PathBuf {
 
 /// Find the most recent Local chat log for a character.
 pub fn find_local_chatlog(chatlog_dir: &Path, character_id: u64) -> io::Result<Option<PathBuf>> {
-    let logs = scan_logs_dir(chatlog_dir, Some("Local"), LogType::Chatlog)?;
+    find_channel_chatlog(chatlog_dir, "Local", character_id)
+}
+
+/// Find the most recent chat log of any channel (e.g. "Fleet", "Corp") for a character.
+pub fn find_channel_chatlog(
+    chatlog_dir: &Path,
+    channel_prefix: &str,
+    character_id: u64,
+) -> io::Result<Option<PathBuf>> {
+    let logs = scan_logs_dir(chatlog_dir, Some(channel_prefix), LogType::Chatlog)?;
 
     // Find logs matching the character ID
     let matching: Vec<_> = logs
@@ -358,6 +374,12 @@ mod tests {
             extract_character_id_from_filename("Local_20260103_174237.txt"),
             None
         );
+
+        // Other channels
+        assert_eq!(
+            extract_character_id_from_filename("Fleet_20260103_174237_2112699440.txt"),
+            Some(2112699440)
+        );
     }
 
     #[test]
@@ -421,6 +443,30 @@ mod tests {
         assert_eq!(chatlog, PathBuf::from("/home/user/EVE/logs/Chatlogs"));
     }
 
+    #[test]
+    fn test_find_channel_chatlog() {
+        let dir = tempdir().unwrap();
+
+        create_chatlog(
+            &dir.path().join("Local_20260103_100000_111.txt"),
+            "CharA",
+            "2026.01.03 10:00:00",
+        );
+        create_chatlog(
+            &dir.path().join("Fleet_20260103_100500_111.txt"),
+            "CharA",
+            "2026.01.03 10:05:00",
+        );
+
+        let fleet = find_channel_chatlog(dir.path(), "Fleet", 111)
+            .unwrap()
+            .unwrap();
+        assert!(fleet.to_string_lossy().contains("Fleet_"));
+
+        let local = find_local_chatlog(dir.path(), 111).unwrap().unwrap();
+        assert!(local.to_string_lossy().contains("Local_"));
+    }
+
     #[test]
     fn test_find_local_chatlog_by_id() {
         let dir = tempdir().unwrap();
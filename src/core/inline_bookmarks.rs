@@ -5,11 +5,15 @@
 //!
 //! This allows bookmarks to travel with the log file and be parsed during replay.
 
-use chrono::{DateTime, Utc};
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
-use std::fs::OpenOptions;
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use super::model::{format_log_timestamp, CombatEvent, EventType, TzMode};
 
 /// Types of bookmarks that can be placed in a gamelog
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -62,29 +66,453 @@ pub struct InlineBookmark {
     pub label: Option<String>,
 }
 
-/// Append a bookmark line to a gamelog file.
+/// Parse a bookmark line like: `[ 2026.01.04 03:56:49 ] (bookmark) TYPE: label`
+pub fn parse_bookmark_line(line: &str) -> Option<InlineBookmark> {
+    if !line.contains("(bookmark)") {
+        return None;
+    }
+
+    let timestamp_secs = line_timestamp_secs(line)?;
+
+    // Extract type and optional label after (bookmark)
+    let after_bookmark = line.split("(bookmark)").nth(1)?.trim();
+    let (type_str, label) = match after_bookmark.split_once(':') {
+        Some((btype, lbl)) => (btype, Some(lbl.trim().to_string())),
+        None => (after_bookmark, None),
+    };
+
+    Some(InlineBookmark {
+        timestamp_secs,
+        bookmark_type: BookmarkType::from_str(type_str)?,
+        label,
+    })
+}
+
+/// Epoch seconds of a log line's `[ YYYY.MM.DD HH:MM:SS ]` prefix
+fn line_timestamp_secs(line: &str) -> Option<u64> {
+    let timestamp_start = line.find('[')? + 1;
+    let timestamp_end = line.find(']')?;
+    let timestamp_str = line.get(timestamp_start..timestamp_end)?.trim();
+    let naive = NaiveDateTime::parse_from_str(timestamp_str, "%Y.%m.%d %H:%M:%S").ok()?;
+    Some(naive.and_utc().timestamp() as u64)
+}
+
+/// Format a bookmark line: `[ TIMESTAMP ] (bookmark) TYPE: label`
+pub fn format_bookmark_line(timestamp: &str, bookmark_type: &str, label: Option<&str>) -> String {
+    if let Some(lbl) = label {
+        format!("[ {} ] (bookmark) {}: {}", timestamp, bookmark_type, lbl)
+    } else {
+        format!("[ {} ] (bookmark) {}", timestamp, bookmark_type)
+    }
+}
+
+fn write_line(gamelog_path: &Path, line: &str) -> io::Result<()> {
+    write_lines(gamelog_path, &[line.to_string()])
+}
+
+fn write_lines(gamelog_path: &Path, lines: &[String]) -> io::Result<()> {
+    let mut file = OpenOptions::new().append(true).open(gamelog_path)?;
+    for line in lines {
+        file.write_all(format!("{}\n", line).as_bytes())?;
+    }
+    file.sync_all()?;
+    Ok(())
+}
+
+/// How long queued bookmarks may wait before `BookmarkWriter::flush_due` writes
+/// them. Short enough that trackers and replay pick them up within a second.
+pub const DEFAULT_FLUSH_DELAY: Duration = Duration::from_millis(500);
+
+/// Batches automatic bookmark appends, so a burst of location changes opens and
+/// syncs each gamelog once instead of once per marker.
+pub struct BookmarkWriter {
+    delay: Duration,
+    /// Lines per gamelog, in queue order
+    pending: HashMap<PathBuf, Vec<String>>,
+    /// When the oldest pending line was queued
+    oldest: Option<Instant>,
+}
+
+impl BookmarkWriter {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            pending: HashMap::new(),
+            oldest: None,
+        }
+    }
+
+    /// Queue a bookmark line stamped `when` (time since the Unix epoch).
+    pub fn queue_at(
+        &mut self,
+        gamelog_path: &Path,
+        bookmark_type: &str,
+        label: Option<&str>,
+        when: Duration,
+    ) {
+        self.pending
+            .entry(gamelog_path.to_path_buf())
+            .or_default()
+            .push(format_bookmark_line(
+                &format_timestamp(when),
+                bookmark_type,
+                label,
+            ));
+        self.oldest.get_or_insert_with(Instant::now);
+    }
+
+    /// Queue a run start marker stamped now
+    pub fn add_run_start(&mut self, gamelog_path: &Path) {
+        self.queue_at(gamelog_path, "RUN_START", None, now());
+    }
+
+    /// Queue a run end marker stamped now
+    pub fn add_run_end(&mut self, gamelog_path: &Path) {
+        self.queue_at(gamelog_path, "RUN_END", None, now());
+    }
+
+    /// Flush if the oldest queued line has waited out the delay.
+    pub fn flush_due(&mut self) -> io::Result<()> {
+        match self.oldest {
+            Some(oldest) if oldest.elapsed() >= self.delay => self.flush(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Write every queued line now. Lines for a gamelog that fails to open are
+    /// dropped with the error, so one missing file can't block the others.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.oldest = None;
+        let mut result = Ok(());
+        for (path, lines) in self.pending.drain() {
+            if let Err(e) = write_lines(&path, &lines) {
+                result = Err(io::Error::new(
+                    e.kind(),
+                    format!("{}: {}", path.display(), e),
+                ));
+            }
+        }
+        result
+    }
+}
+
+/// Format an epoch-relative instant like EVE logs: "2026.01.04 03:56:49" (UTC,
+/// as bookmark lines must match the log's own timestamps)
+pub fn format_timestamp(when: Duration) -> String {
+    format_log_timestamp(when, TzMode::Utc)
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Append a bookmark line to a gamelog file, stamped with the current time.
 pub fn append_bookmark(
     gamelog_path: &Path,
     bookmark_type: &str,
     label: Option<&str>,
 ) -> io::Result<()> {
-    let mut file = OpenOptions::new().append(true).open(gamelog_path)?;
+    append_bookmark_at(gamelog_path, bookmark_type, label, now())
+}
 
-    // Format timestamp like EVE logs: "2026.01.04 03:56:49"
-    let now: DateTime<Utc> = Utc::now();
-    let timestamp = now.format("%Y.%m.%d %H:%M:%S");
+/// Append a bookmark line stamped with an explicit instant (time since the Unix epoch).
+/// Used when back-filling bookmarks for events that happened in the past.
+pub fn append_bookmark_at(
+    gamelog_path: &Path,
+    bookmark_type: &str,
+    label: Option<&str>,
+    when: Duration,
+) -> io::Result<()> {
+    write_line(
+        gamelog_path,
+        &format_bookmark_line(&format_timestamp(when), bookmark_type, label),
+    )
+}
 
-    // Format: [ TIMESTAMP ] (bookmark) TYPE: label
-    let line = if let Some(lbl) = label {
-        format!("[ {} ] (bookmark) {}: {}\n", timestamp, bookmark_type, lbl)
-    } else {
-        format!("[ {} ] (bookmark) {}\n", timestamp, bookmark_type)
-    };
+/// Existing bookmarks this close to a new one of the same type count as the same
+/// bookmark. Live run markers are stamped when the app reads the jump, a few
+/// seconds after the chatlog's own timestamp.
+pub const DUPLICATE_TOLERANCE_SECS: u64 = 10;
 
-    file.write_all(line.as_bytes())?;
-    file.sync_all()?;
+/// Insert bookmarks at their chronological positions in a gamelog, skipping any
+/// that already exist (same type within `DUPLICATE_TOLERANCE_SECS`).
+/// Returns how many lines were written.
+pub fn insert_bookmarks_if_absent(
+    gamelog_path: &Path,
+    bookmarks: &[(Duration, BookmarkType, Option<&str>)],
+) -> io::Result<usize> {
+    let content = fs::read_to_string(gamelog_path)?;
+    let existing: Vec<InlineBookmark> = content.lines().filter_map(parse_bookmark_line).collect();
 
-    Ok(())
+    let mut to_insert: Vec<(u64, String)> = bookmarks
+        .iter()
+        .filter(|(when, kind, _)| {
+            !existing.iter().any(|bm| {
+                bm.bookmark_type == *kind
+                    && bm.timestamp_secs.abs_diff(when.as_secs()) <= DUPLICATE_TOLERANCE_SECS
+            })
+        })
+        .map(|(when, kind, label)| {
+            (
+                when.as_secs(),
+                format_bookmark_line(&format_timestamp(*when), kind.as_str(), *label),
+            )
+        })
+        .collect();
+    if to_insert.is_empty() {
+        return Ok(0);
+    }
+    to_insert.sort_by_key(|(secs, _)| *secs);
+    let added = to_insert.len();
+
+    let mut output = String::with_capacity(content.len() + added * 48);
+    let mut pending = to_insert.into_iter().peekable();
+    for chunk in content.split_inclusive('\n') {
+        // Bookmarks go before the first line stamped at or after them
+        if let Some(line_secs) = line_timestamp_secs(chunk) {
+            while let Some((_, line)) = pending.next_if(|(secs, _)| *secs <= line_secs) {
+                output.push_str(&line);
+                output.push('\n');
+            }
+        }
+        output.push_str(chunk);
+    }
+    for (_, line) in pending {
+        if !output.is_empty() && !output.ends_with('\n') {
+            output.push('\n');
+        }
+        output.push_str(&line);
+        output.push('\n');
+    }
+
+    fs::write(gamelog_path, output)?;
+    Ok(added)
+}
+
+/// Rewrite the first bookmark line matching `timestamp_secs` and `bookmark_type`.
+/// `edit` returns the replacement line (without terminator), or `None` to drop it.
+/// Returns true if a matching bookmark was found.
+fn edit_bookmark<F>(
+    gamelog_path: &Path,
+    timestamp_secs: u64,
+    bookmark_type: BookmarkType,
+    edit: F,
+) -> io::Result<bool>
+where
+    F: FnOnce(&InlineBookmark) -> Option<String>,
+{
+    let content = fs::read_to_string(gamelog_path)?;
+    let mut output = String::with_capacity(content.len());
+    let mut edit = Some(edit);
+
+    for chunk in content.split_inclusive('\n') {
+        let line = chunk.trim_end_matches(['\r', '\n']);
+        let matched = parse_bookmark_line(line)
+            .filter(|bm| bm.timestamp_secs == timestamp_secs && bm.bookmark_type == bookmark_type);
+
+        match (matched, edit.take()) {
+            (Some(bm), Some(f)) => {
+                if let Some(replacement) = f(&bm) {
+                    // Keep the original line terminator
+                    output.push_str(&replacement);
+                    output.push_str(&chunk[line.len()..]);
+                }
+            }
+            (_, pending) => {
+                edit = pending;
+                output.push_str(chunk);
+            }
+        }
+    }
+
+    if edit.is_some() {
+        return Ok(false);
+    }
+    fs::write(gamelog_path, output)?;
+    Ok(true)
+}
+
+/// Remove a bookmark identified by its timestamp and type.
+/// Returns false if no such bookmark exists.
+pub fn remove_bookmark(
+    gamelog_path: &Path,
+    timestamp_secs: u64,
+    bookmark_type: BookmarkType,
+) -> io::Result<bool> {
+    edit_bookmark(gamelog_path, timestamp_secs, bookmark_type, |_| None)
+}
+
+/// Replace (or clear) the label of a bookmark identified by its timestamp and type.
+/// Returns false if no such bookmark exists.
+pub fn set_bookmark_label(
+    gamelog_path: &Path,
+    timestamp_secs: u64,
+    bookmark_type: BookmarkType,
+    label: Option<&str>,
+) -> io::Result<bool> {
+    edit_bookmark(gamelog_path, timestamp_secs, bookmark_type, |bm| {
+        Some(format_bookmark_line(
+            &format_timestamp(Duration::from_secs(bm.timestamp_secs)),
+            bm.bookmark_type.as_str(),
+            label,
+        ))
+    })
+}
+
+/// An Abyss run recorded by RUN_START/RUN_END bookmarks in a gamelog
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StoredRun {
+    /// Position of the run within its gamelog, from 0
+    pub index: usize,
+    pub start_secs: u64,
+    /// None when the run has no RUN_END (logged out inside, or still running)
+    pub end_secs: Option<u64>,
+    /// Where the run started from (the RUN_START label, if any)
+    pub origin: Option<String>,
+    /// Other bookmarks (rooms, highlights) placed during the run
+    pub bookmark_count: usize,
+}
+
+impl StoredRun {
+    pub fn duration_secs(&self) -> Option<u64> {
+        self.end_secs.map(|end| end.saturating_sub(self.start_secs))
+    }
+}
+
+/// Group bookmarks (in file order) into runs. A RUN_START while a run is open
+/// leaves the previous one without an end.
+pub fn runs_from_bookmarks(bookmarks: &[InlineBookmark]) -> Vec<StoredRun> {
+    let mut runs: Vec<StoredRun> = Vec::new();
+    let mut open = false;
+
+    for bookmark in bookmarks {
+        match bookmark.bookmark_type {
+            BookmarkType::RunStart => {
+                runs.push(StoredRun {
+                    index: runs.len(),
+                    start_secs: bookmark.timestamp_secs,
+                    end_secs: None,
+                    origin: bookmark.label.clone(),
+                    bookmark_count: 0,
+                });
+                open = true;
+            }
+            BookmarkType::RunEnd => {
+                if let (true, Some(run)) = (open, runs.last_mut()) {
+                    run.end_secs = Some(bookmark.timestamp_secs);
+                }
+                open = false;
+            }
+            _ => {
+                if let (true, Some(run)) = (open, runs.last_mut()) {
+                    run.bookmark_count += 1;
+                }
+            }
+        }
+    }
+    runs
+}
+
+/// Events of a gamelog that fall within a run. `base_time` is the UTC time the
+/// events' timestamps are relative to (the log parser's base time).
+pub fn slice_run_events(
+    events: &[CombatEvent],
+    base_time: NaiveDateTime,
+    run: &StoredRun,
+) -> Vec<CombatEvent> {
+    let base_secs = base_time.and_utc().timestamp().max(0) as u64;
+    events
+        .iter()
+        .filter(|event| {
+            let secs = base_secs + event.timestamp.as_secs();
+            secs >= run.start_secs && run.end_secs.is_none_or(|end| secs <= end)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Outgoing damage of one weapon across several runs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WeaponTotal {
+    pub weapon: String,
+    pub damage: f32,
+}
+
+/// Combined stats over a selection of runs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MultiRunStats {
+    pub run_count: usize,
+    /// Summed run durations, in seconds
+    pub combat_secs: u64,
+    /// Mean of the runs' outgoing DPS
+    pub average_dps: f32,
+    pub best_dps: f32,
+    /// Highest damage first
+    pub weapon_totals: Vec<WeaponTotal>,
+}
+
+/// Combine runs with their events (`events_by_run[i]` belongs to `runs[i]`).
+/// A run without a RUN_END lasts from its start to its last event.
+pub fn aggregate_runs(runs: &[&StoredRun], events_by_run: &[Vec<CombatEvent>]) -> MultiRunStats {
+    let mut combat_secs = 0;
+    let mut dps_sum = 0.0_f32;
+    let mut best_dps = 0.0_f32;
+    let mut by_weapon: HashMap<&str, f32> = HashMap::new();
+
+    for (run, events) in runs.iter().zip(events_by_run) {
+        let mut damage = 0.0;
+        for event in events
+            .iter()
+            .filter(|e| e.event_type == EventType::Damage && !e.incoming)
+        {
+            damage += event.amount;
+            *by_weapon.entry(&event.weapon).or_insert(0.0) += event.amount;
+        }
+
+        let duration = run.duration_secs().unwrap_or_else(|| {
+            let first = events.iter().map(|e| e.timestamp).min().unwrap_or_default();
+            let last = events.iter().map(|e| e.timestamp).max().unwrap_or_default();
+            (last - first).as_secs()
+        });
+        combat_secs += duration;
+        let dps = damage / duration.max(1) as f32;
+        dps_sum += dps;
+        best_dps = best_dps.max(dps);
+    }
+
+    let mut weapon_totals: Vec<WeaponTotal> = by_weapon
+        .into_iter()
+        .map(|(weapon, damage)| WeaponTotal {
+            weapon: weapon.to_string(),
+            damage,
+        })
+        .collect();
+    weapon_totals.sort_by(|a, b| {
+        b.damage
+            .partial_cmp(&a.damage)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.weapon.cmp(&b.weapon))
+    });
+
+    MultiRunStats {
+        run_count: runs.len(),
+        combat_secs,
+        average_dps: if runs.is_empty() {
+            0.0
+        } else {
+            dps_sum / runs.len() as f32
+        },
+        best_dps,
+        weapon_totals,
+    }
+}
+
+/// All bookmarks in a gamelog, in file order
+pub fn read_bookmarks(gamelog_path: &Path) -> io::Result<Vec<InlineBookmark>> {
+    let content = fs::read_to_string(gamelog_path)?;
+    Ok(content.lines().filter_map(parse_bookmark_line).collect())
 }
 
 /// Add a highlight bookmark
@@ -104,17 +532,18 @@ pub fn add_room_end(gamelog_path: &Path) -> io::Result<()> {
 
 /// Add a run start marker
 pub fn add_run_start(gamelog_path: &Path) -> io::Result<()> {
-    append_bookmark(gamelog_path, "RUN_START", None)
+    append_bookmark_at(gamelog_path, "RUN_START", None, now())
 }
 
 /// Add a run end marker
 pub fn add_run_end(gamelog_path: &Path) -> io::Result<()> {
-    append_bookmark(gamelog_path, "RUN_END", None)
+    append_bookmark_at(gamelog_path, "RUN_END", None, now())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::analysis::tests::make_event;
     use std::fs;
     use tempfile::tempdir;
 
@@ -141,4 +570,225 @@ mod tests {
         assert!(content.contains("(bookmark) ROOM_START"));
         assert!(content.contains("(bookmark) ROOM_END"));
     }
+
+    #[test]
+    fn test_insert_bookmarks_skips_live_markers() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("test.txt");
+        // Live markers trail the chatlog jump times (03:00:00, 03:10:00) by a few seconds
+        fs::write(
+            &log,
+            "[ 2026.01.04 02:59:00 ] (combat) 100 from Me to Target\n\
+             [ 2026.01.04 03:00:03 ] (bookmark) RUN_START\n\
+             [ 2026.01.04 03:05:00 ] (combat) 200 from Me to Target\n\
+             [ 2026.01.04 03:10:04 ] (bookmark) RUN_END\n\
+             [ 2026.01.04 03:25:00 ] (combat) 300 from Me to Target\n",
+        )
+        .unwrap();
+
+        // 2026.01.04 03:00:00 UTC
+        let base = 1_767_495_600;
+        let at = |offset: u64| Duration::from_secs(base + offset);
+        let detected = [
+            (at(0), BookmarkType::RunStart, Some("Jita")),
+            (at(600), BookmarkType::RunEnd, None),
+            (at(1200), BookmarkType::RunStart, None),
+            (at(1800), BookmarkType::RunEnd, None),
+        ];
+
+        // Detecting twice only adds the run the live tracker missed
+        assert_eq!(insert_bookmarks_if_absent(&log, &detected).unwrap(), 2);
+        assert_eq!(insert_bookmarks_if_absent(&log, &detected).unwrap(), 0);
+
+        let runs = runs_from_bookmarks(&read_bookmarks(&log).unwrap());
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].duration_secs(), Some(601));
+        assert_eq!(runs[1].start_secs, base + 1200);
+        assert_eq!(runs[1].end_secs, Some(base + 1800));
+
+        // The second run's start lands in chronological order
+        let content = fs::read_to_string(&log).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines[4], "[ 2026.01.04 03:20:00 ] (bookmark) RUN_START");
+        assert_eq!(lines[6], "[ 2026.01.04 03:30:00 ] (bookmark) RUN_END");
+    }
+
+    #[test]
+    fn test_append_bookmark_at_uses_supplied_time() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("test.txt");
+        fs::write(&log, "").unwrap();
+
+        // 2026.01.04 03:56:49 UTC
+        let when = Duration::from_secs(1_767_499_009);
+        append_bookmark_at(&log, "RUN_START", None, when).unwrap();
+
+        let content = fs::read_to_string(&log).unwrap();
+        assert_eq!(content, "[ 2026.01.04 03:56:49 ] (bookmark) RUN_START\n");
+
+        let bm = parse_bookmark_line(content.trim_end()).unwrap();
+        assert_eq!(bm.timestamp_secs, when.as_secs());
+    }
+
+    #[test]
+    fn test_remove_and_relabel_bookmarks() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("test.txt");
+        fs::write(
+            &log,
+            "[ 2026.01.04 03:00:00 ] (combat) 100 from Me to Target\r\n",
+        )
+        .unwrap();
+
+        let t1 = Duration::from_secs(1_767_499_000);
+        let t2 = Duration::from_secs(1_767_499_010);
+        let t3 = Duration::from_secs(1_767_499_020);
+        append_bookmark_at(&log, "HIGHLIGHT", Some("first"), t1).unwrap();
+        append_bookmark_at(&log, "HIGHLIGHT", Some("second"), t2).unwrap();
+        append_bookmark_at(&log, "HIGHLIGHT", Some("third"), t3).unwrap();
+
+        // Delete the middle one
+        assert!(remove_bookmark(&log, t2.as_secs(), BookmarkType::Highlight).unwrap());
+        assert!(!remove_bookmark(&log, t2.as_secs(), BookmarkType::Highlight).unwrap());
+        // Type must match too
+        assert!(!remove_bookmark(&log, t1.as_secs(), BookmarkType::RunStart).unwrap());
+
+        // Relabel the last one
+        assert!(
+            set_bookmark_label(&log, t3.as_secs(), BookmarkType::Highlight, Some("boss")).unwrap()
+        );
+
+        let content = fs::read_to_string(&log).unwrap();
+        // Non-bookmark lines keep their original terminator
+        assert!(content.starts_with("[ 2026.01.04 03:00:00 ] (combat) 100 from Me to Target\r\n"));
+
+        let remaining: Vec<_> = content.lines().filter_map(parse_bookmark_line).collect();
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(remaining[0].timestamp_secs, t1.as_secs());
+        assert_eq!(remaining[0].label.as_deref(), Some("first"));
+        assert_eq!(remaining[1].timestamp_secs, t3.as_secs());
+        assert_eq!(remaining[1].label.as_deref(), Some("boss"));
+    }
+
+    #[test]
+    fn test_parse_bookmark_line() {
+        let bm = parse_bookmark_line("[ 2026.01.04 03:56:49 ] (bookmark) HIGHLIGHT: Big hit")
+            .expect("should parse highlight");
+        assert_eq!(bm.bookmark_type, BookmarkType::Highlight);
+        assert_eq!(bm.label.as_deref(), Some("Big hit"));
+        assert_eq!(bm.timestamp_secs, 1_767_499_009);
+
+        let bm = parse_bookmark_line("[ 2026.01.04 03:56:49 ] (bookmark) RUN_START")
+            .expect("should parse run start");
+        assert_eq!(bm.bookmark_type, BookmarkType::RunStart);
+        assert!(bm.label.is_none());
+
+        assert!(parse_bookmark_line("[ 2026.01.04 03:56:49 ] (combat) 100 to X").is_none());
+    }
+
+    #[test]
+    fn test_runs_from_bookmarks_lists_each_run() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("test.txt");
+        let lines = [
+            format_bookmark_line("2026.01.04 03:00:00", "RUN_START", Some("Torrinos")),
+            format_bookmark_line("2026.01.04 03:01:00", "ROOM_START", None),
+            format_bookmark_line("2026.01.04 03:05:00", "HIGHLIGHT", Some("boss")),
+            format_bookmark_line("2026.01.04 03:15:30", "RUN_END", None),
+            format_bookmark_line("2026.01.04 04:00:00", "RUN_START", None),
+            format_bookmark_line("2026.01.04 04:12:00", "RUN_END", None),
+        ];
+        fs::write(&log, lines.join("\n")).unwrap();
+
+        let runs = runs_from_bookmarks(&read_bookmarks(&log).unwrap());
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].duration_secs(), Some(15 * 60 + 30));
+        assert_eq!(runs[0].origin.as_deref(), Some("Torrinos"));
+        assert_eq!(runs[0].bookmark_count, 2);
+        assert_eq!(runs[1].index, 1);
+        assert_eq!(runs[1].duration_secs(), Some(12 * 60));
+        assert_eq!(runs[1].bookmark_count, 0);
+    }
+
+    #[test]
+    fn test_aggregate_runs_combines_dps_and_weapons() {
+        let hit = |secs: u64, amount: f32, weapon: &str| CombatEvent {
+            weapon: weapon.to_string(),
+            ..make_event(secs, amount, false, "Pilot", "Rat")
+        };
+        let base =
+            NaiveDateTime::parse_from_str("2026.01.04 03:00:00", "%Y.%m.%d %H:%M:%S").unwrap();
+        let base_secs = base.and_utc().timestamp() as u64;
+        let bookmark = |offset: u64, bookmark_type| InlineBookmark {
+            timestamp_secs: base_secs + offset,
+            bookmark_type,
+            label: None,
+        };
+        let runs = runs_from_bookmarks(&[
+            bookmark(0, BookmarkType::RunStart),
+            bookmark(100, BookmarkType::RunEnd),
+            bookmark(200, BookmarkType::RunStart),
+            bookmark(250, BookmarkType::RunEnd),
+        ]);
+        let events = vec![
+            hit(10, 4000.0, "Gun"),
+            hit(20, 1000.0, "Drone"),
+            // Between runs
+            hit(150, 9999.0, "Gun"),
+            hit(210, 5000.0, "Gun"),
+        ];
+        let events_by_run: Vec<Vec<CombatEvent>> = runs
+            .iter()
+            .map(|run| slice_run_events(&events, base, run))
+            .collect();
+        assert_eq!(events_by_run[0].len(), 2);
+        assert_eq!(events_by_run[1].len(), 1);
+
+        let selected: Vec<&StoredRun> = runs.iter().collect();
+        let stats = aggregate_runs(&selected, &events_by_run);
+
+        assert_eq!(stats.run_count, 2);
+        assert_eq!(stats.combat_secs, 150);
+        // 5000 / 100s and 5000 / 50s
+        assert_eq!(stats.average_dps, 75.0);
+        assert_eq!(stats.best_dps, 100.0);
+        assert_eq!(stats.weapon_totals[0].weapon, "Gun");
+        assert_eq!(stats.weapon_totals[0].damage, 9000.0);
+        assert_eq!(stats.weapon_totals[1].damage, 1000.0);
+    }
+
+    #[test]
+    fn test_bookmark_writer_batches_until_flush() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("test.txt");
+        fs::write(&log, "").unwrap();
+
+        let mut writer = BookmarkWriter::new(Duration::from_secs(60));
+        let start = Duration::from_secs(1_767_499_009);
+        for i in 0..3 {
+            writer.queue_at(&log, "RUN_START", None, start + Duration::from_secs(i * 10));
+            writer.queue_at(
+                &log,
+                "RUN_END",
+                None,
+                start + Duration::from_secs(i * 10 + 5),
+            );
+        }
+
+        // Nothing is written before the delay
+        writer.flush_due().unwrap();
+        assert_eq!(fs::read_to_string(&log).unwrap(), "");
+
+        writer.flush().unwrap();
+        let bookmarks = read_bookmarks(&log).unwrap();
+        assert_eq!(bookmarks.len(), 6);
+        assert_eq!(bookmarks[0].bookmark_type, BookmarkType::RunStart);
+        assert_eq!(bookmarks[5].bookmark_type, BookmarkType::RunEnd);
+
+        // A zero delay writes on the next check
+        let mut writer = BookmarkWriter::new(Duration::ZERO);
+        writer.add_run_start(&log);
+        writer.flush_due().unwrap();
+        assert_eq!(read_bookmarks(&log).unwrap().len(), 7);
+    }
 }
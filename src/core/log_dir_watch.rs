@@ -0,0 +1,188 @@
+// Notices gamelogs being created or removed in the log directories, so the
+// character list can refresh without a manual rescan.
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How often the directories are listed when filesystem notifications are unavailable
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+enum Mode {
+    Notify {
+        // Kept alive for as long as the watch runs
+        _watcher: RecommendedWatcher,
+        rx: mpsc::Receiver<()>,
+    },
+    Poll {
+        interval: Duration,
+        last_poll: Instant,
+        known: HashSet<PathBuf>,
+    },
+}
+
+pub struct LogDirWatch {
+    dirs: Vec<PathBuf>,
+    mode: Mode,
+}
+
+impl LogDirWatch {
+    /// Watch `dirs` with filesystem notifications, falling back to polling
+    /// when the platform or the directories don't support them.
+    pub fn new(dirs: &[PathBuf]) -> Self {
+        // A directory that doesn't exist yet can't be watched; listing picks it up once created
+        if let Some(missing) = dirs.iter().find(|dir| !dir.is_dir()) {
+            println!("Log directory {} not found; polling", missing.display());
+            return Self::polling(dirs, DEFAULT_POLL_INTERVAL);
+        }
+        match Self::notify_mode(dirs) {
+            Ok(mode) => Self {
+                dirs: dirs.to_vec(),
+                mode,
+            },
+            Err(e) => {
+                println!("Log directory notifications unavailable ({}); polling", e);
+                Self::polling(dirs, DEFAULT_POLL_INTERVAL)
+            }
+        }
+    }
+
+    /// Watch `dirs` by listing them every `interval`.
+    pub fn polling(dirs: &[PathBuf], interval: Duration) -> Self {
+        Self {
+            dirs: dirs.to_vec(),
+            mode: Mode::Poll {
+                interval,
+                last_poll: Instant::now(),
+                known: list_logs(dirs),
+            },
+        }
+    }
+
+    fn notify_mode(dirs: &[PathBuf]) -> notify::Result<Mode> {
+        let (tx, rx) = mpsc::channel();
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                let Ok(event) = res else {
+                    return;
+                };
+                // Existing logs are written constantly; only files coming and going matter
+                let created_or_removed =
+                    matches!(event.kind, EventKind::Create(_) | EventKind::Remove(_));
+                if created_or_removed && event.paths.iter().any(|path| is_log_file(path)) {
+                    let _ = tx.send(());
+                }
+            })?;
+        for dir in dirs {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+        Ok(Mode::Notify {
+            _watcher: watcher,
+            rx,
+        })
+    }
+
+    /// Whether a log file appeared or disappeared since the last call.
+    pub fn changed(&mut self) -> bool {
+        match &mut self.mode {
+            Mode::Notify { rx, .. } => {
+                let mut changed = false;
+                while rx.try_recv().is_ok() {
+                    changed = true;
+                }
+                changed
+            }
+            Mode::Poll {
+                interval,
+                last_poll,
+                known,
+            } => {
+                if last_poll.elapsed() < *interval {
+                    return false;
+                }
+                *last_poll = Instant::now();
+                let current = list_logs(&self.dirs);
+                if current == *known {
+                    return false;
+                }
+                *known = current;
+                true
+            }
+        }
+    }
+}
+
+fn is_log_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("txt"))
+}
+
+fn list_logs(dirs: &[PathBuf]) -> HashSet<PathBuf> {
+    dirs.iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| is_log_file(path))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn is_polling(watch: &LogDirWatch) -> bool {
+        matches!(watch.mode, Mode::Poll { .. })
+    }
+
+    fn wait_for_change(watch: &mut LogDirWatch) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(3);
+        while Instant::now() < deadline {
+            if watch.changed() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        false
+    }
+
+    #[test]
+    fn new_log_file_is_noticed() {
+        let dir = tempdir().unwrap();
+        let dirs = vec![dir.path().to_path_buf()];
+
+        for (polling, name) in [(false, "a.txt"), (true, "b.txt")] {
+            let mut watch = if polling {
+                LogDirWatch::polling(&dirs, Duration::ZERO)
+            } else {
+                LogDirWatch::new(&dirs)
+            };
+            assert!(!watch.changed());
+            fs::write(dir.path().join(name), "  Listener: Pilot\n").unwrap();
+            assert!(wait_for_change(&mut watch));
+
+            // Other files don't count
+            fs::write(dir.path().join(format!("{}.json", name)), "{}").unwrap();
+            std::thread::sleep(Duration::from_millis(100));
+            assert!(!watch.changed());
+        }
+    }
+
+    #[test]
+    fn missing_dir_is_polled_until_created() {
+        let root = tempdir().unwrap();
+        let later = root.path().join("later");
+        let mut watch = LogDirWatch::new(&[later.clone()]);
+        assert!(is_polling(&watch));
+
+        if let Mode::Poll { interval, .. } = &mut watch.mode {
+            *interval = Duration::ZERO;
+        }
+        fs::create_dir(&later).unwrap();
+        fs::write(later.join("a.txt"), "  Listener: Pilot\n").unwrap();
+        assert!(wait_for_change(&mut watch));
+    }
+}
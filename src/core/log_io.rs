@@ -1,16 +1,17 @@
-use chrono::{NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
-use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
-use super::model::CombatEvent;
+use super::discovery::{self, LogType};
+use super::model::{CombatEvent, DpsSample};
 use super::parser;
 
 /// Detected encoding of a log file
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum LogEncoding {
     Utf8,
     Utf16Le,
@@ -58,6 +59,8 @@ impl LogTailer {
         Ok(LogEncoding::Utf8)
     }
 
+    /// Complete lines appended since the last read. A trailing line without its
+    /// newline yet (the client is mid-write) is left for the next read.
     pub fn read_new_lines(&mut self) -> io::Result<Vec<String>> {
         match self.encoding {
             LogEncoding::Utf8 => self.read_utf8_lines(),
@@ -75,7 +78,7 @@ impl LogTailer {
         loop {
             buffer.clear();
             let bytes_read = reader.read_line(&mut buffer)?;
-            if bytes_read == 0 {
+            if bytes_read == 0 || !buffer.ends_with('\n') {
                 break;
             }
             self.position += bytes_read as u64;
@@ -113,23 +116,60 @@ impl LogTailer {
             .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
             .collect();
 
-        let text = String::from_utf16_lossy(&u16_units);
+        // Stop after the last complete line; the rest is re-read next time
+        let Some(last_newline) = u16_units.iter().rposition(|&unit| unit == u16::from(b'\n'))
+        else {
+            self.position += start as u64;
+            return Ok(lines);
+        };
+        let complete = &u16_units[..=last_newline];
+
+        let text = String::from_utf16_lossy(complete);
 
         for line in text.lines() {
             lines.push(line.to_string());
         }
 
-        self.position += bytes.len() as u64;
+        self.position += (start + complete.len() * 2) as u64;
 
         Ok(lines)
     }
 
+    /// Open the file again after it went missing (deleted, unmounted drive, ...).
+    /// Continues where reading stopped if the file is still at least that long,
+    /// otherwise it was replaced and is read from the start.
+    pub fn reopen(&mut self) -> io::Result<()> {
+        let mut file = File::open(&self.path)?;
+        let len = file.metadata()?.len();
+        self.encoding = Self::detect_encoding(&mut file)?;
+        if len < self.position {
+            self.position = 0;
+        }
+        self.file = file;
+        Ok(())
+    }
+
     pub fn rewind(&mut self) -> io::Result<()> {
         self.position = 0;
         self.file.seek(SeekFrom::Start(0))?;
         Ok(())
     }
 
+    /// Start over from the beginning if the file shrank below the read position
+    /// (truncated in place). Returns whether it did.
+    pub fn reset_if_truncated(&mut self) -> io::Result<bool> {
+        if self.file.metadata()?.len() < self.position {
+            self.rewind()?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Byte offset reading continues from
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
     #[allow(dead_code)]
     pub fn path(&self) -> &Path {
         &self.path
@@ -141,6 +181,74 @@ impl LogTailer {
     }
 }
 
+/// One line of JSON-lines output
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JsonlRecord<'a> {
+    Event(&'a CombatEvent),
+    Sample(&'a DpsSample),
+}
+
+/// Appends records as JSON lines to a file, or to stdout when the path is `-`,
+/// for external tools tailing the output.
+pub struct JsonlSink {
+    path: PathBuf,
+    writer: Box<dyn Write + Send>,
+}
+
+impl JsonlSink {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let writer: Box<dyn Write + Send> = if path == Path::new("-") {
+            Box::new(io::stdout())
+        } else {
+            let file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?;
+            Box::new(BufWriter::new(file))
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write a batch of records and flush, so a tailing reader sees whole lines promptly.
+    pub fn write_all(&mut self, records: &[JsonlRecord]) -> io::Result<()> {
+        for record in records {
+            serde_json::to_writer(&mut self.writer, record)?;
+            self.writer.write_all(b"\n")?;
+        }
+        self.writer.flush()
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Write combat events as a pretty-printed JSON array.
+pub fn write_events_json(path: &Path, events: &[CombatEvent]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(&mut writer, events)?;
+    writer.flush()
+}
+
+/// Write a target's hit timeline (see `analysis::target_timeline`) as CSV,
+/// seconds since the log's start and damage per row.
+pub fn write_target_timeline_csv(path: &Path, timeline: &[(Duration, f32)]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "seconds,amount")?;
+    for (time, amount) in timeline {
+        writeln!(writer, "{:.3},{}", time.as_secs_f64(), amount)?;
+    }
+    writer.flush()
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct CharacterLog {
     pub character: String,
@@ -158,99 +266,126 @@ pub struct Session {
     pub logs: Vec<CharacterLog>,
 }
 
-fn extract_header_info(path: &Path) -> io::Result<Option<(String, SystemTime)>> {
-    let file = File::open(path)?;
-    let mut reader = BufReader::new(file);
-    let mut buffer = String::new();
-
-    let mut character = None;
-    let mut timestamp = None;
-
-    for _ in 0..20 {
-        buffer.clear();
-        let bytes_read = reader.read_line(&mut buffer)?;
-        if bytes_read == 0 {
-            break;
-        }
-        let trimmed = buffer.trim();
-        if let Some(rest) = trimmed.strip_prefix("Listener:") {
-            character = Some(rest.trim().to_string());
-        } else if let Some(rest) = trimmed.strip_prefix("Session Started:") {
-            // Parse "2025.01.01 12:00:00"
-            let time_str = rest.trim();
-            if let Ok(naive) = NaiveDateTime::parse_from_str(time_str, "%Y.%m.%d %H:%M:%S") {
-                timestamp = Some(SystemTime::from(Utc.from_utc_datetime(&naive)));
-            }
-        }
+/// All gamelogs in `dir` that have a "Listener:" header, newest session first.
+pub fn scan_all_logs(dir: impl AsRef<Path>) -> io::Result<Vec<CharacterLog>> {
+    let headers = discovery::scan_logs_dir(dir, None, LogType::Gamelog)?;
+
+    Ok(headers
+        .into_iter()
+        .map(|header| CharacterLog {
+            character: header.character,
+            path: header.path,
+            last_modified: header.last_modified,
+            session_start: header.session_start,
+            file_size: header.file_size,
+        })
+        .collect())
+}
 
-        if character.is_some() && timestamp.is_some() {
-            return Ok(Some((character.unwrap(), timestamp.unwrap())));
-        }
+pub fn group_logs_by_character(logs: Vec<CharacterLog>) -> HashMap<String, Vec<CharacterLog>> {
+    let mut groups: HashMap<String, Vec<CharacterLog>> = HashMap::new();
+    for log in logs {
+        groups.entry(log.character.clone()).or_default().push(log);
     }
-
-    // Fallback: If we found character but no timestamp, we might return None or use SystemTime::UNIX_EPOCH?
-    // Let's adhere to previous strictness: if we can't fully parse, return None (or at least Character is mandatory).
-    if let Some(c) = character {
-        return Ok(Some((c, timestamp.unwrap_or(SystemTime::UNIX_EPOCH))));
+    // Sort logs within each group
+    for list in groups.values_mut() {
+        list.sort_by(|a, b| b.session_start.cmp(&a.session_start));
     }
+    groups
+}
 
-    Ok(None)
+/// Logs whose character name or file name matches `query`, case-insensitively,
+/// either as a substring or with its letters in order ("flx" matches "Felix").
+/// An empty query matches everything.
+pub fn filter_characters(logs: &[CharacterLog], query: &str) -> Vec<CharacterLog> {
+    let query = query.trim().to_lowercase();
+    logs.iter()
+        .filter(|log| {
+            let file_name = log
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            [log.character.to_lowercase(), file_name]
+                .iter()
+                .any(|text| fuzzy_matches(text, &query))
+        })
+        .cloned()
+        .collect()
 }
 
-pub fn scan_all_logs(dir: impl AsRef<Path>) -> io::Result<Vec<CharacterLog>> {
-    let mut logs = Vec::new();
+fn fuzzy_matches(text: &str, query: &str) -> bool {
+    if text.contains(query) {
+        return true;
+    }
+    let mut chars = text.chars();
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|q| chars.any(|c| c == q))
+}
 
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if !path.is_file() {
-            continue;
-        }
+/// One character's log files ordered by session start, oldest first.
+/// EVE starts a new file after every downtime, so a single play session
+/// is usually spread over several of these.
+pub fn session_chain(logs: &[CharacterLog], character: &str) -> Vec<CharacterLog> {
+    let mut chain: Vec<CharacterLog> = logs
+        .iter()
+        .filter(|log| log.character == character)
+        .cloned()
+        .collect();
+    chain.sort_by(|a, b| a.session_start.cmp(&b.session_start));
+    chain
+}
 
-        if let Some(extension) = path.extension().and_then(|ext| ext.to_str()) {
-            if !extension.eq_ignore_ascii_case("txt") {
-                continue;
-            }
-        } else {
-            continue;
+/// Split an ordered chain into runs of consecutive files started on the same
+/// (UTC) day; each run can be replayed as one continuous timeline.
+pub fn same_day_chains(chain: Vec<CharacterLog>) -> Vec<Vec<CharacterLog>> {
+    let day = |log: &CharacterLog| DateTime::<Utc>::from(log.session_start).date_naive();
+    let mut groups: Vec<Vec<CharacterLog>> = Vec::new();
+    for log in chain {
+        match groups.last_mut() {
+            Some(group) if group.last().map(day) == Some(day(&log)) => group.push(log),
+            _ => groups.push(vec![log]),
         }
+    }
+    groups
+}
 
-        let metadata = fs::metadata(&path)?;
-        let last_modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
-        let file_size = metadata.len();
-
-        if let Some((character, session_start)) = extract_header_info(&path)? {
-            logs.push(CharacterLog {
-                character,
-                path,
-                last_modified,
-                session_start,
-                file_size,
-            });
+/// `scan_all_logs` over several directories, merged newest session first.
+/// Directories that can't be read are skipped unless all of them fail.
+pub fn scan_all_logs_in(dirs: &[PathBuf]) -> io::Result<Vec<CharacterLog>> {
+    let mut logs = Vec::new();
+    let mut last_error = None;
+    let mut any_ok = false;
+
+    for dir in dirs {
+        match scan_all_logs(dir) {
+            Ok(found) => {
+                any_ok = true;
+                logs.extend(found);
+            }
+            Err(e) => last_error = Some(e),
         }
     }
 
+    if let (false, Some(e)) = (any_ok, last_error) {
+        return Err(e);
+    }
     logs.sort_by(|a, b| b.session_start.cmp(&a.session_start));
     Ok(logs)
 }
 
-pub fn group_logs_by_character(logs: Vec<CharacterLog>) -> HashMap<String, Vec<CharacterLog>> {
-    let mut groups: HashMap<String, Vec<CharacterLog>> = HashMap::new();
-    for log in logs {
-        groups.entry(log.character.clone()).or_default().push(log);
-    }
-    // Sort logs within each group
-    for list in groups.values_mut() {
-        list.sort_by(|a, b| b.session_start.cmp(&a.session_start));
-    }
-    groups
+pub fn scan_gamelogs_dir(dir: impl AsRef<Path>) -> io::Result<Vec<CharacterLog>> {
+    scan_gamelogs_dirs(&[dir.as_ref().to_path_buf()])
 }
 
-pub fn scan_gamelogs_dir(dir: impl AsRef<Path>) -> io::Result<Vec<CharacterLog>> {
+/// Latest log per character across all `dirs`, newest session first.
+pub fn scan_gamelogs_dirs(dirs: &[PathBuf]) -> io::Result<Vec<CharacterLog>> {
     let mut per_character: HashMap<String, CharacterLog> = HashMap::new();
 
     // Use scan_all_logs internally to DRY
-    let all_logs = scan_all_logs(dir)?;
+    let all_logs = scan_all_logs_in(dirs)?;
 
     for log in all_logs {
         match per_character.get(&log.character) {
@@ -262,10 +397,21 @@ pub fn scan_gamelogs_dir(dir: impl AsRef<Path>) -> io::Result<Vec<CharacterLog>>
     }
 
     let mut logs: Vec<CharacterLog> = per_character.into_values().collect();
-    logs.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+    logs.sort_by(|a, b| b.session_start.cmp(&a.session_start));
     Ok(logs)
 }
 
+/// Track the most recently modified log if nothing is tracked yet.
+/// Returns the newly tracked path, if any.
+pub fn auto_track_latest(logs: &[CharacterLog], tracked: &mut HashSet<PathBuf>) -> Option<PathBuf> {
+    if !tracked.is_empty() {
+        return None;
+    }
+    let newest = logs.iter().max_by_key(|log| log.last_modified)?;
+    tracked.insert(newest.path.clone());
+    Some(newest.path.clone())
+}
+
 #[allow(dead_code)]
 pub fn read_full_lines(path: impl AsRef<Path>) -> io::Result<Vec<String>> {
     let file = File::open(path)?;
@@ -280,11 +426,80 @@ pub fn read_full_lines(path: impl AsRef<Path>) -> io::Result<Vec<String>> {
     Ok(lines)
 }
 
-#[allow(dead_code)]
-pub fn read_full_events(path: impl AsRef<Path>) -> io::Result<Vec<CombatEvent>> {
+/// How many unparsed combat lines `diagnose` keeps as examples
+const DROPPED_SAMPLE_LINES: usize = 5;
+
+/// What `diagnose` found in a gamelog, for bug reports about unparsed formats
+#[derive(Debug, Clone, Serialize)]
+pub struct LogDiagnostics {
+    pub header_found: bool,
+    pub character: Option<String>,
+    pub session_start: Option<SystemTime>,
+    pub encoding: LogEncoding,
+    pub total_lines: usize,
+    pub combat_events: usize,
+    pub notify_events: usize,
+    /// Combat-channel misses; they carry no damage and aren't turned into events
+    pub miss_lines: usize,
+    /// Combat-channel lines the parser couldn't make sense of
+    pub dropped_lines: usize,
+    /// The first few dropped lines, verbatim
+    pub dropped_sample: Vec<String>,
+}
+
+/// Parse a whole gamelog with `locale`'s combat tokens and report how much of it was understood.
+pub fn diagnose(path: &Path, locale: &parser::LocaleConfig) -> io::Result<LogDiagnostics> {
+    let header = discovery::extract_header(path, LogType::Gamelog)?;
+    let encoding = LogTailer::open(path)?.encoding();
+    let content = discovery::read_log_file(path)?;
+
+    let mut parser = match &header {
+        Some(header) => parser::LineParser::new_with_listener(&header.character),
+        None => parser::LineParser::new(),
+    }
+    .with_locale(locale.clone());
+    let source = header.as_ref().map_or("", |h| h.character.as_str());
+
+    let mut diagnostics = LogDiagnostics {
+        header_found: header.is_some(),
+        character: header.as_ref().map(|h| h.character.clone()),
+        session_start: header.as_ref().map(|h| h.session_start),
+        encoding,
+        total_lines: 0,
+        combat_events: 0,
+        notify_events: 0,
+        miss_lines: 0,
+        dropped_lines: 0,
+        dropped_sample: Vec::new(),
+    };
+
+    for line in content.lines() {
+        diagnostics.total_lines += 1;
+        if parser.parse_line(line, source).is_some() {
+            diagnostics.combat_events += 1;
+        } else if parser.parse_notify_line(line, source).is_some() {
+            diagnostics.notify_events += 1;
+        } else if parser.is_miss_line(line) {
+            diagnostics.miss_lines += 1;
+        } else if line.contains(parser.locale().combat_marker.as_str()) {
+            diagnostics.dropped_lines += 1;
+            if diagnostics.dropped_sample.len() < DROPPED_SAMPLE_LINES {
+                diagnostics.dropped_sample.push(line.to_string());
+            }
+        }
+    }
+
+    Ok(diagnostics)
+}
+
+/// Every combat event of a log, read with the client language's `locale`
+pub fn read_full_events(
+    path: impl AsRef<Path>,
+    locale: &parser::LocaleConfig,
+) -> io::Result<Vec<CombatEvent>> {
     let lines = read_full_lines(path)?;
     let mut events = Vec::new();
-    let mut parser = parser::LineParser::new();
+    let mut parser = parser::LineParser::new().with_locale(locale.clone());
 
     for line in lines {
         if let Some(event) = parser.parse_line(&line, "") {
@@ -299,7 +514,6 @@ pub fn read_full_events(path: impl AsRef<Path>) -> io::Result<Vec<CombatEvent>>
 mod tests {
     use super::*;
     use std::io::Write;
-    use std::time::Duration;
     use tempfile::tempdir;
 
     fn create_dummy_log(path: PathBuf, char_name: &str, time_str: &str) {
@@ -336,6 +550,95 @@ mod tests {
         assert_eq!(all_logs.len(), 2);
     }
 
+    #[test]
+    fn test_scan_skips_headerless_files() {
+        let dir = tempdir().unwrap();
+        create_dummy_log(dir.path().join("valid.txt"), "CharA", "2024.01.01 10:00:00");
+        fs::write(dir.path().join("notes.txt"), "just some notes\n").unwrap();
+        fs::write(
+            dir.path().join("partial.txt"),
+            "------------------------------------------------------------\n  Gamelog\n  Listener:\n",
+        )
+        .unwrap();
+
+        let logs = scan_gamelogs_dir(dir.path()).unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].character, "CharA");
+        assert_eq!(logs[0].path.file_name().unwrap(), "valid.txt");
+    }
+
+    #[test]
+    fn test_session_chain_orders_same_day_files() {
+        let dir = tempdir().unwrap();
+        // Written out of order on purpose
+        create_dummy_log(
+            dir.path().join("after_dt.txt"),
+            "CharA",
+            "2024.01.01 11:30:00",
+        );
+        create_dummy_log(
+            dir.path().join("before_dt.txt"),
+            "CharA",
+            "2024.01.01 09:00:00",
+        );
+        create_dummy_log(dir.path().join("other.txt"), "CharB", "2024.01.01 10:00:00");
+        create_dummy_log(
+            dir.path().join("next_day.txt"),
+            "CharA",
+            "2024.01.02 09:00:00",
+        );
+
+        let logs = scan_all_logs(dir.path()).unwrap();
+        let chain = session_chain(&logs, "CharA");
+        let names: Vec<_> = chain
+            .iter()
+            .map(|log| log.path.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["before_dt.txt", "after_dt.txt", "next_day.txt"]);
+
+        let days = same_day_chains(chain);
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].len(), 2);
+        assert_eq!(days[0][0].path.file_name().unwrap(), "before_dt.txt");
+        assert_eq!(days[0][1].path.file_name().unwrap(), "after_dt.txt");
+        assert_eq!(days[1].len(), 1);
+    }
+
+    #[test]
+    fn test_scan_multiple_dirs_unions_characters() {
+        let install_a = tempdir().unwrap();
+        let install_b = tempdir().unwrap();
+        create_dummy_log(
+            install_a.path().join("a.txt"),
+            "CharA",
+            "2024.01.01 10:00:00",
+        );
+        create_dummy_log(
+            install_b.path().join("b.txt"),
+            "CharB",
+            "2024.01.01 11:00:00",
+        );
+        create_dummy_log(
+            install_b.path().join("a2.txt"),
+            "CharA",
+            "2024.01.01 12:00:00",
+        );
+
+        let dirs = vec![
+            install_a.path().to_path_buf(),
+            install_b.path().to_path_buf(),
+            install_b.path().join("missing"),
+        ];
+        let logs = scan_gamelogs_dirs(&dirs).unwrap();
+
+        let mut characters: Vec<_> = logs.iter().map(|l| l.character.as_str()).collect();
+        characters.sort();
+        assert_eq!(characters, vec!["CharA", "CharB"]);
+
+        let all = scan_all_logs_in(&dirs).unwrap();
+        assert_eq!(all.len(), 3);
+    }
+
     #[test]
     fn test_group_logs_by_character() {
         let dir = tempdir().unwrap();
@@ -350,4 +653,120 @@ mod tests {
         assert_eq!(groups.get("CharA").unwrap().len(), 2);
         assert_eq!(groups.get("CharB").unwrap().len(), 1);
     }
+
+    #[test]
+    fn test_diagnose_counts_parsed_and_dropped_lines() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("20250101_120000_1.txt");
+        create_dummy_log(path.clone(), "Pilot", "2025.01.01 12:00:00");
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        for line in [
+            "[ 2025.01.01 12:01:00 ] (combat) 100 to Enemy - Gun - Hits",
+            "[ 2025.01.01 12:01:01 ] (combat) 40 from Rat - Hits",
+            "[ 2025.01.01 12:01:02 ] (combat) Your target is too far away",
+            "[ 2025.01.01 12:01:03 ] (notify) Gun requires 39.0 units of charge. The capacitor has only 6.2 units.",
+            "[ 2025.01.01 12:01:04 ] (hint) Some tooltip",
+            "[ 2025.01.01 12:01:05 ] (combat) garbled <b>line",
+            "[ 2025.01.01 12:01:06 ] (combat) Your Gun misses Enemy completely - Gun",
+            "[ 2025.01.01 12:01:07 ] (combat) <b>Rat</b><font size=10>misses you completely</font>",
+        ] {
+            writeln!(file, "{}", line).unwrap();
+        }
+
+        let diagnostics = diagnose(&path, &parser::LocaleConfig::default()).unwrap();
+        assert!(diagnostics.header_found);
+        assert_eq!(diagnostics.character.as_deref(), Some("Pilot"));
+        assert_eq!(diagnostics.encoding, LogEncoding::Utf8);
+        assert_eq!(diagnostics.combat_events, 2);
+        assert_eq!(diagnostics.notify_events, 1);
+        assert_eq!(diagnostics.miss_lines, 2);
+        assert_eq!(diagnostics.dropped_lines, 2);
+        assert!(diagnostics.dropped_sample[0].contains("too far away"));
+        assert_eq!(diagnostics.total_lines, 13);
+    }
+
+    #[test]
+    fn test_auto_track_latest_picks_newest_when_nothing_tracked() {
+        let log = |character: &str, file: &str, secs: u64| CharacterLog {
+            character: character.to_string(),
+            path: PathBuf::from(file),
+            last_modified: SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+            session_start: SystemTime::UNIX_EPOCH,
+            file_size: 0,
+        };
+        let logs = vec![
+            log("Old Pilot", "20250101_120000_111.txt", 100),
+            log("Active Pilot", "20250101_120000_222.txt", 500),
+            log("Alt", "20250101_120000_333.txt", 300),
+        ];
+
+        let mut tracked = HashSet::new();
+        assert_eq!(
+            auto_track_latest(&logs, &mut tracked),
+            Some(PathBuf::from("20250101_120000_222.txt"))
+        );
+        assert!(tracked.contains(&PathBuf::from("20250101_120000_222.txt")));
+
+        // An existing selection is left alone
+        let mut tracked = HashSet::from([PathBuf::from("20250101_120000_111.txt")]);
+        assert_eq!(auto_track_latest(&logs, &mut tracked), None);
+        assert_eq!(tracked.len(), 1);
+    }
+
+    #[test]
+    fn test_filter_characters_matches_name_case_insensitively() {
+        let log = |character: &str, file: &str| CharacterLog {
+            character: character.to_string(),
+            path: PathBuf::from(file),
+            last_modified: SystemTime::UNIX_EPOCH,
+            session_start: SystemTime::UNIX_EPOCH,
+            file_size: 0,
+        };
+        let logs = vec![
+            log("Felix Allistar", "20250101_120000_111.txt"),
+            log("Bob", "20250101_120000_222.txt"),
+        ];
+
+        let names = |query: &str| -> Vec<String> {
+            filter_characters(&logs, query)
+                .into_iter()
+                .map(|log| log.character)
+                .collect()
+        };
+        assert_eq!(names("fel"), vec!["Felix Allistar"]);
+        assert_eq!(names("FLX"), vec!["Felix Allistar"]);
+        // File names match too
+        assert_eq!(names("_222"), vec!["Bob"]);
+        assert_eq!(names("").len(), 2);
+        assert!(names("zed").is_empty());
+    }
+
+    #[test]
+    fn test_utf16_partial_line_waits_for_newline() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Local_20260103_112630_12345.txt");
+        let utf16 = |text: &str| -> Vec<u8> {
+            text.encode_utf16()
+                .flat_map(|unit| unit.to_le_bytes())
+                .collect()
+        };
+
+        fs::write(&path, [&[0xFF, 0xFE][..], &utf16("header\r\n")].concat()).unwrap();
+        let mut tailer = LogTailer::open(&path).unwrap();
+        tailer.rewind().unwrap();
+        assert_eq!(tailer.read_new_lines().unwrap(), vec!["header"]);
+
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&utf16("[ 2026.01.03 11:30:05 ] EVE System > Channel chan"))
+            .unwrap();
+        assert!(tailer.read_new_lines().unwrap().is_empty());
+
+        file.write_all(&utf16("ged to Local : Unknown\r\n"))
+            .unwrap();
+        assert_eq!(
+            tailer.read_new_lines().unwrap(),
+            vec!["[ 2026.01.03 11:30:05 ] EVE System > Channel changed to Local : Unknown"]
+        );
+        assert!(tailer.read_new_lines().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,86 @@
+// Bounded in-memory log of recent backend diagnostics, for the UI debug panel.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How many lines the backend keeps by default
+pub const DEFAULT_LOG_CAPACITY: usize = 500;
+
+/// One logged diagnostic line
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LogLine {
+    /// Wall-clock time the line was pushed (ms since the Unix epoch)
+    pub timestamp_ms: u64,
+    pub message: String,
+}
+
+/// Keeps the last `capacity` log lines, dropping the oldest first.
+pub struct LogRing {
+    lines: Mutex<VecDeque<LogLine>>,
+    capacity: usize,
+}
+
+impl LogRing {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    /// Record a line, evicting the oldest once full.
+    pub fn push(&self, message: impl Into<String>) {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        let mut lines = self.lines.lock().unwrap();
+        if self.capacity == 0 {
+            return;
+        }
+        while lines.len() >= self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(LogLine {
+            timestamp_ms,
+            message: message.into(),
+        });
+    }
+
+    /// Print a line to stdout as well as recording it.
+    pub fn log(&self, message: impl Into<String>) {
+        let message = message.into();
+        println!("{}", message);
+        self.push(message);
+    }
+
+    /// Copy of the current lines, oldest first
+    pub fn snapshot(&self) -> Vec<LogLine> {
+        self.lines.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+impl Default for LogRing {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOG_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_evicts_oldest_past_capacity() {
+        let ring = LogRing::new(3);
+        for i in 0..5 {
+            ring.push(format!("line {}", i));
+        }
+
+        let messages: Vec<String> = ring.snapshot().into_iter().map(|l| l.message).collect();
+        assert_eq!(messages, vec!["line 2", "line 3", "line 4"]);
+    }
+}
@@ -5,7 +5,9 @@ pub mod config;
 pub mod coordinator;
 pub mod discovery;
 pub mod inline_bookmarks;
+pub mod log_dir_watch;
 pub mod log_io;
+pub mod log_ring;
 pub mod model;
 pub mod parser;
 pub mod replay_engine;
@@ -1,9 +1,10 @@
 // NOTE: TypeScript mirror types are in ui/src/types.ts
 // Keep both files in sync when modifying data structures.
 
+use chrono::{DateTime, FixedOffset, Local, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::Duration;
+use std::time::{Duration, UNIX_EPOCH};
 
 pub type EntityName = String;
 pub type WeaponName = String;
@@ -14,6 +15,41 @@ pub enum EventType {
     Repair,
     Capacitor,
     Neut,
+    /// Electronic warfare applied (amount is always 0)
+    Ewar {
+        kind: EwarKind,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum EwarKind {
+    WarpScramble,
+    WarpDisruption,
+    StasisWeb,
+    TargetPainter,
+    TrackingDisruptor,
+}
+
+impl EwarKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::WarpScramble => "Warp Scramble",
+            Self::WarpDisruption => "Warp Disruption",
+            Self::StasisWeb => "Stasis Web",
+            Self::TargetPainter => "Target Painter",
+            Self::TrackingDisruptor => "Tracking Disruptor",
+        }
+    }
+}
+
+/// Broad weapon family, for summaries coarser than per-weapon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WeaponCategory {
+    Turret,
+    Missile,
+    Drone,
+    Smartbomb,
+    Other,
 }
 
 /// Per-target damage breakdown for a weapon action
@@ -44,6 +80,101 @@ pub struct CombatEvent {
     pub incoming: bool,
     pub character: String, // The character whose log this event came from
     pub event_type: EventType,
+    /// Hit quality suffix of damage lines ("Hits", "Smashes", ...), if present
+    #[serde(default)]
+    pub hit_quality: Option<HitQuality>,
+    /// Damage dealt by a drone (the `weapon` is the drone's name)
+    #[serde(default)]
+    pub via_drone: bool,
+    /// The listener is both source and target; counted as incoming only
+    #[serde(default)]
+    pub self_inflicted: bool,
+    /// Tank layer an incoming hit landed on, when the line says so
+    #[serde(default)]
+    pub damage_layer: Option<DamageLayer>,
+}
+
+/// Tank layer named on an incoming damage line
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum DamageLayer {
+    Shield,
+    Armor,
+    Hull,
+}
+
+impl DamageLayer {
+    /// Parse a layer segment such as "Armor" or "(hull)" (case-insensitive)
+    pub fn from_segment(text: &str) -> Option<Self> {
+        let word = text.trim().trim_start_matches('(').trim_end_matches(')');
+        match word.trim().to_ascii_lowercase().as_str() {
+            "shield" | "shields" => Some(DamageLayer::Shield),
+            "armor" | "armour" => Some(DamageLayer::Armor),
+            "hull" | "structure" => Some(DamageLayer::Hull),
+            _ => None,
+        }
+    }
+}
+
+/// Application quality reported at the end of EVE damage lines
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum HitQuality {
+    Misses,
+    Scratches,
+    Glances,
+    Grazes,
+    Hits,
+    Penetrates,
+    Smashes,
+    Wrecks,
+}
+
+impl HitQuality {
+    /// Parse the quality segment of a combat line (case-insensitive)
+    pub fn from_suffix(text: &str) -> Option<Self> {
+        let lower = text.to_lowercase();
+        [
+            ("misses", HitQuality::Misses),
+            ("scratches", HitQuality::Scratches),
+            ("glances", HitQuality::Glances),
+            ("grazes", HitQuality::Grazes),
+            ("hits", HitQuality::Hits),
+            ("penetrates", HitQuality::Penetrates),
+            ("smashes", HitQuality::Smashes),
+            ("wrecks", HitQuality::Wrecks),
+        ]
+        .into_iter()
+        .find(|(word, _)| lower.contains(word))
+        .map(|(_, quality)| quality)
+    }
+}
+
+/// Per-weapon tally of hit qualities
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QualityCounts {
+    pub misses: u32,
+    pub scratches: u32,
+    pub glances: u32,
+    pub grazes: u32,
+    pub hits: u32,
+    pub penetrates: u32,
+    pub smashes: u32,
+    pub wrecks: u32,
+}
+
+impl QualityCounts {
+    pub fn record(&mut self, quality: HitQuality) {
+        let count = match quality {
+            HitQuality::Misses => &mut self.misses,
+            HitQuality::Scratches => &mut self.scratches,
+            HitQuality::Glances => &mut self.glances,
+            HitQuality::Grazes => &mut self.grazes,
+            HitQuality::Hits => &mut self.hits,
+            HitQuality::Penetrates => &mut self.penetrates,
+            HitQuality::Smashes => &mut self.smashes,
+            HitQuality::Wrecks => &mut self.wrecks,
+        };
+        *count += 1;
+    }
 }
 
 impl CombatEvent {
@@ -56,6 +187,128 @@ impl CombatEvent {
     }
 }
 
+/// Combat stats for one closed Abyss run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunSummary {
+    pub character: String,
+    pub duration_secs: u64,
+    pub total_outgoing: f32,
+    pub total_incoming: f32,
+    pub peak_dps: f32,
+    /// Most damaged targets, highest first
+    pub top_targets: Vec<TargetHit>,
+}
+
+/// Combat stats for one room, from a ROOM_START to the next ROOM_END bookmark
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoomStats {
+    /// Position of the room in the run, from 0
+    pub index: usize,
+    /// Room bounds on the events' timeline
+    pub start: Duration,
+    pub end: Duration,
+    pub duration_secs: u64,
+    pub total_outgoing: f32,
+    pub total_incoming: f32,
+    pub peak_outgoing_dps: f32,
+    pub peak_incoming_dps: f32,
+}
+
+/// How dangerous a stretch of combat was, judged by damage dealt vs received
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SurvivabilityBand {
+    Safe,
+    Tense,
+    Dangerous,
+}
+
+/// Outgoing/incoming damage ratio with its band, for color-coding a room
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SurvivabilityScore {
+    /// Outgoing divided by incoming; `None` when nothing was received
+    pub ratio: Option<f32>,
+    pub band: SurvivabilityBand,
+}
+
+/// Time zone used when showing log timestamps to the user.
+/// Logs themselves (and bookmarks written into them) always stay in UTC.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TzMode {
+    /// EVE time
+    #[default]
+    Utc,
+    /// The system's local time zone
+    Local,
+    /// Fixed offset east of UTC, in minutes
+    Fixed(i32),
+}
+
+/// Format an instant (since the Unix epoch) like EVE logs, "2026.01.04 03:56:49",
+/// in the given time zone. Out-of-range fixed offsets fall back to UTC.
+pub fn format_log_timestamp(epoch: Duration, tz: TzMode) -> String {
+    const FORMAT: &str = "%Y.%m.%d %H:%M:%S";
+    let utc: DateTime<Utc> = DateTime::from(UNIX_EPOCH + epoch);
+    match tz {
+        TzMode::Utc => utc.format(FORMAT).to_string(),
+        TzMode::Local => utc.with_timezone(&Local).format(FORMAT).to_string(),
+        TzMode::Fixed(minutes) => match FixedOffset::east_opt(minutes * 60) {
+            Some(offset) => utc.with_timezone(&offset).format(FORMAT).to_string(),
+            None => utc.format(FORMAT).to_string(),
+        },
+    }
+}
+
+/// How DPS and damage numbers are written out
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum NumberFormat {
+    /// "1234567.0"
+    #[default]
+    Plain,
+    /// "1,234,567.0"
+    Thousands,
+    /// "1.23M", "4.5k"
+    Suffixed,
+}
+
+/// Format a DPS or damage value in the given style, with one decimal (or up to
+/// two significant decimals for suffixed values).
+pub fn format_number(value: f64, style: NumberFormat) -> String {
+    match style {
+        NumberFormat::Plain => format!("{:.1}", value),
+        NumberFormat::Thousands => {
+            let formatted = format!("{:.1}", value.abs());
+            let (int_part, frac_part) = formatted.split_once('.').unwrap_or((&formatted, "0"));
+            let mut grouped = String::with_capacity(int_part.len() + int_part.len() / 3);
+            for (i, digit) in int_part.chars().enumerate() {
+                if i > 0 && (int_part.len() - i) % 3 == 0 {
+                    grouped.push(',');
+                }
+                grouped.push(digit);
+            }
+            let sign = if value < 0.0 && formatted != "0.0" {
+                "-"
+            } else {
+                ""
+            };
+            format!("{}{}.{}", sign, grouped, frac_part)
+        }
+        NumberFormat::Suffixed => {
+            let abs = value.abs();
+            // 999_999 would round to "1000k"; show it as "1M" instead
+            let (scaled, suffix) = if abs >= 999_995.0 {
+                (value / 1_000_000.0, "M")
+            } else if abs >= 1_000.0 {
+                (value / 1_000.0, "k")
+            } else {
+                return format!("{:.1}", value);
+            };
+            let formatted = format!("{:.2}", scaled);
+            let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+            format!("{}{}", trimmed, suffix)
+        }
+    }
+}
+
 /// A notify event from EVE logs - module activation failures, etc.
 /// Parsed from lines like: (notify) Module requires X units of charge. The capacitor has only Y units.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,6 +320,15 @@ pub struct NotifyEvent {
     pub available_cap: f32,
 }
 
+/// A ship confirmed destroyed, from a (notify) destruction line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KillEvent {
+    pub time: Duration,
+    /// Character whose log reported the kill
+    pub character: String,
+    pub victim: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DpsSample {
     pub time: Duration,
@@ -78,6 +340,15 @@ pub struct DpsSample {
     pub incoming_cap: f32, // New
     pub outgoing_neut: f32,
     pub incoming_neut: f32, // New
+    /// Incoming DPS split by `classify_attacker`; zero until
+    /// `split_incoming_by_attacker` runs with the configured overrides
+    #[serde(default)]
+    pub incoming_npc_dps: f32,
+    #[serde(default)]
+    pub incoming_player_dps: f32,
+    /// Incoming DPS from hazards rather than ships, see `split_environmental`
+    #[serde(default)]
+    pub incoming_environmental_dps: f32,
 
     // Detailed breakdowns
     pub outgoing_by_weapon: HashMap<WeaponName, f32>,
@@ -96,6 +367,47 @@ pub struct DpsSample {
     pub combat_actions_by_character: HashMap<String, Vec<CombatAction>>,
 }
 
+/// Resist percentages (0-100) of the ship taking damage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResistProfile {
+    pub em: f32,
+    pub thermal: f32,
+    pub kinetic: f32,
+    pub explosive: f32,
+}
+
+/// Fraction of an attacker's damage dealt as each type. Fractions sum to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DamageProfile {
+    pub em: f32,
+    pub thermal: f32,
+    pub kinetic: f32,
+    pub explosive: f32,
+}
+
+impl DamageProfile {
+    /// Even split, used when the attacker's damage type is unknown
+    pub const UNIFORM: Self = Self::new(0.25, 0.25, 0.25, 0.25);
+
+    pub const fn new(em: f32, thermal: f32, kinetic: f32, explosive: f32) -> Self {
+        Self {
+            em,
+            thermal,
+            kinetic,
+            explosive,
+        }
+    }
+
+    /// Fraction of raw damage that gets through `resists`.
+    pub fn multiplier(&self, resists: &ResistProfile) -> f32 {
+        let through = |resist: f32| 1.0 - resist.clamp(0.0, 100.0) / 100.0;
+        self.em * through(resists.em)
+            + self.thermal * through(resists.thermal)
+            + self.kinetic * through(resists.kinetic)
+            + self.explosive * through(resists.explosive)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct FightSummary {
@@ -104,3 +416,430 @@ pub struct FightSummary {
     pub total_damage: f32,
     pub samples: Vec<DpsSample>,
 }
+
+/// Canned combat patterns for tests, benchmarks and UI demos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DemoProfile {
+    /// Light trading of fire with a few frigates
+    QuietRoom,
+    /// Heavy outgoing volleys, little return fire
+    BurstDps,
+    /// The ship is being hammered and shoots back sparingly
+    HeavyIncoming,
+}
+
+const DEMO_CHARACTER: &str = "Demo Pilot";
+const DEMO_WEAPONS: &[&str] = &[
+    "Small Focused Beam Laser II",
+    "Hornet EC-300",
+    "Light Ion Blaster II",
+    "Scourge Light Missile",
+];
+const DEMO_RATS: &[&str] = &[
+    "Starving Damavik",
+    "Blinding Leshak",
+    "Ghosting Kikimora",
+    "Tessellating Lucid Warden",
+];
+
+/// A minute of synthetic combat for `profile`, sorted by timestamp.
+/// The same profile always produces the same events.
+pub fn demo_events(profile: DemoProfile) -> Vec<CombatEvent> {
+    // (outgoing interval ms, outgoing amount range, incoming interval ms, incoming amount range)
+    let (out_every, out_range, in_every, in_range) = match profile {
+        DemoProfile::QuietRoom => (3000, (120.0, 260.0), 6000, (40.0, 110.0)),
+        DemoProfile::BurstDps => (500, (300.0, 650.0), 8000, (30.0, 90.0)),
+        DemoProfile::HeavyIncoming => (2500, (100.0, 220.0), 400, (180.0, 420.0)),
+    };
+
+    // Small LCG so demo data is reproducible without a rand dependency
+    let mut seed = profile as u64 + 0x2545_f491_4f6c_dd1d;
+    let mut next = move || {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 33) as u32
+    };
+
+    const SPAN_MS: u64 = 60_000;
+    let mut events = Vec::new();
+    for (incoming, every, (min, max)) in [(false, out_every, out_range), (true, in_every, in_range)]
+    {
+        let mut at = 0;
+        while at < SPAN_MS {
+            let rat = DEMO_RATS[next() as usize % DEMO_RATS.len()];
+            let weapon = DEMO_WEAPONS[next() as usize % DEMO_WEAPONS.len()];
+            let amount = min + (max - min) * (next() % 1000) as f32 / 1000.0;
+            let (source, target) = if incoming {
+                (rat, DEMO_CHARACTER)
+            } else {
+                (DEMO_CHARACTER, rat)
+            };
+            events.push(CombatEvent {
+                timestamp: Duration::from_millis(at),
+                source: source.to_string(),
+                target: target.to_string(),
+                weapon: if incoming { rat } else { weapon }.to_string(),
+                amount: amount.round(),
+                incoming,
+                character: DEMO_CHARACTER.to_string(),
+                event_type: EventType::Damage,
+                hit_quality: Some(HitQuality::Hits),
+                via_drone: !incoming && weapon.starts_with("Hornet"),
+                self_inflicted: false,
+                damage_layer: None,
+            });
+            // Jitter the cadence by up to +/-25%
+            at += every * 3 / 4 + (next() as u64 % (every / 2 + 1));
+        }
+    }
+    events.sort_by_key(|event| event.timestamp);
+    events
+}
+
+/// Who dealt incoming damage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AttackerKind {
+    Npc,
+    Player,
+}
+
+/// Lowercase words that only show up in NPC names: Abyssal hull types, their
+/// qualifiers, and environmental sources.
+const NPC_NAME_WORDS: &[&str] = &[
+    // Triglavian and Drifter hulls
+    "damavik",
+    "kikimora",
+    "vedmak",
+    "leshak",
+    "drekavac",
+    "rodiva",
+    "zoreyshka",
+    "hospodar",
+    "deepwatcher",
+    "upholder",
+    "warden",
+    "firewatcher",
+    "obfuscator",
+    // Rogue drones and Sleepers
+    "ephialtes",
+    "karybdis",
+    "tyrannos",
+    "skybreaker",
+    "thunderchild",
+    "stormbringer",
+    "swarmer",
+    "entanglers",
+    "sentinel",
+    "awakened",
+    "emergent",
+    // Qualifiers
+    "starving",
+    "vila",
+    "blinding",
+    "anchoring",
+    "ghosting",
+    "harrowing",
+    "renewing",
+    "spearing",
+    "striking",
+    "tangling",
+    "shining",
+    "lucid",
+    "tessellating",
+    "photic",
+    "devoted",
+    "elite",
+    // Environment
+    "abyssal",
+    "unstable",
+    "cache",
+    "tower",
+];
+
+/// Heuristic attacker classification. An `overrides` entry for the (undecorated)
+/// name wins; otherwise corp-ticker decorated names ("Name[CORP](Ship)") are
+/// players, names containing a known NPC word are NPCs, and anything else is
+/// assumed to be a capsuleer name.
+pub fn classify_attacker(name: &str, overrides: &HashMap<String, AttackerKind>) -> AttackerKind {
+    let base = name.split(['[', '(']).next().unwrap_or(name).trim();
+    if let Some(kind) = overrides.get(base) {
+        return *kind;
+    }
+    if name.contains('[') {
+        return AttackerKind::Player;
+    }
+    let is_npc = base
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| NPC_NAME_WORDS.contains(&word));
+    if is_npc {
+        AttackerKind::Npc
+    } else {
+        AttackerKind::Player
+    }
+}
+
+/// Damage sources that are Abyss hazards rather than ships
+pub const DEFAULT_ENVIRONMENTAL_SOURCES: &[&str] = &["Unstable Abyssal Depths"];
+
+pub fn default_environmental_sources() -> Vec<String> {
+    DEFAULT_ENVIRONMENTAL_SOURCES
+        .iter()
+        .map(|source| source.to_string())
+        .collect()
+}
+
+/// Whether a damage source contains any of `sources` (case-insensitive)
+pub fn is_environmental_source(source: &str, sources: &[String]) -> bool {
+    let lower = source.to_lowercase();
+    sources
+        .iter()
+        .any(|env| !env.is_empty() && lower.contains(&env.to_lowercase()))
+}
+
+/// Move environmental sources out of `incoming_by_source` into
+/// `incoming_environmental_dps`, so hazards don't show up as attackers.
+/// `incoming_dps` still includes them; re-run `split_incoming_by_attacker` afterwards.
+pub fn split_environmental(sample: &mut DpsSample, sources: &[String]) {
+    sample.incoming_environmental_dps = 0.0;
+    sample.incoming_by_source.retain(|source, dps| {
+        if is_environmental_source(source, sources) {
+            sample.incoming_environmental_dps += *dps;
+            false
+        } else {
+            true
+        }
+    });
+}
+
+/// Fold the per-character breakdowns of aliased characters into their canonical
+/// pilot (`aliases` maps raw character name to pilot). Events keep their raw
+/// names, so removing an alias undoes the folding on the next sample.
+pub fn fold_character_aliases(sample: &mut DpsSample, aliases: &HashMap<String, String>) {
+    if aliases.is_empty() {
+        return;
+    }
+    let pilot = |name: String| aliases.get(&name).cloned().unwrap_or(name);
+
+    fn fold_totals<K: std::hash::Hash + Eq>(
+        map: &mut HashMap<String, HashMap<K, f32>>,
+        pilot: impl Fn(String) -> String,
+    ) {
+        let mut folded: HashMap<String, HashMap<K, f32>> = HashMap::new();
+        for (character, inner) in map.drain() {
+            let entry = folded.entry(pilot(character)).or_default();
+            for (key, value) in inner {
+                *entry.entry(key).or_default() += value;
+            }
+        }
+        *map = folded;
+    }
+
+    for map in [
+        &mut sample.outgoing_by_character,
+        &mut sample.incoming_by_character,
+    ] {
+        let mut folded: HashMap<String, f32> = HashMap::new();
+        for (character, dps) in map.drain() {
+            *folded.entry(pilot(character)).or_default() += dps;
+        }
+        *map = folded;
+    }
+    fold_totals(&mut sample.outgoing_by_char_weapon, pilot);
+    fold_totals(&mut sample.outgoing_by_char_target, pilot);
+
+    let mut actions: HashMap<String, Vec<CombatAction>> = HashMap::new();
+    for (character, character_actions) in sample.combat_actions_by_character.drain() {
+        let merged = actions.entry(pilot(character)).or_default();
+        for action in character_actions {
+            let existing = merged.iter_mut().find(|a| {
+                a.name == action.name
+                    && a.action_type == action.action_type
+                    && a.incoming == action.incoming
+            });
+            let Some(existing) = existing else {
+                merged.push(action);
+                continue;
+            };
+            existing.value += action.value;
+            for hit in action.targets {
+                match existing.targets.iter_mut().find(|t| t.target == hit.target) {
+                    Some(target) => target.value += hit.value,
+                    None => existing.targets.push(hit),
+                }
+            }
+        }
+    }
+    sample.combat_actions_by_character = actions;
+}
+
+/// Split a sample's incoming DPS into NPC and player parts from `incoming_by_source`.
+pub fn split_incoming_by_attacker(
+    sample: &mut DpsSample,
+    overrides: &HashMap<String, AttackerKind>,
+) {
+    sample.incoming_npc_dps = 0.0;
+    sample.incoming_player_dps = 0.0;
+    for (source, dps) in &sample.incoming_by_source {
+        match classify_attacker(source, overrides) {
+            AttackerKind::Npc => sample.incoming_npc_dps += dps,
+            AttackerKind::Player => sample.incoming_player_dps += dps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::analysis::tests::{last_sample, make_event};
+
+    #[test]
+    fn formats_log_timestamp_in_utc_and_fixed_offset() {
+        // 2026-01-04 03:56:49 UTC
+        let epoch = Duration::from_secs(1_767_499_009);
+        assert_eq!(
+            format_log_timestamp(epoch, TzMode::Utc),
+            "2026.01.04 03:56:49"
+        );
+        assert_eq!(
+            format_log_timestamp(epoch, TzMode::Fixed(3 * 60)),
+            "2026.01.04 06:56:49"
+        );
+        // Crossing midnight backwards
+        assert_eq!(
+            format_log_timestamp(epoch, TzMode::Fixed(-5 * 60)),
+            "2026.01.03 22:56:49"
+        );
+    }
+
+    #[test]
+    fn demo_profiles_have_expected_dominant_direction() {
+        let totals = |profile| {
+            let events = demo_events(profile);
+            assert!(!events.is_empty());
+            assert!(events.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+            events.iter().fold((0.0, 0.0), |(out, inc), e| {
+                if e.incoming {
+                    (out, inc + e.amount)
+                } else {
+                    (out + e.amount, inc)
+                }
+            })
+        };
+
+        let (out, inc) = totals(DemoProfile::QuietRoom);
+        assert!(out > inc);
+        let (out, inc) = totals(DemoProfile::BurstDps);
+        assert!(out > inc * 5.0);
+        let (out, inc) = totals(DemoProfile::HeavyIncoming);
+        assert!(inc > out);
+
+        // Deterministic
+        assert_eq!(
+            demo_events(DemoProfile::BurstDps).len(),
+            demo_events(DemoProfile::BurstDps).len()
+        );
+    }
+
+    #[test]
+    fn classifies_npcs_and_players() {
+        let none = HashMap::new();
+        assert_eq!(
+            classify_attacker("Starving Damavik", &none),
+            AttackerKind::Npc
+        );
+        assert_eq!(
+            classify_attacker("Unstable Abyssal Depths", &none),
+            AttackerKind::Npc
+        );
+        assert_eq!(
+            classify_attacker("Felix Allistar", &none),
+            AttackerKind::Player
+        );
+        assert_eq!(
+            classify_attacker("Gank Pilot[CODE](Thrasher)", &none),
+            AttackerKind::Player
+        );
+
+        // Overrides win over the heuristic
+        let overrides = HashMap::from([("Vila Warden".to_string(), AttackerKind::Player)]);
+        assert_eq!(
+            classify_attacker("Vila Warden", &overrides),
+            AttackerKind::Player
+        );
+    }
+
+    #[test]
+    fn test_format_number_styles() {
+        assert_eq!(format_number(1234.5, NumberFormat::Plain), "1234.5");
+        assert_eq!(format_number(1234.5, NumberFormat::Thousands), "1,234.5");
+        assert_eq!(
+            format_number(1_250_000.0, NumberFormat::Thousands),
+            "1,250,000.0"
+        );
+        assert_eq!(format_number(-999.0, NumberFormat::Thousands), "-999.0");
+        assert_eq!(format_number(1_250_000.0, NumberFormat::Suffixed), "1.25M");
+        assert_eq!(format_number(4_500.0, NumberFormat::Suffixed), "4.5k");
+        assert_eq!(format_number(999_999.0, NumberFormat::Suffixed), "1M");
+        assert_eq!(format_number(42.25, NumberFormat::Suffixed), "42.2");
+    }
+
+    #[test]
+    fn test_environmental_damage_gets_its_own_bucket() {
+        let hit = |source: &str, amount: f32| CombatEvent {
+            character: "Pilot".to_string(),
+            ..make_event(1, amount, true, source, "Pilot")
+        };
+        let events = vec![
+            hit("Starving Damavik", 100.0),
+            hit("Unstable Abyssal Depths", 40.0),
+        ];
+        let mut sample = last_sample(&events);
+
+        split_environmental(&mut sample, &default_environmental_sources());
+        split_incoming_by_attacker(&mut sample, &HashMap::new());
+
+        assert_eq!(sample.incoming_environmental_dps, 40.0);
+        assert!(!sample
+            .incoming_by_source
+            .contains_key("Unstable Abyssal Depths"));
+        assert_eq!(sample.incoming_by_source["Starving Damavik"], 100.0);
+        assert_eq!(sample.incoming_npc_dps, 100.0);
+        // Still real damage taken
+        assert_eq!(sample.incoming_dps, 140.0);
+    }
+
+    #[test]
+    fn aliased_characters_fold_into_one_pilot() {
+        let hit = |character: &str, amount: f32| CombatEvent {
+            weapon: "Small Focused Beam Laser II".to_string(),
+            ..make_event(0, amount, false, character, "Starving Damavik")
+        };
+        let events = vec![
+            hit("Old Name", 100.0),
+            hit("New Name", 50.0),
+            hit("Wingman", 30.0),
+        ];
+        let mut sample = last_sample(&events);
+
+        let aliases = HashMap::from([
+            ("Old Name".to_string(), "Pilot".to_string()),
+            ("New Name".to_string(), "Pilot".to_string()),
+        ]);
+        fold_character_aliases(&mut sample, &aliases);
+
+        assert_eq!(sample.outgoing_by_character.len(), 2);
+        assert_eq!(sample.outgoing_by_character["Pilot"], 150.0);
+        assert_eq!(sample.outgoing_by_character["Wingman"], 30.0);
+        let actions = &sample.combat_actions_by_character["Pilot"];
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].value, 150.0);
+        assert_eq!(
+            sample.outgoing_by_char_weapon["Pilot"]["Small Focused Beam Laser II"],
+            150.0
+        );
+        // The whole-fleet totals are unchanged
+        assert_eq!(sample.outgoing_dps, 180.0);
+    }
+}
@@ -1,11 +1,17 @@
 use chrono::NaiveDateTime;
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
-use super::model::{CombatEvent, EventType, NotifyEvent};
+use super::model::{
+    CombatEvent, DamageLayer, EventType, EwarKind, HitQuality, KillEvent, NotifyEvent,
+};
 
 const SESSION_PREFIX: &str = "Session Started:";
 const TIMESTAMP_FMT: &str = "%Y.%m.%d %H:%M:%S";
+/// Channel markers EVE uses for module activation failures
+const NOTIFY_MARKERS: [&str; 2] = ["(notify)", "(None)"];
 
 lazy_static! {
     static ref TAG_RE: Regex = Regex::new(r"<[^>]+>").unwrap();
@@ -13,15 +19,124 @@ lazy_static! {
     static ref CAP_FAIL_RE: Regex = Regex::new(
         r"^(.+?) requires ([\d.]+) units of charge\. The capacitor has only ([\d.]+) units\.$"
     ).unwrap();
+    // Pattern: "The ModuleName requires X.X units of charge but only Y.Y remain"
+    static ref CAP_FAIL_REMAIN_RE: Regex = Regex::new(
+        r"^(?:The )?(.+?) requires ([\d.]+) units of charge but only ([\d.]+) remains?\.?$"
+    ).unwrap();
+    // Older layouts: "1,204 from ..." with thousands separators
+    static ref LOOSE_AMOUNT_RE: Regex = Regex::new(r"^(\d[\d,]*(?:\.\d+)?)\s*(\S.*)$").unwrap();
+    // Field separators with a space on only one side ("Angel Viper -Heavy Pulse Laser II")
+    static ref LOOSE_DASH_RE: Regex = Regex::new(r"\s+-\s*|\s*-\s+").unwrap();
+    // Pattern: "Starving Damavik has been destroyed." or "You have destroyed Starving Damavik"
+    static ref KILL_RE: Regex = Regex::new(
+        r"(?i)^(?:you have destroyed (.+?)|(.+?) (?:has been|was) destroyed)[.!]?$"
+    ).unwrap();
+    // Pattern: "Warp scramble attempt from Starving Damavik to you!"
+    static ref EWAR_RE: Regex = Regex::new(
+        r"(?i)^(.+?) attempt from (.+?) to (.+?)!?$"
+    ).unwrap();
+}
+
+/// EWAR attempt phrases (lowercase) and what they mean
+const EWAR_PHRASES: &[(&str, EwarKind)] = &[
+    ("warp scramble", EwarKind::WarpScramble),
+    ("warp disruption", EwarKind::WarpDisruption),
+    ("stasis webification", EwarKind::StasisWeb),
+    ("stasis webifier", EwarKind::StasisWeb),
+    ("target painting", EwarKind::TargetPainter),
+    ("target painter", EwarKind::TargetPainter),
+    ("tracking disruption", EwarKind::TrackingDisruptor),
+    ("tracking disruptor", EwarKind::TrackingDisruptor),
+];
+
+/// Client-language tokens of damage lines. Repair, capacitor, neut and EWAR
+/// phrasing is still matched in English.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocaleConfig {
+    /// Channel marker of combat lines, "(combat)" in English
+    pub combat_marker: String,
+    /// Words between amount and target on outgoing damage ("to", "against")
+    pub outgoing: Vec<String>,
+    /// Words between amount and attacker on incoming damage ("from")
+    pub incoming: Vec<String>,
+}
+
+impl Default for LocaleConfig {
+    fn default() -> Self {
+        Self {
+            combat_marker: "(combat)".to_string(),
+            outgoing: vec!["to".to_string(), "against".to_string()],
+            incoming: vec!["from".to_string()],
+        }
+    }
+}
+
+impl LocaleConfig {
+    /// The direction words lowercased, the way lines are compared against them
+    fn normalized(mut self) -> Self {
+        for token in self.outgoing.iter_mut().chain(self.incoming.iter_mut()) {
+            *token = token.to_lowercase();
+        }
+        self
+    }
+
+    /// Whether `lower_body` has any of `tokens` as a separate word
+    fn has_token(tokens: &[String], lower_body: &str) -> bool {
+        tokens
+            .iter()
+            .any(|token| lower_body.contains(&format!(" {} ", token)))
+    }
+
+    /// `tokens` as entity prefixes ("to ")
+    fn prefixes(tokens: &[String]) -> Vec<String> {
+        tokens.iter().map(|token| format!("{} ", token)).collect()
+    }
 }
 
 pub struct LineParser {
     base_time: Option<NaiveDateTime>,
+    /// Canonical name of the log's character; replaces the `source` passed to
+    /// `parse_line` and any "You" in the log
+    listener: Option<String>,
+    /// Whether an event before `base_time` has already been reported
+    warned_before_base: bool,
+    locale: LocaleConfig,
 }
 
 impl LineParser {
     pub fn new() -> Self {
-        Self { base_time: None }
+        Self {
+            base_time: None,
+            listener: None,
+            warned_before_base: false,
+            locale: LocaleConfig::default(),
+        }
+    }
+
+    /// A parser for one character's log that always reports that character by
+    /// `name`, so multibox aggregation doesn't depend on how the log spells it.
+    pub fn new_with_listener(name: impl Into<String>) -> Self {
+        Self {
+            base_time: None,
+            listener: Some(name.into()),
+            warned_before_base: false,
+            locale: LocaleConfig::default(),
+        }
+    }
+
+    /// Read combat lines written by a client in another language.
+    pub fn with_locale(mut self, locale: LocaleConfig) -> Self {
+        self.set_locale(locale);
+        self
+    }
+
+    pub fn set_locale(&mut self, locale: LocaleConfig) {
+        self.locale = locale.normalized();
+    }
+
+    pub fn locale(&self) -> &LocaleConfig {
+        &self.locale
     }
 
     pub fn get_base_time(&self) -> Option<NaiveDateTime> {
@@ -37,6 +152,8 @@ impl Default for LineParser {
 
 impl LineParser {
     pub fn parse_line(&mut self, line: &str, source: &str) -> Option<CombatEvent> {
+        let listener = self.listener.clone();
+        let source = listener.as_deref().unwrap_or(source);
         let trimmed = line.trim();
         if trimmed.is_empty() {
             return None;
@@ -47,14 +164,51 @@ impl LineParser {
             return None;
         }
 
-        if !trimmed.contains("(combat)") {
+        if !trimmed.contains(self.locale.combat_marker.as_str()) {
             return None;
         }
 
         let timestamp = extract_timestamp(trimmed)?;
-        let body = extract_body(trimmed);
+        let body = extract_body(trimmed, &self.locale.combat_marker);
         let cleaned_body = strip_tags(&body);
-        let lower = cleaned_body.to_ascii_lowercase();
+
+        // EWAR lines have no amount, so they take their own path
+        if let Some((kind, source_entity, target_entity, incoming)) =
+            parse_ewar(&cleaned_body, source)
+        {
+            let duration = self.relative_time(timestamp);
+            return Some(CombatEvent {
+                timestamp: duration,
+                source: resolve_you(source_entity, source),
+                target: resolve_you(target_entity, source),
+                weapon: kind.label().to_string(),
+                amount: 0.0,
+                incoming,
+                character: source.to_string(),
+                event_type: EventType::Ewar { kind },
+                hit_quality: None,
+                via_drone: false,
+                self_inflicted: false,
+                damage_layer: None,
+            });
+        }
+
+        if let Some(event) = self.parse_amount_line(timestamp, &body, &cleaned_body, source) {
+            return Some(event);
+        }
+        // Older clients laid the same fields out with different tags
+        self.parse_loose_damage(timestamp, &body, source)
+    }
+
+    /// Parse a line of the current layout: amount, direction word, entity, " - weapon".
+    fn parse_amount_line(
+        &mut self,
+        timestamp: NaiveDateTime,
+        body: &str,
+        cleaned_body: &str,
+        source: &str,
+    ) -> Option<CombatEvent> {
+        let lower = cleaned_body.to_lowercase();
 
         // 1. Identify Event Type
         let event_type = if lower.contains("repaired to")
@@ -72,10 +226,10 @@ impl LineParser {
         };
 
         // 2. Identify Direction (pass raw body for color-based neut detection)
-        let direction = determine_direction(&lower, &body, &event_type)?;
+        let direction = determine_direction(&lower, body, &event_type, &self.locale)?;
 
         // 3. Extract Amount
-        let (mut amount, remainder) = split_amount_body(&cleaned_body)?;
+        let (mut amount, remainder) = split_amount_body(cleaned_body)?;
 
         // Handle "+4 GJ" or "-6 GJ" for drains
         // split_amount_body parses the float. If it was negative, amount is negative.
@@ -87,14 +241,72 @@ impl LineParser {
         // If we want "Neut Pressure", we probably want absolute value.
         amount = amount.abs();
 
+        self.build_event(timestamp, source, event_type, direction, amount, remainder)
+    }
+
+    /// Fallback for damage lines whose tags sit directly against the fields
+    /// ("<b>146</b><font>to</font>") or use one-sided dashes and thousands
+    /// separators. The fields are re-spaced into the current layout.
+    fn parse_loose_damage(
+        &mut self,
+        timestamp: NaiveDateTime,
+        body: &str,
+        source: &str,
+    ) -> Option<CombatEvent> {
+        let spaced = strip_tags_spaced(body);
+        let caps = LOOSE_AMOUNT_RE.captures(&spaced)?;
+        let amount: f32 = caps.get(1)?.as_str().replace(',', "").parse().ok()?;
+        let rest = caps.get(2)?.as_str();
+
+        let lower_rest = rest.to_lowercase();
+        let starts_with_token = |tokens: &[String]| {
+            tokens.iter().find(|token| {
+                lower_rest
+                    .strip_prefix(token.as_str())
+                    .is_some_and(|after| after.starts_with(' '))
+            })
+        };
+        let direction = if starts_with_token(&self.locale.outgoing).is_some() {
+            Direction::Outgoing
+        } else if starts_with_token(&self.locale.incoming).is_some() {
+            Direction::Incoming
+        } else {
+            return None;
+        };
+
+        let remainder = LOOSE_DASH_RE.replace_all(rest, " - ");
+        self.build_event(
+            timestamp,
+            source,
+            EventType::Damage,
+            direction,
+            amount,
+            &remainder,
+        )
+    }
+
+    /// The event for an amount line once its type, direction and amount are known.
+    fn build_event(
+        &mut self,
+        timestamp: NaiveDateTime,
+        source: &str,
+        event_type: EventType,
+        direction: Direction,
+        amount: f32,
+        remainder: &str,
+    ) -> Option<CombatEvent> {
         // 4. Extract Entities
-        let (source_entity, target_entity, weapon) =
-            split_entities_and_weapon(remainder, direction, &event_type, source)?;
+        let (source_entity, target_entity, weapon, hit_quality, damage_layer) =
+            split_entities_and_weapon(remainder, direction, &event_type, source, &self.locale)?;
 
-        self.ensure_base_time(timestamp);
+        let duration = self.relative_time(timestamp);
+        let via_drone = event_type == EventType::Damage && is_drone_weapon(&weapon);
 
-        let base = *self.base_time.as_ref()?;
-        let duration = timestamp.signed_duration_since(base).to_std().ok()?;
+        let source_entity = resolve_you(source_entity, source);
+        let target_entity = resolve_you(target_entity, source);
+        // Splash from our own smartbombs/Vortons lands on us: count it only as
+        // damage taken, never as damage dealt
+        let self_inflicted = event_type == EventType::Damage && source_entity == target_entity;
 
         Some(CombatEvent {
             timestamp: duration,
@@ -102,9 +314,14 @@ impl LineParser {
             target: target_entity,
             weapon,
             amount,
-            incoming: matches!(direction, Direction::Incoming),
+            incoming: self_inflicted || matches!(direction, Direction::Incoming),
             character: source.to_string(),
             event_type,
+            hit_quality,
+            via_drone,
+            self_inflicted,
+            // Only meaningful for our own tank
+            damage_layer: damage_layer.filter(|_| matches!(direction, Direction::Incoming)),
         })
     }
 
@@ -124,11 +341,45 @@ impl LineParser {
         }
     }
 
-    /// Parse a (notify) line for capacitor failure events.
-    /// Example: [ 2025.12.22 02:38:08 ] (notify) Gistii A-Type Small Remote Shield Booster requires 39.0 units of charge. The capacitor has only 6.2 units.
+    /// Time of `timestamp` relative to the log's base time. Events stamped before
+    /// the base (clock skew, a stale or future session header) are clamped to zero
+    /// instead of dropped.
+    fn relative_time(&mut self, timestamp: NaiveDateTime) -> Duration {
+        self.ensure_base_time(timestamp);
+        let base = self.base_time.unwrap_or(timestamp);
+        match timestamp.signed_duration_since(base).to_std() {
+            Ok(duration) => duration,
+            Err(_) => {
+                if !self.warned_before_base {
+                    self.warned_before_base = true;
+                    println!(
+                        "Log event at {} predates session start {}; clamping to 0",
+                        timestamp, base
+                    );
+                }
+                Duration::ZERO
+            }
+        }
+    }
+
+    /// Whether a combat line is a miss ("... misses Starving Damavik completely",
+    /// "Rat misses you completely"). Misses carry no damage, so `parse_line`
+    /// deliberately skips them. Matched in English, like repair phrasing.
+    pub fn is_miss_line(&self, line: &str) -> bool {
+        let marker = self.locale.combat_marker.as_str();
+        line.contains(marker)
+            && strip_tags_spaced(&extract_body(line, marker))
+                .to_lowercase()
+                .contains(" misses ")
+    }
+
+    /// Parse a (notify) or (None) line for capacitor failure events.
+    /// Examples:
+    /// [ 2025.12.22 02:38:08 ] (notify) Gistii A-Type Small Remote Shield Booster requires 39.0 units of charge. The capacitor has only 6.2 units.
+    /// [ 2025.12.22 02:38:08 ] (None) The Afterburner requires 10.0 units of charge but only 2.0 remain
     pub fn parse_notify_line(&mut self, line: &str, source: &str) -> Option<NotifyEvent> {
         let trimmed = line.trim();
-        if trimmed.is_empty() || !trimmed.contains("(notify)") {
+        if trimmed.is_empty() {
             return None;
         }
 
@@ -138,29 +389,25 @@ impl LineParser {
             return None;
         }
 
+        let marker = NOTIFY_MARKERS.into_iter().find(|m| trimmed.contains(m))?;
         let timestamp = extract_timestamp(trimmed)?;
 
-        // Extract body after (notify)
-        let body = trimmed.split("(notify)").nth(1).map(str::trim)?;
+        // Extract body after the channel marker
+        let body = trimmed.split(marker).nth(1).map(str::trim)?;
 
         // Clean HTML tags from body
         let cleaned_body = strip_tags(body);
 
-        println!("[DEBUG] parse_notify_line: cleaned_body='{}'", cleaned_body);
-
-        // Try to match capacitor failure pattern
-        let caps = CAP_FAIL_RE.captures(&cleaned_body);
-        println!("[DEBUG] CAP_FAIL_RE matched: {}", caps.is_some());
-        let caps = caps?;
+        // Try both known capacitor failure phrasings
+        let caps = CAP_FAIL_RE
+            .captures(&cleaned_body)
+            .or_else(|| CAP_FAIL_REMAIN_RE.captures(&cleaned_body))?;
 
         let module_name = caps.get(1)?.as_str().to_string();
         let required_cap: f32 = caps.get(2)?.as_str().parse().ok()?;
         let available_cap: f32 = caps.get(3)?.as_str().parse().ok()?;
 
-        self.ensure_base_time(timestamp);
-
-        let base = *self.base_time.as_ref()?;
-        let duration = timestamp.signed_duration_since(base).to_std().ok()?;
+        let duration = self.relative_time(timestamp);
 
         Some(NotifyEvent {
             timestamp: duration,
@@ -170,10 +417,87 @@ impl LineParser {
             available_cap,
         })
     }
+
+    /// Parse a (notify) or (None) line reporting a ship's destruction. Losing our
+    /// own ship ("Your ship has been destroyed") isn't a kill.
+    pub fn parse_kill_line(&mut self, line: &str, source: &str) -> Option<KillEvent> {
+        let trimmed = line.trim();
+        if trimmed.starts_with(SESSION_PREFIX) {
+            self.parse_session_start(trimmed);
+            return None;
+        }
+
+        let marker = NOTIFY_MARKERS.into_iter().find(|m| trimmed.contains(m))?;
+        let timestamp = extract_timestamp(trimmed)?;
+        let body = strip_tags(trimmed.split(marker).nth(1)?.trim());
+
+        let caps = KILL_RE.captures(&body)?;
+        let victim = caps.get(1).or_else(|| caps.get(2))?.as_str().trim();
+        let lower = victim.to_ascii_lowercase();
+        if victim.is_empty() || lower == "you" || lower.starts_with("your ") {
+            return None;
+        }
+
+        Some(KillEvent {
+            time: self.relative_time(timestamp),
+            character: source.to_string(),
+            victim: victim.to_string(),
+        })
+    }
+}
+
+/// Write an event back out as a plain (tag-free) gamelog combat line stamped
+/// `time`, such that `LineParser::parse_line` reads the same event back for the
+/// event's character. Sub-second precision is lost, as in real logs.
+pub fn format_combat_line(event: &CombatEvent, time: NaiveDateTime) -> String {
+    let stamp = time.format(TIMESTAMP_FMT);
+    if let EventType::Ewar { kind } = event.event_type {
+        let phrase = EWAR_PHRASES
+            .iter()
+            .find(|(_, k)| *k == kind)
+            .map_or("warp scramble", |(text, _)| text);
+        let (from, to) = if event.incoming {
+            (event.source.as_str(), "you")
+        } else {
+            ("you", event.target.as_str())
+        };
+        return format!(
+            "[ {} ] (combat) {} attempt from {} to {}!",
+            stamp, phrase, from, to
+        );
+    }
+
+    let (verb, entity) = match (&event.event_type, event.incoming) {
+        (EventType::Damage, false) => ("to", &event.target),
+        (EventType::Damage, true) => ("from", &event.source),
+        (EventType::Repair, false) => ("repaired to", &event.target),
+        (EventType::Repair, true) => ("repaired by", &event.source),
+        (EventType::Capacitor, false) => ("remote capacitor transmitted to", &event.target),
+        (EventType::Capacitor, true) => ("remote capacitor transmitted by", &event.source),
+        (EventType::Neut, false) => ("GJ energy neutralized", &event.target),
+        (EventType::Neut, true) => ("GJ energy neutralized by", &event.source),
+        (EventType::Ewar { .. }, _) => unreachable!("handled above"),
+    };
+    let mut line = format!(
+        "[ {} ] (combat) {} {} {}",
+        stamp, event.amount, verb, entity
+    );
+    // A missing weapon is parsed back as the entity, so there's no need to write it
+    if !event.weapon.is_empty() && event.weapon != *entity {
+        line.push_str(" - ");
+        line.push_str(&event.weapon);
+    }
+    if let Some(quality) = event.hit_quality {
+        line.push_str(&format!(" - {:?}", quality));
+    }
+    if let Some(layer) = event.damage_layer {
+        line.push_str(&format!(" - {:?}", layer));
+    }
+    line
 }
 
-fn extract_body(line: &str) -> String {
-    line.split("(combat)")
+fn extract_body(line: &str, marker: &str) -> String {
+    line.split(marker)
         .nth(1)
         .map(str::trim)
         .unwrap_or_default()
@@ -190,12 +514,13 @@ fn determine_direction(
     lower_body: &str,
     raw_body: &str,
     event_type: &EventType,
+    locale: &LocaleConfig,
 ) -> Option<Direction> {
     match event_type {
         EventType::Damage => {
-            if lower_body.contains(" to ") {
+            if LocaleConfig::has_token(&locale.outgoing, lower_body) {
                 Some(Direction::Outgoing)
-            } else if lower_body.contains(" from ") {
+            } else if LocaleConfig::has_token(&locale.incoming, lower_body) {
                 Some(Direction::Incoming)
             } else {
                 None
@@ -219,6 +544,7 @@ fn determine_direction(
                 None
             }
         }
+        EventType::Ewar { .. } => None,
         EventType::Neut => {
             // EVE uses same text for incoming/outgoing neuts, but colors differ:
             // 0xffe57f7f = incoming (reddish) - being neuted
@@ -255,8 +581,17 @@ fn extract_timestamp(line: &str) -> Option<NaiveDateTime> {
 }
 
 fn strip_tags(value: &str) -> String {
-    let cleaned = TAG_RE.replace_all(value, "");
-    cleaned
+    collapse_whitespace(&TAG_RE.replace_all(value, ""))
+}
+
+/// Like `strip_tags`, but every tag becomes a space so fields that older
+/// clients wrote without whitespace between their tags stay apart.
+fn strip_tags_spaced(value: &str) -> String {
+    collapse_whitespace(&TAG_RE.replace_all(value, " "))
+}
+
+fn collapse_whitespace(value: &str) -> String {
+    value
         .replace("&nbsp;", " ")
         .replace(['\r', '\n'], " ")
         .split_whitespace()
@@ -264,6 +599,36 @@ fn strip_tags(value: &str) -> String {
         .join(" ")
 }
 
+/// The log's own character where the log just says "You".
+fn resolve_you(name: String, listener: &str) -> String {
+    if name.eq_ignore_ascii_case("you") {
+        listener.to_string()
+    } else {
+        name
+    }
+}
+
+/// Parse "<EWAR> attempt from <source> to <target>!" into (kind, source, target, incoming).
+/// "you" on either side is replaced by the listener.
+fn parse_ewar(body: &str, listener: &str) -> Option<(EwarKind, String, String, bool)> {
+    let caps = EWAR_RE.captures(body)?;
+    let phrase = caps.get(1)?.as_str().to_lowercase();
+    let kind = EWAR_PHRASES
+        .iter()
+        .find(|(text, _)| phrase.contains(text))
+        .map(|(_, kind)| *kind)?;
+
+    let source = caps.get(2)?.as_str().trim();
+    let target = caps.get(3)?.as_str().trim();
+    if target.eq_ignore_ascii_case("you") {
+        Some((kind, source.to_string(), listener.to_string(), true))
+    } else if source.eq_ignore_ascii_case("you") {
+        Some((kind, listener.to_string(), target.to_string(), false))
+    } else {
+        None
+    }
+}
+
 fn split_amount_body(body: &str) -> Option<(f32, &str)> {
     // "127 to..." or "+4 GJ..." or "-6 GJ..."
     let trimmed = body.trim();
@@ -290,24 +655,69 @@ fn split_amount_body(body: &str) -> Option<(f32, &str)> {
     Some((amount, clean_remainder))
 }
 
+const GROUP_PREFIX: &str = "Your group of ";
+
+/// Drone hull names; any size/faction variant ("Hobgoblin II", "'Augmented'
+/// Hammerhead", "Imperial Navy Acolyte") contains one of these words.
+const DRONE_NAMES: &[&str] = &[
+    "acolyte",
+    "infiltrator",
+    "curator",
+    "praetor",
+    "hornet",
+    "vespa",
+    "wasp",
+    "valkyrie",
+    "hobgoblin",
+    "hammerhead",
+    "ogre",
+    "warrior",
+    "berserker",
+    "bouncer",
+    "garde",
+    "warden",
+    "gecko",
+];
+
+/// Whether a weapon name is a combat drone
+pub fn is_drone_weapon(weapon: &str) -> bool {
+    weapon
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| DRONE_NAMES.contains(&word))
+}
+
+/// `text` without a leading `prefix`, compared case-insensitively. Compares char
+/// by char so the cut stays on a boundary of `text` even where lowercasing
+/// changes byte lengths ('İ').
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let mut expected = prefix.chars().flat_map(char::to_lowercase).peekable();
+    for (idx, c) in text.char_indices() {
+        if expected.peek().is_none() {
+            return Some(&text[idx..]);
+        }
+        for lower in c.to_lowercase() {
+            if expected.next() != Some(lower) {
+                return None;
+            }
+        }
+    }
+    expected.peek().is_none().then_some("")
+}
+
 fn split_entities_and_weapon(
     remainder: &str,
     direction: Direction,
     event_type: &EventType,
     listener: &str,
-) -> Option<(String, String, String)> {
-    // Quality suffixes for damage
-    let qualities = [
-        "hits",
-        "misses",
-        "grazes",
-        "glances",
-        "scratches",
-        "penetrates",
-        "smashes",
-        "wrecks",
-    ];
-
+    locale: &LocaleConfig,
+) -> Option<(
+    String,
+    String,
+    String,
+    Option<HitQuality>,
+    Option<DamageLayer>,
+)> {
     let mut parts: Vec<&str> = remainder
         .split(" - ")
         .map(|s| s.trim())
@@ -320,17 +730,21 @@ fn split_entities_and_weapon(
     // 3. Everything remaining is the Entity (joined back with " - ")
 
     let mut weapon = String::new();
+    let mut hit_quality = None;
+    let mut damage_layer = None;
 
     match event_type {
         EventType::Damage => {
+            // The layer segment ("- Armor") may sit on either side of the quality
+            damage_layer = pop_damage_layer(&mut parts);
             // Check if rightmost is a quality word
-            if parts
-                .last()
-                .map(|s| qualities.iter().any(|q| s.to_lowercase().contains(q)))
-                .unwrap_or(false)
-            {
+            hit_quality = parts.last().and_then(|s| HitQuality::from_suffix(s));
+            if hit_quality.is_some() {
                 parts.pop(); // Remove quality
             }
+            if damage_layer.is_none() {
+                damage_layer = pop_damage_layer(&mut parts);
+            }
             // Weapon is now the rightmost (if more than 1 part remains)
             if parts.len() > 1 {
                 weapon = parts
@@ -354,15 +768,27 @@ fn split_entities_and_weapon(
         }
     }
 
+    // Grouped weapons show up as "Your group of Light Missile Launcher II";
+    // aggregate them under the plain weapon name
+    if let Some(prefix) = weapon.get(..GROUP_PREFIX.len()) {
+        if prefix.eq_ignore_ascii_case(GROUP_PREFIX) {
+            weapon = weapon[GROUP_PREFIX.len()..].trim().to_string();
+        }
+    }
+
     // Remaining parts form the Entity text (joined back with " - " to preserve dashes in names)
     let text_part = parts.join(" - ");
+    let damage_prefixes = LocaleConfig::prefixes(match direction {
+        Direction::Outgoing => &locale.outgoing,
+        Direction::Incoming => &locale.incoming,
+    });
 
     let entity_name: String;
 
     match direction {
         Direction::Outgoing => {
             let prefixes = match event_type {
-                EventType::Damage => vec!["to ", "against "],
+                EventType::Damage => damage_prefixes.iter().map(String::as_str).collect(),
                 EventType::Repair => vec![
                     "remote armor repaired to ",
                     "remote shield repaired to ",
@@ -380,6 +806,7 @@ fn split_entities_and_weapon(
                     "boosted to ",
                 ],
                 EventType::Capacitor => vec!["remote capacitor transmitted to "],
+                EventType::Ewar { .. } => vec![],
                 EventType::Neut => vec![
                     "energy neutralized ",
                     "energy drained from ",
@@ -388,19 +815,16 @@ fn split_entities_and_weapon(
             };
 
             let text = text_part.trim();
-            let lower_text = text.to_lowercase();
-            let mut result = text.to_string();
-            for prefix in prefixes {
-                if lower_text.starts_with(&prefix.to_lowercase()) {
-                    result = text[prefix.len()..].trim().to_string();
-                    break;
-                }
-            }
-            entity_name = result;
+            entity_name = prefixes
+                .iter()
+                .find_map(|prefix| strip_prefix_ignore_case(text, prefix))
+                .unwrap_or(text)
+                .trim()
+                .to_string();
         }
         Direction::Incoming => {
             let prefixes = match event_type {
-                EventType::Damage => vec!["from "],
+                EventType::Damage => damage_prefixes.iter().map(String::as_str).collect(),
                 EventType::Repair => vec![
                     "remote armor repaired by ",
                     "remote shield repaired by ",
@@ -419,18 +843,16 @@ fn split_entities_and_weapon(
                 ],
                 EventType::Capacitor => vec!["remote capacitor transmitted by "],
                 EventType::Neut => vec!["energy neutralized by ", "energy drained by "],
+                EventType::Ewar { .. } => vec![],
             };
 
             let text = text_part.trim();
-            let lower_text = text.to_lowercase();
-            let mut result = text.to_string();
-            for prefix in prefixes {
-                if lower_text.starts_with(&prefix.to_lowercase()) {
-                    result = text[prefix.len()..].trim().to_string();
-                    break;
-                }
-            }
-            entity_name = result;
+            entity_name = prefixes
+                .iter()
+                .find_map(|prefix| strip_prefix_ignore_case(text, prefix))
+                .unwrap_or(text)
+                .trim()
+                .to_string();
         }
     }
 
@@ -446,9 +868,31 @@ fn split_entities_and_weapon(
     }
 
     match direction {
-        Direction::Outgoing => Some((listener.to_string(), entity, weapon)),
-        Direction::Incoming => Some((entity, listener.to_string(), weapon)),
+        Direction::Outgoing => Some((
+            listener.to_string(),
+            entity,
+            weapon,
+            hit_quality,
+            damage_layer,
+        )),
+        Direction::Incoming => Some((
+            entity,
+            listener.to_string(),
+            weapon,
+            hit_quality,
+            damage_layer,
+        )),
+    }
+}
+
+/// Remove a trailing tank-layer segment, never the entity itself.
+fn pop_damage_layer(parts: &mut Vec<&str>) -> Option<DamageLayer> {
+    if parts.len() < 2 {
+        return None;
     }
+    let layer = parts.last().and_then(|s| DamageLayer::from_segment(s))?;
+    parts.pop();
+    Some(layer)
 }
 
 #[cfg(test)]
@@ -629,6 +1073,7 @@ mod tests {
         assert_eq!(event.target, "Habitation Module - Breeding Facility");
         assert_eq!(event.weapon, "Small Vorton Projector II");
         assert!(!event.incoming);
+        assert_eq!(event.hit_quality, Some(HitQuality::Hits));
     }
 
     #[test]
@@ -645,6 +1090,7 @@ mod tests {
         assert_eq!(event.amount, 265.0);
         assert_eq!(event.target, "Habitation Module - Breeding Facility");
         assert_eq!(event.weapon, "Small Vorton Projector II");
+        assert_eq!(event.hit_quality, None);
     }
 
     #[test]
@@ -681,6 +1127,29 @@ mod tests {
         assert_eq!(event.available_cap, 0.7);
     }
 
+    #[test]
+    fn parses_none_channel_cap_failure() {
+        let mut parser = LineParser::new();
+        let _ = parser.parse_line("Session Started: 2025.12.22 02:38:00", "TestPilot");
+
+        let line = "[ 2025.12.22 02:38:12 ] (None) The Afterburner requires 10.0 units of charge but only 2.0 remain";
+        let event = parser
+            .parse_notify_line(line, "TestPilot")
+            .expect("should parse (None) cap failure");
+
+        assert_eq!(event.module_name, "Afterburner");
+        assert_eq!(event.required_cap, 10.0);
+        assert_eq!(event.available_cap, 2.0);
+        assert_eq!(event.timestamp.as_secs(), 12);
+    }
+
+    #[test]
+    fn ignores_unrelated_none_lines() {
+        let mut parser = LineParser::new();
+        let line = "[ 2025.12.22 02:38:12 ] (None) Jumping from Jita to Perimeter";
+        assert!(parser.parse_notify_line(line, "TestPilot").is_none());
+    }
+
     #[test]
     fn ignores_non_notify_lines() {
         let mut parser = LineParser::new();
@@ -692,4 +1161,313 @@ mod tests {
         // Empty line should return None
         assert!(parser.parse_notify_line("", "Test").is_none());
     }
+
+    #[test]
+    fn strips_group_of_prefix_from_weapon() {
+        let mut parser = LineParser::new();
+        let _ = parser.parse_line("Session Started: 2026.01.03 19:20:00", "TestPilot");
+
+        let line = "[ 2026.01.03 19:20:00 ] (combat) 50 to Starving Damavik - Your group of Light Missile Launcher II - Hits";
+        let event = parser
+            .parse_line(line, "TestPilot")
+            .expect("should parse grouped weapon hit");
+
+        assert_eq!(event.amount, 50.0);
+        assert_eq!(event.target, "Starving Damavik");
+        assert_eq!(event.weapon, "Light Missile Launcher II");
+        assert_eq!(event.hit_quality, Some(HitQuality::Hits));
+    }
+
+    #[test]
+    fn ignores_group_of_miss_lines() {
+        let mut parser = LineParser::new();
+        let _ = parser.parse_line("Session Started: 2026.01.03 19:20:00", "TestPilot");
+
+        // Misses carry no damage number, so they must not become damage events
+        let line = "[ 2026.01.03 19:20:01 ] (combat) Your group of Light Missile Launcher II misses Starving Damavik completely - Light Missile Launcher II";
+        assert!(parser.parse_line(line, "TestPilot").is_none());
+        assert!(parser.is_miss_line(line));
+
+        let line = "[ 2012.07.14 19:32:06 ] (combat) <b>Angel Viper</b><font size=10>misses you completely</font>";
+        assert!(parser.is_miss_line(line));
+        let line = "[ 2026.01.03 19:20:02 ] (combat) 50 to Starving Damavik - Light Missile Launcher II - Hits";
+        assert!(!parser.is_miss_line(line));
+    }
+
+    #[test]
+    fn tags_drone_damage() {
+        let mut parser = LineParser::new();
+        let _ = parser.parse_line("Session Started: 2026.01.03 19:20:00", "TestPilot");
+
+        let line = "[ 2026.01.03 19:20:00 ] (combat) <color=0xff00ffff><b>48</b> <color=0x77ffffff><font size=10>to</font> <b><color=0xffffffff>Starving Damavik</b><font size=10><color=0x77ffffff> - Hobgoblin II - Hits";
+        let event = parser
+            .parse_line(line, "TestPilot")
+            .expect("should parse drone hit");
+        assert_eq!(event.weapon, "Hobgoblin II");
+        assert_eq!(event.character, "TestPilot");
+        assert!(!event.incoming);
+        assert!(event.via_drone);
+
+        let line = "[ 2026.01.03 19:20:01 ] (combat) 523 to Starving Damavik - Small Focused Beam Laser II - Penetrates";
+        let event = parser.parse_line(line, "TestPilot").unwrap();
+        assert!(!event.via_drone);
+    }
+
+    #[test]
+    fn parses_warp_scramble_attempt() {
+        let mut parser = LineParser::new();
+        let _ = parser.parse_line("Session Started: 2026.01.03 19:20:00", "TestPilot");
+
+        let line = "[ 2026.01.03 19:20:05 ] (combat) <color=0xffffffff><b>Warp scramble attempt</b> <color=0x77ffffff><font size=10>from</font> <color=0xffffffff><b>Starving Damavik</b> <color=0x77ffffff><font size=10>to <b><color=0xffffffff></font>you!";
+        let event = parser
+            .parse_line(line, "TestPilot")
+            .expect("should parse warp scramble");
+
+        assert_eq!(
+            event.event_type,
+            EventType::Ewar {
+                kind: EwarKind::WarpScramble
+            }
+        );
+        assert!(event.incoming);
+        assert_eq!(event.source, "Starving Damavik");
+        assert_eq!(event.target, "TestPilot");
+        assert_eq!(event.amount, 0.0);
+    }
+
+    #[test]
+    fn parses_stasis_webifier_attempt() {
+        let mut parser = LineParser::new();
+        let _ = parser.parse_line("Session Started: 2026.01.03 19:20:00", "TestPilot");
+
+        let line = "[ 2026.01.03 19:20:06 ] (combat) <color=0xffffffff><b>Stasis webification attempt</b> <color=0x77ffffff><font size=10>from</font> <color=0xffffffff><b>Blinding Leshak</b> <color=0x77ffffff><font size=10>to <b><color=0xffffffff></font>you!";
+        let event = parser
+            .parse_line(line, "TestPilot")
+            .expect("should parse stasis web");
+
+        assert_eq!(
+            event.event_type,
+            EventType::Ewar {
+                kind: EwarKind::StasisWeb
+            }
+        );
+        assert!(event.incoming);
+        assert_eq!(event.source, "Blinding Leshak");
+        assert_eq!(event.weapon, "Stasis Web");
+    }
+
+    #[test]
+    fn listener_name_replaces_you() {
+        let mut parser = LineParser::new_with_listener("Felix Allistar");
+        let _ = parser.parse_line("Session Started: 2025.11.15 07:09:22", "You");
+
+        let line = "[ 2025.11.15 07:14:31 ] (combat) 523 to Starving Damavik - Small Focused Beam Laser II - Penetrates";
+        let event = parser.parse_line(line, "You").expect("should parse");
+        assert_eq!(event.source, "Felix Allistar");
+        assert_eq!(event.character, "Felix Allistar");
+
+        let line = "[ 2025.11.15 07:14:32 ] (combat) 80 from Starving Damavik - Hits";
+        let event = parser.parse_line(line, "You").expect("should parse");
+        assert!(event.incoming);
+        assert_eq!(event.target, "Felix Allistar");
+
+        // Self-inflicted damage names the listener on both sides
+        let line = "[ 2025.11.15 07:14:33 ] (combat) 10 from You - Vorton Projector II - Hits";
+        let event = parser.parse_line(line, "You").expect("should parse");
+        assert_eq!(event.source, "Felix Allistar");
+        assert_eq!(event.target, "Felix Allistar");
+    }
+
+    #[test]
+    fn parses_against_phrasing_as_outgoing() {
+        let mut parser = LineParser::new();
+        let line = "[ 2025.11.15 07:14:31 ] (combat) 312 against Striking Damavik - Small Focused Beam Laser II - Hits";
+        let event = parser.parse_line(line, "Pilot").expect("should parse");
+        assert!(!event.incoming);
+        assert!(!event.self_inflicted);
+        assert_eq!(event.source, "Pilot");
+        assert_eq!(event.target, "Striking Damavik");
+        assert_eq!(event.weapon, "Small Focused Beam Laser II");
+        assert_eq!(event.amount, 312.0);
+    }
+
+    #[test]
+    fn self_damage_counts_as_incoming_only() {
+        let mut parser = LineParser::new_with_listener("Felix Allistar");
+
+        for line in [
+            "[ 2025.11.15 07:14:33 ] (combat) 10 from You - Vorton Projector II - Hits",
+            "[ 2025.11.15 07:14:34 ] (combat) 45 to You - Small Proton Smartbomb II - Hits",
+        ] {
+            let event = parser.parse_line(line, "You").expect("should parse");
+            assert!(event.self_inflicted, "{}", line);
+            assert!(event.incoming, "{}", line);
+        }
+
+        let event = parser
+            .parse_line(
+                "[ 2025.11.15 07:14:35 ] (combat) 80 from Starving Damavik - Hits",
+                "You",
+            )
+            .unwrap();
+        assert!(!event.self_inflicted);
+    }
+
+    #[test]
+    fn tags_incoming_armor_layer_hit() {
+        let mut parser = LineParser::new();
+        let line = "[ 2025.11.15 07:14:31 ] (combat) 120 from Striking Damavik - Light Entropic Disintegrator - Hits - Armor";
+        let event = parser.parse_line(line, "Pilot").expect("should parse");
+        assert!(event.incoming);
+        assert_eq!(event.damage_layer, Some(DamageLayer::Armor));
+        assert_eq!(event.source, "Striking Damavik");
+        assert_eq!(event.weapon, "Light Entropic Disintegrator");
+        assert_eq!(event.hit_quality, Some(HitQuality::Hits));
+    }
+
+    #[test]
+    fn tags_incoming_hull_layer_hit() {
+        let mut parser = LineParser::new();
+        // Layer before the quality, no weapon segment
+        let line = "[ 2025.11.15 07:14:32 ] (combat) 80 from Starving Damavik - (hull) - Smashes";
+        let event = parser.parse_line(line, "Pilot").expect("should parse");
+        assert!(event.incoming);
+        assert_eq!(event.damage_layer, Some(DamageLayer::Hull));
+        assert_eq!(event.source, "Starving Damavik");
+        assert_eq!(event.hit_quality, Some(HitQuality::Smashes));
+
+        // Lines without a layer are unchanged
+        let line = "[ 2025.11.15 07:14:33 ] (combat) 80 from Starving Damavik - Hits";
+        let event = parser.parse_line(line, "Pilot").unwrap();
+        assert_eq!(event.damage_layer, None);
+    }
+
+    #[test]
+    fn event_before_session_header_is_clamped_to_zero() {
+        let mut parser = LineParser::new();
+        let _ = parser.parse_line("Session Started: 2025.11.15 07:20:00", "Pilot");
+
+        let line = "[ 2025.11.15 07:14:31 ] (combat) 523 to Starving Damavik - Small Focused Beam Laser II - Penetrates";
+        let event = parser
+            .parse_line(line, "Pilot")
+            .expect("event should be kept");
+        assert_eq!(event.timestamp, Duration::ZERO);
+        assert_eq!(event.amount, 523.0);
+
+        // Later events are still relative to the header
+        let line = "[ 2025.11.15 07:20:05 ] (combat) 80 from Starving Damavik - Hits";
+        let event = parser.parse_line(line, "Pilot").unwrap();
+        assert_eq!(event.timestamp, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn parses_older_tag_layouts() {
+        let mut parser = LineParser::new_with_listener("Pilot");
+        let _ = parser.parse_line("Session Started: 2012.07.14 19:30:00", "Pilot");
+
+        // Tags directly against the amount and direction word
+        let line = "[ 2012.07.14 19:32:05 ] (combat) <color=0xff00ffff><b>146</b><color=0x77ffffff><font size=10>to</font><b><color=0xffffffff>Angel Viper</b><font size=10><color=0x77ffffff> - Hobgoblin I - Hits</font>";
+        let event = parser.parse_line(line, "Pilot").expect("should parse");
+        assert_eq!(event.amount, 146.0);
+        assert!(!event.incoming);
+        assert_eq!(event.source, "Pilot");
+        assert_eq!(event.target, "Angel Viper");
+        assert_eq!(event.weapon, "Hobgoblin I");
+        assert!(event.via_drone);
+        assert_eq!(event.hit_quality, Some(HitQuality::Hits));
+        assert_eq!(event.timestamp, Duration::from_secs(125));
+
+        // Thousands separator and dashes inside their own tags
+        let line = "[ 2013.02.20 21:10:44 ] (combat) <color=0xffcc0000><b>1,204</b> <color=0x77ffffff><font size=10>from</font> <b><color=0xffffffff>Sansha's Berserker</b><font size=10><color=0x77ffffff>-Heavy Pulse Laser II</font><font size=10>-Smashes</font>";
+        let event = parser.parse_line(line, "Pilot").expect("should parse");
+        assert_eq!(event.amount, 1204.0);
+        assert!(event.incoming);
+        assert_eq!(event.source, "Sansha's Berserker");
+        assert_eq!(event.target, "Pilot");
+        assert_eq!(event.weapon, "Heavy Pulse Laser II");
+        assert_eq!(event.hit_quality, Some(HitQuality::Smashes));
+
+        // Misses still carry no damage
+        let line = "[ 2012.07.14 19:32:06 ] (combat) <b>Angel Viper</b><font size=10>misses you completely</font>";
+        assert!(parser.parse_line(line, "Pilot").is_none());
+    }
+
+    #[test]
+    fn parses_destruction_line_as_kill() {
+        let mut parser = LineParser::new();
+        let _ = parser.parse_kill_line("Session Started: 2025.11.15 07:09:22", "Pilot");
+
+        let line = "[ 2025.11.15 07:09:52 ] (notify) <color=0xffffffff>Starving Damavik</color> has been destroyed.";
+        let kill = parser.parse_kill_line(line, "Pilot").expect("should parse");
+        assert_eq!(kill.victim, "Starving Damavik");
+        assert_eq!(kill.character, "Pilot");
+        assert_eq!(kill.time, Duration::from_secs(30));
+
+        let line = "[ 2025.11.15 07:10:00 ] (notify) You have destroyed Lucid Deepwatcher";
+        assert_eq!(
+            parser.parse_kill_line(line, "Pilot").unwrap().victim,
+            "Lucid Deepwatcher"
+        );
+
+        // Our own loss, and combat lines, are not kills
+        let line = "[ 2025.11.15 07:10:05 ] (notify) Your ship has been destroyed.";
+        assert!(parser.parse_kill_line(line, "Pilot").is_none());
+        let line = "[ 2025.11.15 07:10:06 ] (combat) 523 to Starving Damavik - Small Focused Beam Laser II - Wrecks";
+        assert!(parser.parse_kill_line(line, "Pilot").is_none());
+    }
+
+    #[test]
+    fn parses_damage_lines_with_german_tokens() {
+        let german = LocaleConfig {
+            combat_marker: "(Kampf)".to_string(),
+            outgoing: vec!["An".to_string(), "gegen".to_string()],
+            incoming: vec!["von".to_string()],
+        };
+        let mut parser = LineParser::new_with_listener("Pilot").with_locale(german);
+        let _ = parser.parse_line("Session Started: 2025.11.15 07:09:22", "Pilot");
+
+        let line = "[ 2025.11.15 07:14:31 ] (Kampf) <color=0xff00ffff><b>523</b> <color=0x77ffffff><font size=10>an</font> <b><color=0xffffffff>Starving Damavik</b><font size=10><color=0x77ffffff> - Small Focused Beam Laser II";
+        let event = parser.parse_line(line, "Pilot").expect("should parse");
+        assert!(!event.incoming);
+        assert_eq!(event.amount, 523.0);
+        assert_eq!(event.target, "Starving Damavik");
+        assert_eq!(event.weapon, "Small Focused Beam Laser II");
+
+        let line = "[ 2025.11.15 07:14:33 ] (Kampf) <color=0xffcc0000><b>77</b> <color=0x77ffffff><font size=10>von</font> <b><color=0xffffffff>Starving Damavik</b>";
+        let event = parser.parse_line(line, "Pilot").expect("should parse");
+        assert!(event.incoming);
+        assert_eq!(event.amount, 77.0);
+        assert_eq!(event.source, "Starving Damavik");
+
+        // English lines aren't read by a German parser
+        let line = "[ 2025.11.15 07:14:35 ] (combat) 100 to Starving Damavik - Gun";
+        assert!(parser.parse_line(line, "Pilot").is_none());
+    }
+
+    #[test]
+    fn strips_non_ascii_locale_tokens() {
+        // 'İ' lowercases to two chars, so lowercased text is longer than the original
+        let locale = LocaleConfig {
+            combat_marker: "(savaş)".to_string(),
+            outgoing: vec!["İLE".to_string()],
+            incoming: vec!["İçin".to_string()],
+        };
+        let mut parser = LineParser::new_with_listener("Pilot").with_locale(locale);
+        let _ = parser.parse_line("Session Started: 2025.11.15 07:09:22", "Pilot");
+
+        let line = "[ 2025.11.15 07:14:31 ] (savaş) 523 İLE Starving Damavik - Small Focused Beam Laser II";
+        let event = parser.parse_line(line, "Pilot").expect("should parse");
+        assert!(!event.incoming);
+        assert_eq!(event.target, "Starving Damavik");
+        assert_eq!(event.weapon, "Small Focused Beam Laser II");
+
+        let line = "[ 2025.11.15 07:14:33 ] (savaş) 77 İçin Ağır Damavik";
+        let event = parser.parse_line(line, "Pilot").expect("should parse");
+        assert!(event.incoming);
+        assert_eq!(event.source, "Ağır Damavik");
+
+        assert_eq!(strip_prefix_ignore_case("İLE x", "i̇le "), Some("x"));
+        assert_eq!(strip_prefix_ignore_case("Ab", "abc"), None);
+        assert_eq!(strip_prefix_ignore_case("ÄB", "äb"), Some(""));
+    }
 }
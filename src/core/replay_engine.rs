@@ -1,37 +1,146 @@
-use super::model::CombatEvent;
-use super::parser::LineParser;
+use super::analysis;
+use super::inline_bookmarks::{self, BookmarkType};
+use super::model::{CombatEvent, DpsSample, RoomStats};
+use super::parser::{self, LineParser, LocaleConfig};
+use super::state::EngineState;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
 use std::fs::File;
-use std::io::{self, BufRead, BufReader};
-use std::path::PathBuf;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
 pub struct MergedStream {
     sources: Vec<LogSource>,
 }
 
+/// An inline bookmark crossed during replay, positioned on the sim timeline.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplayBookmark {
+    pub time: Duration,
+    /// Absolute UTC time since the Unix epoch, as written, plus any clock correction
+    pub absolute: Duration,
+    pub kind: BookmarkType,
+    pub label: Option<String>,
+}
+
+/// Per-room stats of `events` from the replay's ROOM_START/ROOM_END bookmarks,
+/// see `analysis::segment_by_rooms`.
+pub fn room_stats(
+    events: &[CombatEvent],
+    bookmarks: &[ReplayBookmark],
+    window: Duration,
+) -> Vec<RoomStats> {
+    let bookmarks: Vec<(Duration, BookmarkType)> = bookmarks
+        .iter()
+        .map(|bookmark| (bookmark.time, bookmark.kind))
+        .collect();
+    analysis::segment_by_rooms(events, &bookmarks, window)
+}
+
 struct LogSource {
     reader: BufReader<File>,
     character: String,
     next_event: Option<(CombatEvent, String)>,
     parser: LineParser,
+    /// Bookmarks read while looking ahead for the next event
+    pending_bookmarks: Vec<ReplayBookmark>,
+    /// Signed clock correction (ms) applied to this source's timestamps
+    offset_ms: i64,
+    /// Offset (ms) of the current file's base time from the first file in the chain
+    file_offset_ms: i64,
+    /// Later files of the same character's session chain, with their offsets
+    remaining_files: VecDeque<(PathBuf, i64)>,
+    /// Base time (epoch secs) of the first file in the chain
+    base_epoch: Option<i64>,
+    locale: LocaleConfig,
+}
+
+impl LogSource {
+    /// Open a chain of one character's log files, replayed back to back on the
+    /// timeline of the first file.
+    fn open(character: String, files: Vec<PathBuf>, locale: &LocaleConfig) -> io::Result<Self> {
+        let base_epoch = files
+            .first()
+            .and_then(|path| file_base_time(path, locale))
+            .map(|base| base.and_utc().timestamp());
+        let offsets = chain_offsets(&files, locale);
+        let mut files: VecDeque<(PathBuf, i64)> = files.into_iter().zip(offsets).collect();
+
+        let (path, file_offset_ms) = files
+            .pop_front()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "empty log chain"))?;
+
+        let mut source = Self {
+            reader: BufReader::new(File::open(path)?),
+            parser: LineParser::new_with_listener(&character).with_locale(locale.clone()),
+            character,
+            next_event: None,
+            pending_bookmarks: Vec::new(),
+            offset_ms: 0,
+            file_offset_ms,
+            remaining_files: files,
+            base_epoch,
+            locale: locale.clone(),
+        };
+        source.advance();
+        Ok(source)
+    }
+
+    /// Read the next event into `next_event`, rolling over to the next chained file at EOF.
+    fn advance(&mut self) {
+        loop {
+            self.next_event = read_next_event(
+                &mut self.reader,
+                &mut self.parser,
+                &self.character,
+                &mut self.pending_bookmarks,
+                self.file_offset_ms,
+                self.offset_ms,
+            );
+            if self.next_event.is_some() {
+                return;
+            }
+
+            let Some((path, file_offset_ms)) = self.remaining_files.pop_front() else {
+                return;
+            };
+            // A chained file that vanished is skipped rather than ending the whole source
+            if let Ok(file) = File::open(&path) {
+                self.reader = BufReader::new(file);
+                self.parser =
+                    LineParser::new_with_listener(&self.character).with_locale(self.locale.clone());
+                self.file_offset_ms = file_offset_ms;
+            }
+        }
+    }
 }
 
 impl MergedStream {
     pub fn new(paths: Vec<(String, PathBuf)>) -> io::Result<Self> {
+        Self::new_chained(
+            paths
+                .into_iter()
+                .map(|(character, path)| (character, vec![path]))
+                .collect(),
+        )
+    }
+
+    /// Like `new`, but each character may contribute a chain of consecutive files
+    /// (e.g. split by daily downtime) that are read back to back.
+    pub fn new_chained(chains: Vec<(String, Vec<PathBuf>)>) -> io::Result<Self> {
+        Self::new_chained_localized(chains, &LocaleConfig::default())
+    }
+
+    /// Like `new_chained`, for logs of a client in another language.
+    pub fn new_chained_localized(
+        chains: Vec<(String, Vec<PathBuf>)>,
+        locale: &LocaleConfig,
+    ) -> io::Result<Self> {
         let mut sources = Vec::new();
-        for (character, path) in paths {
-            let file = File::open(path)?;
-            let mut reader = BufReader::new(file);
-            let mut parser = LineParser::new();
-
-            let next_event = read_next_event(&mut reader, &mut parser, &character);
-
-            sources.push(LogSource {
-                reader,
-                character,
-                next_event,
-                parser,
-            });
+        for (character, files) in chains {
+            sources.push(LogSource::open(character, files, locale)?);
         }
         Ok(Self { sources })
     }
@@ -52,8 +161,7 @@ impl MergedStream {
         if let Some(idx) = earliest_idx {
             let source = &mut self.sources[idx];
             let result = source.next_event.take();
-            source.next_event =
-                read_next_event(&mut source.reader, &mut source.parser, &source.character);
+            source.advance();
             result
         } else {
             None
@@ -66,26 +174,143 @@ impl MergedStream {
             .filter_map(|s| s.next_event.as_ref().map(|(e, _)| e.timestamp))
             .min()
     }
+
+    /// Shift all timestamps of a character's source by `offset_ms` (signed) to correct clock drift.
+    /// Replaces any previously set offset for that character.
+    pub fn set_source_offset(&mut self, character: &str, offset_ms: i64) {
+        for source in self.sources.iter_mut().filter(|s| s.character == character) {
+            let delta = offset_ms - source.offset_ms;
+            if let Some((event, _)) = source.next_event.as_mut() {
+                event.timestamp = shift_time(event.timestamp, delta);
+            }
+            for bookmark in &mut source.pending_bookmarks {
+                bookmark.time = shift_time(bookmark.time, delta);
+                bookmark.absolute = shift_time(bookmark.absolute, delta);
+            }
+            source.offset_ms = offset_ms;
+        }
+    }
+
+    /// Remove and return all bookmarks at or before the absolute UTC time `absolute`
+    /// (since the Unix epoch), in chronological order.
+    pub fn take_bookmarks_until(&mut self, absolute: Duration) -> Vec<ReplayBookmark> {
+        let mut bookmarks = Vec::new();
+        for source in &mut self.sources {
+            let (ready, pending): (Vec<_>, Vec<_>) = source
+                .pending_bookmarks
+                .drain(..)
+                .partition(|bm| bm.absolute <= absolute);
+            bookmarks.extend(ready);
+            source.pending_bookmarks = pending;
+        }
+        bookmarks.sort_by_key(|bm| bm.absolute);
+        bookmarks
+    }
 }
 
 fn read_next_event(
     reader: &mut BufReader<File>,
     parser: &mut LineParser,
     character: &str,
+    bookmarks: &mut Vec<ReplayBookmark>,
+    file_offset_ms: i64,
+    clock_offset_ms: i64,
 ) -> Option<(CombatEvent, String)> {
+    let offset_ms = file_offset_ms + clock_offset_ms;
     let mut line = String::new();
     while reader.read_line(&mut line).ok()? > 0 {
         let trimmed = line.trim();
         if !trimmed.is_empty() {
-            if let Some(event) = parser.parse_line(trimmed, character) {
+            if let Some(mut event) = parser.parse_line(trimmed, character) {
+                event.timestamp = shift_time(event.timestamp, offset_ms);
                 return Some((event, trimmed.to_string()));
             }
+            if let Some(mut bookmark) = parse_replay_bookmark(trimmed, parser) {
+                // The file offset only realigns relative times; absolute times already agree
+                bookmark.time = shift_time(bookmark.time, offset_ms);
+                bookmark.absolute = shift_time(bookmark.absolute, clock_offset_ms);
+                bookmarks.push(bookmark);
+            }
         }
         line.clear();
     }
     None
 }
 
+/// The time a log's events are measured from: its Session Started header,
+/// or the first event if there is none.
+fn file_base_time(path: &Path, locale: &LocaleConfig) -> Option<NaiveDateTime> {
+    let reader = BufReader::new(File::open(path).ok()?);
+    let mut parser = LineParser::new().with_locale(locale.clone());
+    for line in reader.lines().map_while(Result::ok) {
+        parser.parse_line(line.trim(), "");
+        if let Some(base) = parser.get_base_time() {
+            return Some(base);
+        }
+    }
+    None
+}
+
+/// Offset (ms) of each file's base time from the first file's, so a chain of
+/// files can share one timeline. Files without a base time get no offset.
+fn chain_offsets(files: &[PathBuf], locale: &LocaleConfig) -> Vec<i64> {
+    let bases: Vec<Option<NaiveDateTime>> =
+        files.iter().map(|p| file_base_time(p, locale)).collect();
+    let first = bases.first().copied().flatten();
+    bases
+        .into_iter()
+        .map(|base| match (first, base) {
+            (Some(first), Some(base)) => (base - first).num_milliseconds(),
+            _ => 0,
+        })
+        .collect()
+}
+
+/// Apply a signed millisecond offset to a timestamp, clamping at zero.
+fn shift_time(time: Duration, offset_ms: i64) -> Duration {
+    let delta = Duration::from_millis(offset_ms.unsigned_abs());
+    if offset_ms >= 0 {
+        time + delta
+    } else {
+        time.saturating_sub(delta)
+    }
+}
+
+/// Collect the sim times of all RUN_START bookmarks in a log file.
+fn scan_run_starts(path: &Path, locale: &LocaleConfig) -> io::Result<Vec<Duration>> {
+    let mut parser = LineParser::new().with_locale(locale.clone());
+    let mut starts = Vec::new();
+    for line in super::log_io::read_full_lines(path)? {
+        let trimmed = line.trim();
+        // Parse combat lines too so the base time matches the replay stream
+        if parser.parse_line(trimmed, "").is_some() {
+            continue;
+        }
+        if let Some(bookmark) = parse_replay_bookmark(trimmed, &parser) {
+            if bookmark.kind == BookmarkType::RunStart {
+                starts.push(bookmark.time);
+            }
+        }
+    }
+    Ok(starts)
+}
+
+/// Parse a bookmark line and place it on the same relative timeline as the parser's events.
+fn parse_replay_bookmark(line: &str, parser: &LineParser) -> Option<ReplayBookmark> {
+    let bookmark = inline_bookmarks::parse_bookmark_line(line)?;
+    let base_secs = parser.get_base_time()?.and_utc().timestamp();
+    let offset_secs = (bookmark.timestamp_secs as i64).checked_sub(base_secs)?;
+    Some(ReplayBookmark {
+        time: Duration::from_secs(u64::try_from(offset_secs).ok()?),
+        absolute: Duration::from_secs(bookmark.timestamp_secs),
+        kind: bookmark.bookmark_type,
+        label: bookmark.label,
+    })
+}
+
+/// Default quiet period after which replay treats the next event as a new run
+const DEFAULT_RUN_GAP: Duration = Duration::from_secs(120);
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PlaybackState {
     Playing,
@@ -93,7 +318,7 @@ pub enum PlaybackState {
 }
 
 pub struct ReplayController {
-    stream_paths: Vec<(String, PathBuf)>,
+    stream_paths: Vec<(String, Vec<PathBuf>)>,
     stream: MergedStream,
     state: PlaybackState,
     speed: f64,
@@ -104,17 +329,70 @@ pub struct ReplayController {
 
     current_sim_time: Duration,
     last_update_wall_time: SystemTime,
+
+    /// Optional (start, end) offsets relative to session start to loop playback within
+    loop_range: Option<(Duration, Duration)>,
+    /// Set when playback wrapped back to the loop start; callers must reset their engine
+    loop_wrapped: bool,
+
+    /// Sim times of every RUN_START bookmark across all sources, sorted, with the
+    /// clock corrections applied
+    run_starts: Vec<Duration>,
+    /// RUN_START times per character before clock corrections
+    source_run_starts: Vec<(String, Duration)>,
+
+    /// Per-character clock corrections (ms), re-applied whenever the stream is rebuilt
+    source_offsets: HashMap<String, i64>,
+
+    /// A gap this long without events also starts a new run; `None` disables it
+    run_gap: Option<Duration>,
+    /// Timestamp of the last event returned by `tick`
+    last_event_time: Option<Duration>,
+    /// Index into the last `tick`'s events where a new run began
+    run_boundary: Option<usize>,
+
+    /// Every event of the session in merged order, read once up front so the
+    /// scrubber can compute a sample at any time without playing forward
+    all_events: Vec<CombatEvent>,
+
+    /// Combat tokens of the logs' client language, used whenever the stream is rebuilt
+    locale: LocaleConfig,
 }
 
 impl ReplayController {
     pub fn new(paths: Vec<(String, PathBuf)>) -> Option<Self> {
-        let stream = MergedStream::new(paths.clone()).ok()?;
+        Self::new_localized(paths, LocaleConfig::default())
+    }
+
+    /// Like `new`, for logs of a client in another language.
+    pub fn new_localized(paths: Vec<(String, PathBuf)>, locale: LocaleConfig) -> Option<Self> {
+        Self::new_chained_localized(
+            paths
+                .into_iter()
+                .map(|(character, path)| (character, vec![path]))
+                .collect(),
+            locale,
+        )
+    }
+
+    /// Replay where each character may contribute a chain of consecutive log files
+    /// (see `log_io::session_chain`), played back as one continuous timeline.
+    pub fn new_chained(chains: Vec<(String, Vec<PathBuf>)>) -> Option<Self> {
+        Self::new_chained_localized(chains, LocaleConfig::default())
+    }
+
+    /// Like `new_chained`, for logs of a client in another language.
+    pub fn new_chained_localized(
+        chains: Vec<(String, Vec<PathBuf>)>,
+        locale: LocaleConfig,
+    ) -> Option<Self> {
+        let stream = MergedStream::new_chained_localized(chains.clone(), &locale).ok()?;
 
         // Calculate absolute epoch start (earliest session start)
         let mut min_epoch = u64::MAX;
         for source in &stream.sources {
-            if let Some(base) = source.parser.get_base_time() {
-                let epoch = base.and_utc().timestamp() as u64;
+            if let Some(epoch) = source.base_epoch {
+                let epoch = epoch as u64;
                 if epoch < min_epoch {
                     min_epoch = epoch;
                 }
@@ -131,37 +409,269 @@ impl ReplayController {
         // Try to peek first event time, OR default to 0 duration if no events
         let start_time = stream.peek_time().unwrap_or(Duration::ZERO);
 
-        let mut end_time = start_time;
-        for (_, path) in &paths {
-            if let Ok(events) = super::log_io::read_full_events(path) {
-                if let Some(last) = events.last() {
-                    if last.timestamp > end_time {
-                        end_time = last.timestamp;
-                    }
+        let mut source_run_starts = Vec::new();
+        for (character, files) in &chains {
+            for (path, file_offset_ms) in files.iter().zip(chain_offsets(files, &locale)) {
+                if let Ok(starts) = scan_run_starts(path, &locale) {
+                    source_run_starts.extend(
+                        starts
+                            .into_iter()
+                            .map(|start| (character.clone(), shift_time(start, file_offset_ms))),
+                    );
                 }
             }
         }
 
-        Some(Self {
-            stream_paths: paths,
+        let mut controller = Self {
+            stream_paths: chains,
             stream,
             state: PlaybackState::Paused,
             speed: 1.0,
             session_start_time: start_time,
-            session_duration: end_time.saturating_sub(start_time),
+            session_duration: Duration::ZERO,
             session_epoch_start: min_epoch,
             current_sim_time: start_time,
             last_update_wall_time: SystemTime::now(),
-        })
+            loop_range: None,
+            loop_wrapped: false,
+            run_starts: Vec::new(),
+            source_run_starts,
+            source_offsets: HashMap::new(),
+            run_gap: Some(DEFAULT_RUN_GAP),
+            last_event_time: None,
+            run_boundary: None,
+            all_events: Vec::new(),
+            locale,
+        };
+        controller.rebuild_run_starts();
+        controller.all_events = controller.collect_all_events().ok()?;
+        let end_time = controller
+            .all_events
+            .iter()
+            .map(|event| event.timestamp)
+            .max()
+            .unwrap_or(start_time);
+        controller.session_duration = end_time.saturating_sub(start_time);
+        Some(controller)
+    }
+
+    /// Read the whole session through a fresh stream, with the current clock corrections.
+    fn collect_all_events(&self) -> io::Result<Vec<CombatEvent>> {
+        let mut stream =
+            MergedStream::new_chained_localized(self.stream_paths.clone(), &self.locale)?;
+        for (character, offset_ms) in &self.source_offsets {
+            stream.set_source_offset(character, *offset_ms);
+        }
+        let mut events = Vec::new();
+        while let Some((event, _)) = stream.next_event() {
+            events.push(event);
+        }
+        Ok(events)
+    }
+
+    /// Write the merged session back out as normalized gamelogs in `dir`, one per
+    /// character since a gamelog has a single listener. Lines are re-rendered
+    /// with canonical whole-second timestamps (clock corrections applied), so
+    /// replaying the exported files yields the same events. Returns the files written.
+    pub fn export_merged(&self, dir: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut stream =
+            MergedStream::new_chained_localized(self.stream_paths.clone(), &self.locale)?;
+        for (character, offset_ms) in &self.source_offsets {
+            stream.set_source_offset(character, *offset_ms);
+        }
+        let bases: HashMap<String, NaiveDateTime> = stream
+            .sources
+            .iter()
+            .map(|source| {
+                let epoch = source.base_epoch.unwrap_or(self.session_epoch_start as i64);
+                let base = chrono::DateTime::from_timestamp(epoch, 0)
+                    .unwrap_or_default()
+                    .naive_utc();
+                (source.character.clone(), base)
+            })
+            .collect();
+
+        std::fs::create_dir_all(dir)?;
+        let mut writers: HashMap<String, BufWriter<File>> = HashMap::new();
+        let mut written = Vec::new();
+        while let Some((event, _)) = stream.next_event() {
+            let Some(base) = bases.get(&event.character).copied() else {
+                continue;
+            };
+            if !writers.contains_key(&event.character) {
+                let path = dir.join(format!(
+                    "{}_{}.txt",
+                    base.format("%Y%m%d_%H%M%S"),
+                    writers.len() + 1
+                ));
+                let mut writer = BufWriter::new(File::create(&path)?);
+                writeln!(
+                    writer,
+                    "------------------------------------------------------------"
+                )?;
+                writeln!(writer, "  Gamelog")?;
+                writeln!(writer, "  Listener: {}", event.character)?;
+                writeln!(
+                    writer,
+                    "  Session Started: {}",
+                    base.format("%Y.%m.%d %H:%M:%S")
+                )?;
+                writeln!(
+                    writer,
+                    "------------------------------------------------------------"
+                )?;
+                writers.insert(event.character.clone(), writer);
+                written.push(path);
+            }
+            let time = base + chrono::Duration::seconds(event.timestamp.as_secs() as i64);
+            let writer = writers.get_mut(&event.character).unwrap();
+            writeln!(writer, "{}", parser::format_combat_line(&event, time))?;
+        }
+        for writer in writers.values_mut() {
+            writer.flush()?;
+        }
+        Ok(written)
+    }
+
+    /// The DPS sample as of `offset` from the session start, without moving the
+    /// playhead. `None` before any combat.
+    pub fn snapshot_at(&self, offset: Duration, window: Duration) -> Option<DpsSample> {
+        let time = self.session_start_time + offset;
+        let end = self.all_events.partition_point(|e| e.timestamp <= time);
+        analysis::compute_dps_series(&self.all_events[..end], window, time).pop()
     }
 
     pub fn seek(&mut self, offset: Duration) -> io::Result<()> {
-        self.stream = MergedStream::new(self.stream_paths.clone())?;
+        self.stream = MergedStream::new_chained_localized(self.stream_paths.clone(), &self.locale)?;
+        for (character, offset_ms) in &self.source_offsets {
+            self.stream.set_source_offset(character, *offset_ms);
+        }
         self.current_sim_time = self.session_start_time + offset;
         self.last_update_wall_time = SystemTime::now();
+        self.last_event_time = None;
+        self.run_boundary = None;
         Ok(())
     }
 
+    /// Seek to the next RUN_START after the current position, or the session end if none.
+    /// Returns the new offset relative to the session start.
+    pub fn seek_next_run(&mut self) -> io::Result<Duration> {
+        let offset = self
+            .run_starts
+            .iter()
+            .find(|t| **t > self.current_sim_time)
+            .map_or(self.session_duration, |t| {
+                t.saturating_sub(self.session_start_time)
+            });
+        self.seek(offset)?;
+        Ok(offset)
+    }
+
+    /// Seek to the last RUN_START before the current position, or the session start if none.
+    /// Returns the new offset relative to the session start.
+    pub fn seek_prev_run(&mut self) -> io::Result<Duration> {
+        let offset = self
+            .run_starts
+            .iter()
+            .rev()
+            .find(|t| **t < self.current_sim_time)
+            .map_or(Duration::ZERO, |t| {
+                t.saturating_sub(self.session_start_time)
+            });
+        self.seek(offset)?;
+        Ok(offset)
+    }
+
+    /// Recompute `run_starts` from the per-character times and clock corrections.
+    fn rebuild_run_starts(&mut self) {
+        let mut run_starts: Vec<Duration> = self
+            .source_run_starts
+            .iter()
+            .map(|(character, start)| {
+                shift_time(
+                    *start,
+                    self.source_offsets.get(character).copied().unwrap_or(0),
+                )
+            })
+            .collect();
+        run_starts.sort();
+        run_starts.dedup();
+        self.run_starts = run_starts;
+    }
+
+    pub fn run_starts(&self) -> &[Duration] {
+        &self.run_starts
+    }
+
+    /// Loop playback within `(start, end)` offsets relative to the session start.
+    /// Empty or inverted ranges disable looping. The end is clamped to the session duration.
+    pub fn set_loop(&mut self, range: Option<(Duration, Duration)>) {
+        self.loop_range = range
+            .map(|(start, end)| (start, end.min(self.session_duration)))
+            .filter(|(start, end)| start < end);
+    }
+
+    pub fn loop_range(&self) -> Option<(Duration, Duration)> {
+        self.loop_range
+    }
+
+    /// Treat a gap of at least `gap` between events as a run boundary, like a
+    /// RUN_START bookmark. `None` only uses the bookmarks.
+    pub fn set_run_gap(&mut self, gap: Option<Duration>) {
+        self.run_gap = gap;
+    }
+
+    /// Returns once, after a `tick`, the index into that tick's events where a new
+    /// run started. Callers should reset their engine before pushing `events[index..]`.
+    pub fn take_run_boundary(&mut self) -> Option<usize> {
+        self.run_boundary.take()
+    }
+
+    /// Whether an event at `next` starts a new run after an event at `last`.
+    fn is_run_boundary(&self, last: Duration, next: Duration) -> bool {
+        let gap = self
+            .run_gap
+            .is_some_and(|gap| next.saturating_sub(last) >= gap);
+        gap || self
+            .run_starts
+            .iter()
+            .any(|&start| last < start && start <= next)
+    }
+
+    /// Returns true once after playback wrapped back to the loop start.
+    pub fn take_loop_wrapped(&mut self) -> bool {
+        std::mem::take(&mut self.loop_wrapped)
+    }
+
+    /// Shift one character's events by a signed offset (ms) to align drifted client clocks.
+    pub fn set_source_offset(&mut self, character: &str, offset_ms: i64) {
+        self.stream.set_source_offset(character, offset_ms);
+        let previous = if offset_ms == 0 {
+            self.source_offsets.remove(character)
+        } else {
+            self.source_offsets.insert(character.to_string(), offset_ms)
+        };
+
+        // Move the cached events by the change rather than re-reading the session
+        let delta = offset_ms - previous.unwrap_or(0);
+        if delta != 0 {
+            for event in self
+                .all_events
+                .iter_mut()
+                .filter(|e| e.character == character)
+            {
+                event.timestamp = shift_time(event.timestamp, delta);
+            }
+            self.all_events.sort_by_key(|event| event.timestamp);
+            self.rebuild_run_starts();
+        }
+    }
+
+    /// Every event of the session, with the current clock corrections
+    pub fn all_events(&self) -> &[CombatEvent] {
+        &self.all_events
+    }
+
     pub fn session_duration(&self) -> Duration {
         self.session_duration
     }
@@ -185,12 +695,25 @@ impl ReplayController {
         self.speed = speed;
     }
 
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Carry the playback settings (play/pause, speed, run gap) of the replay this
+    /// one replaces, so swapping the log set doesn't interrupt the user.
+    pub fn resume_from(&mut self, previous: &ReplayController) {
+        self.set_state(previous.state);
+        self.speed = previous.speed;
+        self.run_gap = previous.run_gap;
+    }
+
     pub fn step(&mut self, delta: Duration) {
         self.current_sim_time += delta;
         self.last_update_wall_time = SystemTime::now(); // Reset wall clock to prevent 'jump' if play resumed
     }
 
-    pub fn tick(&mut self) -> (Vec<CombatEvent>, Vec<String>) {
+    /// Advance playback, returning the events, raw lines and bookmarks crossed since the last tick.
+    pub fn tick(&mut self) -> (Vec<CombatEvent>, Vec<String>, Vec<ReplayBookmark>) {
         let now = SystemTime::now();
         let elapsed_wall = now
             .duration_since(self.last_update_wall_time)
@@ -198,17 +721,37 @@ impl ReplayController {
         self.last_update_wall_time = now;
 
         if self.state == PlaybackState::Paused {
-            return (Vec::new(), Vec::new());
+            return (Vec::new(), Vec::new(), Vec::new());
         }
 
         let elapsed_sim = Duration::from_secs_f64(elapsed_wall.as_secs_f64() * self.speed);
         self.current_sim_time += elapsed_sim;
 
+        // Wrap back to the loop start once the loop end is reached.
+        // Events are re-read from the start on the next tick, like a regular seek.
+        if let Some((loop_start, loop_end)) = self.loop_range {
+            if self.current_sim_time >= self.session_start_time + loop_end {
+                if let Err(e) = self.seek(loop_start) {
+                    eprintln!("ReplayController: Failed to wrap loop: {}", e);
+                    self.loop_range = None;
+                } else {
+                    self.loop_wrapped = true;
+                    return (Vec::new(), Vec::new(), Vec::new());
+                }
+            }
+        }
+
         let mut events = Vec::new();
         let mut lines = Vec::new();
         while let Some(next_time) = self.stream.peek_time() {
             if next_time <= self.current_sim_time {
                 if let Some((event, line)) = self.stream.next_event() {
+                    if let Some(last) = self.last_event_time {
+                        if self.is_run_boundary(last, event.timestamp) {
+                            self.run_boundary = Some(events.len());
+                        }
+                    }
+                    self.last_event_time = Some(event.timestamp);
                     events.push(event);
                     lines.push(line);
                 }
@@ -216,17 +759,77 @@ impl ReplayController {
                 break;
             }
         }
-        (events, lines)
+        // Bookmarks carry absolute UTC times; each source's relative timeline starts at its
+        // own base, so only absolute times line up across characters
+        let now_absolute = self.absolute_sim_time();
+        let bookmarks = self.stream.take_bookmarks_until(now_absolute);
+        (events, lines, bookmarks)
     }
 
     pub fn current_sim_time(&self) -> Duration {
         self.current_sim_time
     }
 
+    /// The playhead as absolute UTC time since the Unix epoch.
+    pub fn absolute_sim_time(&self) -> Duration {
+        Duration::from_secs(self.session_epoch_start) + self.current_sim_time
+    }
+
     pub fn relative_progress(&self) -> Duration {
         self.current_sim_time
             .saturating_sub(self.session_start_time)
     }
+
+    /// True once every event of every source has been played.
+    pub fn is_finished(&self) -> bool {
+        self.stream.peek_time().is_none()
+    }
+}
+
+/// Plays a gamelog into an `EngineState` at wall-clock pacing, the same way the live
+/// `Coordinator` feeds it, so the main overlay can be driven from a file.
+pub struct LiveSimulation {
+    controller: ReplayController,
+    engine: EngineState,
+    /// Last computed sample, repeated on ticks that produce none
+    last_sample: Option<DpsSample>,
+}
+
+impl LiveSimulation {
+    pub fn new(character: String, path: PathBuf, speed: f64, locale: LocaleConfig) -> Option<Self> {
+        let mut controller = ReplayController::new_localized(vec![(character, path)], locale)?;
+        controller.set_speed(speed);
+        controller.set_state(PlaybackState::Playing);
+        Some(Self {
+            controller,
+            engine: EngineState::new(),
+            last_sample: None,
+        })
+    }
+
+    /// Push the events due since the last tick and return them with the latest DPS sample.
+    pub fn tick(&mut self, dps_window: Duration) -> (Vec<CombatEvent>, Option<DpsSample>) {
+        let (events, _, _) = self.controller.tick();
+        for event in &events {
+            self.engine.push_event(event.clone());
+        }
+        let new_sample = self
+            .engine
+            .dps_tail(dps_window, self.controller.current_sim_time())
+            .pop();
+        if let Some(sample) = new_sample {
+            self.last_sample = Some(sample);
+        }
+        (events, self.last_sample.clone())
+    }
+
+    pub fn engine(&self) -> &EngineState {
+        &self.engine
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.controller.is_finished()
+    }
 }
 
 #[cfg(test)]
@@ -278,6 +881,89 @@ mod tests {
         assert!(stream.next_event().is_none());
     }
 
+    #[test]
+    fn test_chained_files_share_one_timeline() {
+        let dir = tempdir().unwrap();
+
+        let first = dir.path().join("first.txt");
+        let mut f = File::create(&first).unwrap();
+        writeln!(f, "Session Started: 2024.01.01 08:00:00").unwrap();
+        writeln!(f, "[ 2024.01.01 08:00:05 ] (combat) 10 to X - Gun").unwrap();
+
+        // Next file after downtime, one hour later
+        let second = dir.path().join("second.txt");
+        let mut f = File::create(&second).unwrap();
+        writeln!(f, "Session Started: 2024.01.01 09:00:00").unwrap();
+        writeln!(f, "[ 2024.01.01 09:00:10 ] (combat) 20 to X - Gun").unwrap();
+
+        let chains = vec![("A".to_string(), vec![first, second])];
+        let mut stream = MergedStream::new_chained(chains.clone()).unwrap();
+        assert_eq!(
+            stream.next_event().unwrap().0.timestamp,
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            stream.next_event().unwrap().0.timestamp,
+            Duration::from_secs(3610)
+        );
+        assert!(stream.next_event().is_none());
+
+        let ctrl = ReplayController::new_chained(chains).unwrap();
+        assert_eq!(ctrl.session_duration(), Duration::from_secs(3605));
+    }
+
+    #[test]
+    fn test_source_offset_reorders_merge() {
+        let dir = tempdir().unwrap();
+
+        let path_a = dir.path().join("A.txt");
+        let mut f_a = File::create(&path_a).unwrap();
+        writeln!(f_a, "Session Started: 2024.01.01 12:00:00").unwrap();
+        writeln!(f_a, "[ 2024.01.01 12:00:03 ] (combat) 10 to X - Gun").unwrap();
+
+        let path_b = dir.path().join("B.txt");
+        let mut f_b = File::create(&path_b).unwrap();
+        writeln!(f_b, "Session Started: 2024.01.01 12:00:00").unwrap();
+        writeln!(f_b, "[ 2024.01.01 12:00:01 ] (combat) 10 to X - Gun").unwrap();
+
+        let paths = vec![("CharA".to_string(), path_a), ("CharB".to_string(), path_b)];
+
+        // Without an offset, CharB's event comes first
+        let mut stream = MergedStream::new(paths.clone()).unwrap();
+        assert_eq!(stream.next_event().unwrap().0.character, "CharB");
+
+        // Shifting CharB by +5s moves its event after CharA's
+        let mut ctrl = ReplayController::new(paths).unwrap();
+        ctrl.set_source_offset("CharB", 5_000);
+        let first = ctrl.stream.next_event().unwrap().0;
+        assert_eq!(first.character, "CharA");
+        let second = ctrl.stream.next_event().unwrap().0;
+        assert_eq!(second.character, "CharB");
+        assert_eq!(second.timestamp, Duration::from_secs(6));
+
+        // The cached session follows the offset, in merged order
+        let cached: Vec<_> = ctrl
+            .all_events()
+            .iter()
+            .map(|e| (e.character.as_str(), e.timestamp))
+            .collect();
+        assert_eq!(
+            cached,
+            vec![
+                ("CharA", Duration::from_secs(3)),
+                ("CharB", Duration::from_secs(6)),
+            ]
+        );
+        ctrl.set_source_offset("CharB", 0);
+        assert_eq!(ctrl.all_events()[0].character, "CharB");
+        assert_eq!(ctrl.all_events()[0].timestamp, Duration::from_secs(1));
+
+        // Offsets survive a seek
+        ctrl.set_source_offset("CharB", 5_000);
+        ctrl.seek(Duration::ZERO).unwrap();
+        assert_eq!(ctrl.stream.next_event().unwrap().0.character, "CharA");
+    }
+
     #[test]
     fn test_replay_controller_speed() {
         let dir = tempdir().unwrap();
@@ -297,4 +983,374 @@ mod tests {
         let events = ctrl.tick();
         assert_eq!(events.0.len(), 1);
     }
+
+    #[test]
+    fn test_replay_controller_loop_wraps() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        let mut f = File::create(&path).unwrap();
+        for sec in 0..10 {
+            writeln!(
+                f,
+                "[ 2024.01.01 12:00:{:02} ] (combat) 10 from A to X [ Gun ]",
+                sec
+            )
+            .unwrap();
+        }
+
+        let mut ctrl = ReplayController::new(vec![("A".to_string(), path)]).unwrap();
+        ctrl.set_loop(Some((Duration::from_secs(2), Duration::from_secs(4))));
+        ctrl.seek(Duration::from_secs(2)).unwrap();
+        ctrl.set_state(PlaybackState::Playing);
+
+        let mut wraps = 0;
+        for _ in 0..6 {
+            ctrl.step(Duration::from_secs(1));
+            let _ = ctrl.tick();
+            if ctrl.take_loop_wrapped() {
+                wraps += 1;
+            }
+            assert!(ctrl.relative_progress() < Duration::from_secs(4));
+        }
+        assert!(wraps >= 2, "sim time should wrap instead of ending");
+
+        // Paused playback never wraps
+        ctrl.set_state(PlaybackState::Paused);
+        ctrl.step(Duration::from_secs(5));
+        let _ = ctrl.tick();
+        assert!(!ctrl.take_loop_wrapped());
+    }
+
+    #[test]
+    fn test_tick_surfaces_inline_bookmarks() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        let mut f = File::create(&path).unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:00 ] (combat) 10 from A to X [ Gun ]").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:03 ] (bookmark) RUN_START").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:05 ] (combat) 10 from A to X [ Gun ]").unwrap();
+
+        let mut ctrl = ReplayController::new(vec![("A".to_string(), path)]).unwrap();
+        ctrl.set_state(PlaybackState::Playing);
+
+        // Before the marker: nothing surfaced yet
+        ctrl.step(Duration::from_secs(2));
+        let (_, _, bookmarks) = ctrl.tick();
+        assert!(bookmarks.is_empty());
+
+        // Crossing the marker surfaces it exactly once
+        ctrl.step(Duration::from_secs(2));
+        let (_, _, bookmarks) = ctrl.tick();
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].kind, BookmarkType::RunStart);
+        assert_eq!(bookmarks[0].time, Duration::from_secs(3));
+
+        ctrl.step(Duration::from_secs(2));
+        let (events, _, bookmarks) = ctrl.tick();
+        assert_eq!(events.len(), 1);
+        assert!(bookmarks.is_empty());
+    }
+
+    #[test]
+    fn test_seek_next_and_prev_run() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        let mut f = File::create(&path).unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:00 ] (combat) 10 from A to X [ Gun ]").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:10 ] (bookmark) RUN_START").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:20 ] (bookmark) RUN_START").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:30 ] (bookmark) RUN_START").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:40 ] (combat) 10 from A to X [ Gun ]").unwrap();
+
+        let mut ctrl = ReplayController::new(vec![("A".to_string(), path)]).unwrap();
+        assert_eq!(ctrl.run_starts().len(), 3);
+
+        // Forward through every run, clamping at the session end
+        assert_eq!(ctrl.seek_next_run().unwrap(), Duration::from_secs(10));
+        assert_eq!(ctrl.seek_next_run().unwrap(), Duration::from_secs(20));
+        assert_eq!(ctrl.seek_next_run().unwrap(), Duration::from_secs(30));
+        assert_eq!(ctrl.seek_next_run().unwrap(), Duration::from_secs(40));
+        assert_eq!(ctrl.relative_progress(), Duration::from_secs(40));
+
+        // Backward through every run, clamping at the session start
+        assert_eq!(ctrl.seek_prev_run().unwrap(), Duration::from_secs(30));
+        assert_eq!(ctrl.seek_prev_run().unwrap(), Duration::from_secs(20));
+        assert_eq!(ctrl.seek_prev_run().unwrap(), Duration::from_secs(10));
+        assert_eq!(ctrl.seek_prev_run().unwrap(), Duration::ZERO);
+        assert_eq!(ctrl.seek_prev_run().unwrap(), Duration::ZERO);
+
+        // A clock correction moves the run starts with the source's events
+        ctrl.set_source_offset("A", 2_000);
+        assert_eq!(ctrl.seek_next_run().unwrap(), Duration::from_secs(12));
+        ctrl.set_source_offset("A", 0);
+        assert_eq!(ctrl.seek_next_run().unwrap(), Duration::from_secs(20));
+    }
+
+    #[test]
+    fn test_set_loop_rejects_empty_range() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        let mut f = File::create(&path).unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:00 ] (combat) 10 from A to X [ Gun ]").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:05 ] (combat) 10 from A to X [ Gun ]").unwrap();
+
+        let mut ctrl = ReplayController::new(vec![("A".to_string(), path)]).unwrap();
+        ctrl.set_loop(Some((Duration::from_secs(3), Duration::from_secs(3))));
+        assert!(ctrl.loop_range().is_none());
+
+        // End is clamped to the session duration
+        ctrl.set_loop(Some((Duration::from_secs(1), Duration::from_secs(60))));
+        assert_eq!(
+            ctrl.loop_range(),
+            Some((Duration::from_secs(1), Duration::from_secs(5)))
+        );
+    }
+
+    #[test]
+    fn test_bookmarks_cross_at_absolute_time() {
+        let dir = tempdir().unwrap();
+        let path_a = dir.path().join("a.txt");
+        let mut f = File::create(&path_a).unwrap();
+        writeln!(f, "  Session Started: 2024.01.01 12:00:00").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:10 ] (combat) 10 from A to X [ Gun ]").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:30 ] (combat) 10 from A to X [ Gun ]").unwrap();
+
+        // B's session started 5s later, so its relative times are 5s behind A's
+        let path_b = dir.path().join("b.txt");
+        let mut f = File::create(&path_b).unwrap();
+        writeln!(f, "  Session Started: 2024.01.01 12:00:05").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:15 ] (bookmark) HIGHLIGHT").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:30 ] (combat) 10 from B to X [ Gun ]").unwrap();
+
+        let mut ctrl =
+            ReplayController::new(vec![("A".to_string(), path_a), ("B".to_string(), path_b)])
+                .unwrap();
+        let epoch = NaiveDateTime::parse_from_str("2024.01.01 12:00:00", "%Y.%m.%d %H:%M:%S")
+            .unwrap()
+            .and_utc()
+            .timestamp() as u64;
+        assert_eq!(ctrl.current_sim_time(), Duration::from_secs(10));
+        assert_eq!(ctrl.absolute_sim_time(), Duration::from_secs(epoch + 10));
+        ctrl.set_state(PlaybackState::Playing);
+
+        // B's bookmark is 10s into its own log, but must not surface before 12:00:15
+        ctrl.step(Duration::from_secs(4));
+        let (_, _, bookmarks) = ctrl.tick();
+        assert!(bookmarks.is_empty());
+
+        ctrl.step(Duration::from_secs(1));
+        let (_, _, bookmarks) = ctrl.tick();
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].kind, BookmarkType::Highlight);
+        assert_eq!(bookmarks[0].absolute, Duration::from_secs(epoch + 15));
+        assert!(ctrl.current_sim_time() >= Duration::from_secs(15));
+    }
+
+    #[test]
+    fn test_live_simulation_reaches_total_damage() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        let mut f = File::create(&path).unwrap();
+        for secs in 0..10 {
+            writeln!(
+                f,
+                "[ 2024.01.01 12:00:{:02} ] (combat) 100 to X - Gun - Hits",
+                secs
+            )
+            .unwrap();
+        }
+
+        let mut sim =
+            LiveSimulation::new("A".to_string(), path, 100.0, LocaleConfig::default()).unwrap();
+        // 9s of log at 100x plays in ~90ms of wall time
+        let mut had_sample = false;
+        for _ in 0..200 {
+            let (_, sample) = sim.tick(Duration::from_secs(5));
+            // Once there is a sample, quiet ticks keep repeating it
+            assert!(!had_sample || sample.is_some());
+            had_sample |= sample.is_some();
+            if sim.is_finished() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        assert!(sim.is_finished());
+        assert!(sim.tick(Duration::from_secs(5)).1.is_some());
+        assert_eq!(sim.engine().events().len(), 10);
+        assert_eq!(sim.engine().total_damage(), 1000.0);
+    }
+
+    #[test]
+    fn test_run_boundary_reported_after_gap() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        let mut f = File::create(&path).unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:00 ] (combat) 10 from A to X [ Gun ]").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:05 ] (combat) 10 from A to X [ Gun ]").unwrap();
+        // Five quiet minutes, then the next run
+        writeln!(f, "[ 2024.01.01 12:05:05 ] (combat) 10 from A to Y [ Gun ]").unwrap();
+        writeln!(f, "[ 2024.01.01 12:05:06 ] (combat) 10 from A to Y [ Gun ]").unwrap();
+
+        let mut ctrl = ReplayController::new(vec![("A".to_string(), path)]).unwrap();
+        ctrl.set_state(PlaybackState::Playing);
+
+        ctrl.step(Duration::from_secs(10));
+        let (events, _, _) = ctrl.tick();
+        assert_eq!(events.len(), 2);
+        assert_eq!(ctrl.take_run_boundary(), None);
+
+        // Both runs' remaining events in one tick: the boundary is at the second run
+        ctrl.step(Duration::from_secs(300));
+        let (events, _, _) = ctrl.tick();
+        assert_eq!(events.len(), 2);
+        assert_eq!(ctrl.take_run_boundary(), Some(0));
+        assert_eq!(ctrl.take_run_boundary(), None);
+
+        // Without a gap threshold the same log is one run
+        ctrl.set_run_gap(None);
+        ctrl.seek(Duration::ZERO).unwrap();
+        ctrl.step(Duration::from_secs(400));
+        let (events, _, _) = ctrl.tick();
+        assert_eq!(events.len(), 4);
+        assert_eq!(ctrl.take_run_boundary(), None);
+    }
+
+    #[test]
+    fn test_run_boundary_at_run_start_bookmark() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        let mut f = File::create(&path).unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:00 ] (combat) 10 from A to X [ Gun ]").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:05 ] (combat) 10 from A to X [ Gun ]").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:06 ] (bookmark) RUN_START").unwrap();
+        writeln!(f, "[ 2024.01.01 12:00:07 ] (combat) 10 from A to Y [ Gun ]").unwrap();
+
+        let mut ctrl = ReplayController::new(vec![("A".to_string(), path)]).unwrap();
+        ctrl.set_state(PlaybackState::Playing);
+        ctrl.step(Duration::from_secs(10));
+        let (events, _, _) = ctrl.tick();
+        assert_eq!(events.len(), 3);
+        assert_eq!(ctrl.take_run_boundary(), Some(2));
+    }
+
+    #[test]
+    fn test_snapshot_at_matches_series_sample() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        let mut f = File::create(&path).unwrap();
+        for second in 0..20 {
+            writeln!(
+                f,
+                "[ 2024.01.01 12:00:{:02} ] (combat) {} from A to X [ Gun ]",
+                second,
+                10 * (second + 1)
+            )
+            .unwrap();
+        }
+
+        let ctrl = ReplayController::new(vec![("A".to_string(), path.clone())]).unwrap();
+        let window = Duration::from_secs(5);
+
+        let events =
+            crate::core::log_io::read_full_events(&path, &LocaleConfig::default()).unwrap();
+        let series = analysis::compute_dps_series(&events, window, Duration::from_secs(19));
+        let expected = series
+            .iter()
+            .find(|s| s.time == Duration::from_secs(10))
+            .unwrap();
+
+        let snapshot = ctrl.snapshot_at(Duration::from_secs(10), window).unwrap();
+        assert_eq!(snapshot.time, Duration::from_secs(10));
+        assert_eq!(snapshot.outgoing_dps, expected.outgoing_dps);
+        // Scrubbing doesn't move the playhead
+        assert_eq!(ctrl.relative_progress(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_export_merged_round_trips_events() {
+        let dir = tempdir().unwrap();
+        let write_log = |name: &str, character: &str, lines: &[&str]| {
+            let path = dir.path().join(name);
+            let mut f = File::create(&path).unwrap();
+            writeln!(f, "  Listener: {}", character).unwrap();
+            writeln!(f, "  Session Started: 2024.01.01 12:00:00").unwrap();
+            for line in lines {
+                writeln!(f, "{}", line).unwrap();
+            }
+            path
+        };
+        let path_a = write_log(
+            "A.txt",
+            "Alpha",
+            &[
+                "[ 2024.01.01 12:00:01 ] (combat) <color=0xff00ffff><b>523</b> <font size=10>to</font> <b>Starving Damavik</b> - Small Focused Beam Laser II - Penetrates",
+                "[ 2024.01.01 12:00:03 ] (combat) 77 from Starving Damavik - Armor - Hits",
+                "[ 2024.01.01 12:00:06 ] (combat) <color=0xffe57f7f><b>38 GJ</b> energy neutralized Starving Vedmak - Small Energy Neutralizer II",
+            ],
+        );
+        let path_b = write_log(
+            "B.txt",
+            "Bravo",
+            &[
+                "[ 2024.01.01 12:00:02 ] (combat) 120 remote shield boosted to Alpha - Small Remote Shield Booster II",
+                "[ 2024.01.01 12:00:04 ] (combat) Warp scramble attempt from Starving Damavik to you!",
+                "[ 2024.01.01 12:00:05 ] (combat) 50 to Starving Damavik - Hobgoblin II - Smashes",
+            ],
+        );
+        let chains = vec![
+            ("Alpha".to_string(), vec![path_a]),
+            ("Bravo".to_string(), vec![path_b]),
+        ];
+        let ctrl = ReplayController::new_chained(chains).unwrap();
+
+        let out = dir.path().join("export");
+        let files = ctrl.export_merged(&out).unwrap();
+        assert_eq!(files.len(), 2);
+
+        let reread = ReplayController::new_chained(vec![
+            ("Alpha".to_string(), vec![files[0].clone()]),
+            ("Bravo".to_string(), vec![files[1].clone()]),
+        ])
+        .unwrap();
+        assert_eq!(reread.all_events.len(), 6);
+        assert_eq!(
+            format!("{:?}", reread.all_events),
+            format!("{:?}", ctrl.all_events)
+        );
+    }
+
+    #[test]
+    fn test_resume_from_keeps_playback_with_new_logs() {
+        let dir = tempdir().unwrap();
+        let write_log = |name: &str, last_second: u32| {
+            let path = dir.path().join(name);
+            let mut f = File::create(&path).unwrap();
+            writeln!(f, "  Listener: Pilot").unwrap();
+            writeln!(f, "  Session Started: 2024.01.01 12:00:00").unwrap();
+            for second in [0, last_second] {
+                writeln!(
+                    f,
+                    "[ 2024.01.01 12:00:{:02} ] (combat) 100 to Rat - Gun - Hits",
+                    second
+                )
+                .unwrap();
+            }
+            path
+        };
+        let short = write_log("short.txt", 10);
+        let long = write_log("long.txt", 40);
+
+        let mut ctrl = ReplayController::new(vec![("Pilot".to_string(), short)]).unwrap();
+        ctrl.set_state(PlaybackState::Playing);
+        ctrl.set_speed(4.0);
+        ctrl.step(Duration::from_secs(5));
+        assert_eq!(ctrl.session_duration(), Duration::from_secs(10));
+
+        let mut swapped = ReplayController::new(vec![("Pilot".to_string(), long)]).unwrap();
+        swapped.resume_from(&ctrl);
+        assert_eq!(swapped.session_duration(), Duration::from_secs(40));
+        assert_eq!(swapped.get_state(), PlaybackState::Playing);
+        assert_eq!(swapped.speed(), 4.0);
+    }
 }
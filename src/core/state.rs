@@ -1,20 +1,118 @@
+use std::collections::HashMap;
 use std::time::Duration;
 
-use super::analysis;
-use super::model::{CombatEvent, DpsSample};
+use super::analysis::{self, DpsAccumulator};
+use super::model::{
+    CombatEvent, DpsSample, EntityName, EventType, KillEvent, QualityCounts, WeaponName,
+};
 
 pub struct EngineState {
+    /// Always kept sorted by timestamp so analysis never has to re-sort.
     events: Vec<CombatEvent>,
-    sorted: bool,
+    /// When set, events older than `latest - retention` are dropped on push.
+    retention: Option<Duration>,
+    /// Incremental DPS state for `dps_tail`, created on first use.
+    accumulator: Option<DpsAccumulator>,
+    /// Highest outgoing/incoming DPS seen by `dps_tail` since the last reset
+    peak_outgoing: f32,
+    peak_incoming: f32,
+    /// Damage dealt/taken over every event ever pushed, unaffected by retention
+    total_outgoing: f32,
+    total_incoming: f32,
+    /// When set, outgoing damage to any other target is left out of DPS
+    focus_target: Option<EntityName>,
+    /// Recent kills; pruned with the events when a retention is set
+    kills: Vec<KillEvent>,
+    /// Kills ever pushed, unaffected by retention
+    kill_count: usize,
 }
 
 impl EngineState {
     pub fn new() -> Self {
         Self {
             events: Vec::new(),
-            sorted: true,
+            retention: None,
+            accumulator: None,
+            peak_outgoing: 0.0,
+            peak_incoming: 0.0,
+            total_outgoing: 0.0,
+            total_incoming: 0.0,
+            focus_target: None,
+            kills: Vec::new(),
+            kill_count: 0,
         }
     }
+
+    /// Count outgoing DPS only against `target`, or against everything with `None`.
+    /// Events are still all kept, so clearing the focus restores the full numbers.
+    pub fn set_focus_target(&mut self, target: Option<EntityName>) {
+        if self.focus_target != target {
+            self.focus_target = target;
+            // Rebuilt from the retained events on the next `dps_tail`
+            self.accumulator = None;
+        }
+    }
+
+    pub fn focus_target(&self) -> Option<&str> {
+        self.focus_target.as_deref()
+    }
+
+    /// Bound memory by keeping only events within `retention` of the newest one.
+    /// Meant for live tracking; replay and full-session analysis leave it unset.
+    /// Note that `events()` and aggregates like `total_damage()` then only cover
+    /// the retained window.
+    pub fn set_retention(&mut self, retention: Duration) {
+        self.retention = Some(retention);
+        self.evict_expired();
+    }
+
+    fn evict_expired(&mut self) {
+        self.evict_expired_kills();
+        let (Some(retention), Some(last)) = (self.retention, self.events.last()) else {
+            return;
+        };
+        let cutoff = last.timestamp.saturating_sub(retention);
+        let expired = self
+            .events
+            .partition_point(|event| event.timestamp < cutoff);
+        if expired > 0 {
+            self.events.drain(..expired);
+        }
+    }
+
+    fn evict_expired_kills(&mut self) {
+        let Some(retention) = self.retention else {
+            return;
+        };
+        let newest = self
+            .events
+            .last()
+            .map(|event| event.timestamp)
+            .into_iter()
+            .chain(self.kills.iter().map(|kill| kill.time))
+            .max()
+            .unwrap_or_default();
+        let cutoff = newest.saturating_sub(retention);
+        self.kills.retain(|kill| kill.time >= cutoff);
+    }
+
+    fn add_to_totals(&mut self, event: &CombatEvent) {
+        if event.event_type != EventType::Damage {
+            return;
+        }
+        if event.incoming {
+            self.total_incoming += event.amount;
+        } else {
+            self.total_outgoing += event.amount;
+        }
+    }
+}
+
+/// Whether an event counts towards DPS with `focus` as the focus target
+fn in_focus(focus: Option<&str>, event: &CombatEvent) -> bool {
+    focus.is_none_or(|focus| {
+        event.incoming || event.event_type != EventType::Damage || event.target == focus
+    })
 }
 
 impl Default for EngineState {
@@ -24,9 +122,26 @@ impl Default for EngineState {
 }
 
 impl EngineState {
+    /// Insert an event at its timestamp position. Events usually arrive in order,
+    /// so the common case is a plain push; stragglers from other sources are
+    /// binary-search inserted after any events with the same timestamp.
     pub fn push_event(&mut self, event: CombatEvent) {
-        self.events.push(event);
-        self.sorted = false;
+        if let Some(accumulator) = &mut self.accumulator {
+            if in_focus(self.focus_target.as_deref(), &event) {
+                accumulator.push(event.clone());
+            }
+        }
+        self.add_to_totals(&event);
+        match self.events.last() {
+            Some(last) if last.timestamp > event.timestamp => {
+                let index = self
+                    .events
+                    .partition_point(|existing| existing.timestamp <= event.timestamp);
+                self.events.insert(index, event);
+            }
+            _ => self.events.push(event),
+        }
+        self.evict_expired();
     }
 
     #[allow(dead_code)]
@@ -34,15 +149,43 @@ impl EngineState {
         if new_events.is_empty() {
             return;
         }
+        if let Some(accumulator) = &mut self.accumulator {
+            for event in &new_events {
+                if in_focus(self.focus_target.as_deref(), event) {
+                    accumulator.push(event.clone());
+                }
+            }
+        }
+        for event in &new_events {
+            self.add_to_totals(event);
+        }
+        let boundary = self.events.len();
         self.events.append(&mut new_events);
-        self.sorted = false;
+
+        let in_order = self.events[boundary.saturating_sub(1)..]
+            .windows(2)
+            .all(|pair| pair[0].timestamp <= pair[1].timestamp);
+        if !in_order {
+            // Stable sort merges the two sorted runs cheaply and keeps arrival order for ties
+            self.events.sort_by_key(|event| event.timestamp);
+        }
+        self.evict_expired();
     }
 
+    /// Events sorted by timestamp.
     #[allow(dead_code)]
     pub fn events(&self) -> &[CombatEvent] {
         &self.events
     }
 
+    /// Retained events with `start <= timestamp <= end`.
+    pub fn events_in(&self, start: Duration, end: Duration) -> &[CombatEvent] {
+        let from = self.events.partition_point(|event| event.timestamp < start);
+        let to = self.events.partition_point(|event| event.timestamp <= end);
+        &self.events[from..to.max(from)]
+    }
+
+    /// Total outgoing damage of the retained events.
     #[allow(dead_code)]
     pub fn total_damage(&self) -> f32 {
         self.events
@@ -52,13 +195,107 @@ impl EngineState {
             .sum()
     }
 
-    pub fn dps_series(&mut self, window: Duration, end: Duration) -> Vec<DpsSample> {
-        if !self.sorted {
-            self.events
-                .sort_by_key(|event| event.timestamp.as_millis() as u64);
-            self.sorted = true;
+    /// Damage dealt over the whole session, including events already evicted by retention.
+    /// Only `Damage` events count, unlike `total_damage`.
+    pub fn total_outgoing_damage(&self) -> f32 {
+        self.total_outgoing
+    }
+
+    /// Damage taken over the whole session, including events already evicted by retention.
+    pub fn total_incoming_damage(&self) -> f32 {
+        self.total_incoming
+    }
+
+    /// Raw incoming damage taken in the trailing `window` ending at `now` (inclusive),
+    /// for a "damage eaten recently" gauge. Unlike `incoming_dps` this is a total,
+    /// not a per-second rate. Only covers retained events.
+    pub fn incoming_damage_in(&self, window: Duration, now: Duration) -> f32 {
+        self.events_in(now.saturating_sub(window), now)
+            .iter()
+            .filter(|event| event.incoming && event.event_type == EventType::Damage)
+            .map(|event| event.amount)
+            .sum()
+    }
+
+    /// Per-weapon hit-quality counts over the retained events.
+    pub fn weapon_quality_histogram(&self) -> HashMap<WeaponName, QualityCounts> {
+        analysis::weapon_quality_histogram(&self.events)
+    }
+
+    /// Full DPS series over all retained events, recomputed from scratch.
+    /// Honors the focus target.
+    pub fn dps_series(&self, window: Duration, end: Duration) -> Vec<DpsSample> {
+        if self.focus_target.is_none() {
+            return analysis::compute_dps_series(&self.events, window, end);
         }
-        analysis::compute_dps_series(&self.events, window, end)
+        let focused: Vec<CombatEvent> = self
+            .events
+            .iter()
+            .filter(|event| in_focus(self.focus_target.as_deref(), event))
+            .cloned()
+            .collect();
+        analysis::compute_dps_series(&focused, window, end)
+    }
+
+    /// Samples that are new since the previous call, computed incrementally.
+    /// Changing `window` rebuilds the accumulator from the retained events.
+    pub fn dps_tail(&mut self, window: Duration, end: Duration) -> Vec<DpsSample> {
+        if self.accumulator.as_ref().map(|acc| acc.window()) != Some(window) {
+            let mut accumulator = DpsAccumulator::new(window);
+            let focus = self.focus_target.as_deref();
+            for event in self.events.iter().filter(|event| in_focus(focus, event)) {
+                accumulator.push(event.clone());
+            }
+            self.accumulator = Some(accumulator);
+        }
+        let samples = self
+            .accumulator
+            .as_mut()
+            .map(|acc| acc.advance(end))
+            .unwrap_or_default();
+        for sample in &samples {
+            self.peak_outgoing = self.peak_outgoing.max(sample.outgoing_dps);
+            self.peak_incoming = self.peak_incoming.max(sample.incoming_dps);
+        }
+        samples
+    }
+
+    /// Record a confirmed kill.
+    pub fn push_kill(&mut self, kill: KillEvent) {
+        self.kills.push(kill);
+        self.kill_count += 1;
+        self.evict_expired_kills();
+    }
+
+    pub fn kill_count(&self) -> usize {
+        self.kill_count
+    }
+
+    /// Kills per minute over the `window` ending at `now`.
+    pub fn kills_per_minute(&self, window: Duration, now: Duration) -> f32 {
+        let start = now.saturating_sub(window);
+        let kills = self
+            .kills
+            .iter()
+            .filter(|kill| kill.time > start && kill.time <= now)
+            .count();
+        kills as f32 * 60.0 / window.as_secs_f32().max(1.0)
+    }
+
+    /// Highest outgoing DPS produced by `dps_tail` since the last reset.
+    pub fn peak_outgoing(&self) -> f32 {
+        self.peak_outgoing
+    }
+
+    /// Highest incoming DPS produced by `dps_tail` since the last reset.
+    pub fn peak_incoming(&self) -> f32 {
+        self.peak_incoming
+    }
+
+    /// Forget the peaks, e.g. when a new run starts. Events are kept.
+    pub fn reset_peaks(&mut self) {
+        self.peak_outgoing = 0.0;
+        self.peak_incoming = 0.0;
     }
 }
 
@@ -77,20 +314,52 @@ mod tests {
             incoming: false,
             character: character.to_string(),
             event_type: EventType::Damage,
+            hit_quality: None,
+            via_drone: false,
+            self_inflicted: false,
+            damage_layer: None,
         }
     }
 
     #[test]
-    fn engine_state_sorts_events_before_analysis() {
+    fn engine_state_keeps_events_sorted_on_insert() {
         let mut state = EngineState::new();
         state.push_event(make_event(10, "A"));
         state.push_event(make_event(5, "A"));
+        state.push_event(make_event(7, "B"));
+        state.push_event(make_event(12, "B"));
 
-        assert!(!state.sorted);
-        let _ = state.dps_series(Duration::from_secs(1), Duration::from_secs(10));
-        assert!(state.sorted);
-        assert_eq!(state.events[0].timestamp.as_secs(), 5);
-        assert_eq!(state.events[1].timestamp.as_secs(), 10);
+        let times: Vec<u64> = state
+            .events()
+            .iter()
+            .map(|e| e.timestamp.as_secs())
+            .collect();
+        assert_eq!(times, vec![5, 7, 10, 12]);
+    }
+
+    #[test]
+    fn equal_timestamps_keep_arrival_order() {
+        let mut state = EngineState::new();
+        state.push_event(make_event(10, "A"));
+        state.push_event(make_event(5, "A"));
+        state.push_event(make_event(5, "B"));
+
+        assert_eq!(state.events()[0].character, "A");
+        assert_eq!(state.events()[1].character, "B");
+    }
+
+    #[test]
+    fn push_events_merges_out_of_order_batch() {
+        let mut state = EngineState::new();
+        state.push_events(vec![make_event(1, "A"), make_event(4, "A")]);
+        state.push_events(vec![make_event(2, "B"), make_event(6, "B")]);
+
+        let times: Vec<u64> = state
+            .events()
+            .iter()
+            .map(|e| e.timestamp.as_secs())
+            .collect();
+        assert_eq!(times, vec![1, 2, 4, 6]);
     }
 
     #[test]
@@ -104,4 +373,176 @@ mod tests {
 
         assert_eq!(state.total_damage(), 100.0);
     }
+
+    #[test]
+    fn retention_evicts_old_events() {
+        let mut state = EngineState::new();
+        state.set_retention(Duration::from_secs(5 * 60));
+
+        // One event per minute for an hour
+        for minute in 0..=60 {
+            state.push_event(make_event(minute * 60, "A"));
+        }
+
+        let times: Vec<u64> = state
+            .events()
+            .iter()
+            .map(|e| e.timestamp.as_secs())
+            .collect();
+        assert_eq!(
+            times,
+            vec![55 * 60, 56 * 60, 57 * 60, 58 * 60, 59 * 60, 60 * 60]
+        );
+        assert_eq!(state.total_damage(), 600.0);
+    }
+
+    #[test]
+    fn no_retention_keeps_everything() {
+        let mut state = EngineState::new();
+        for minute in 0..=60 {
+            state.push_event(make_event(minute * 60, "A"));
+        }
+        assert_eq!(state.events().len(), 61);
+    }
+
+    #[test]
+    fn incoming_damage_in_sums_trailing_window() {
+        let incoming = |secs: u64, amount: f32| CombatEvent {
+            incoming: true,
+            amount,
+            ..make_event(secs, "A")
+        };
+        let mut state = EngineState::new();
+        state.push_event(incoming(1, 100.0));
+        state.push_event(incoming(5, 50.0));
+        state.push_event(make_event(8, "A")); // Outgoing, ignored
+        state.push_event(CombatEvent {
+            event_type: EventType::Neut,
+            ..incoming(10, 500.0)
+        }); // Not damage, ignored
+        state.push_event(incoming(12, 30.0));
+        state.push_event(incoming(20, 999.0)); // After `now`
+
+        let total = state.incoming_damage_in(Duration::from_secs(10), Duration::from_secs(15));
+        assert_eq!(total, 80.0);
+        assert_eq!(
+            state.incoming_damage_in(Duration::from_secs(10), Duration::from_secs(100)),
+            0.0
+        );
+    }
+
+    #[test]
+    fn peaks_reset_for_new_run_and_rebuild_from_combat() {
+        let window = Duration::from_secs(1);
+        let mut state = EngineState::new();
+        state.push_event(make_event(1, "A"));
+        state.push_event(make_event(1, "A"));
+        state.dps_tail(window, Duration::from_secs(1));
+        assert_eq!(state.peak_outgoing(), 200.0);
+
+        // Quieter combat doesn't lower the peak
+        state.push_event(make_event(5, "A"));
+        state.dps_tail(window, Duration::from_secs(5));
+        assert_eq!(state.peak_outgoing(), 200.0);
+
+        // New run: peak cleared, then re-established by fresh combat
+        state.reset_peaks();
+        assert_eq!(state.peak_outgoing(), 0.0);
+        state.push_event(make_event(20, "A"));
+        state.dps_tail(window, Duration::from_secs(20));
+        assert_eq!(state.peak_outgoing(), 100.0);
+        assert_eq!(state.peak_incoming(), 0.0);
+    }
+
+    #[test]
+    fn damage_totals_split_by_direction_and_survive_eviction() {
+        let mut state = EngineState::new();
+        state.set_retention(Duration::from_secs(10));
+        state.push_event(make_event(1, "A")); // Outgoing 100
+        state.push_event(CombatEvent {
+            incoming: true,
+            amount: 40.0,
+            ..make_event(2, "A")
+        });
+        state.push_events(vec![
+            CombatEvent {
+                incoming: true,
+                amount: 60.0,
+                ..make_event(30, "A")
+            },
+            make_event(31, "B"),
+        ]);
+        // Reps don't count as damage
+        state.push_event(CombatEvent {
+            event_type: EventType::Repair,
+            ..make_event(32, "B")
+        });
+
+        assert_eq!(state.total_outgoing_damage(), 200.0);
+        assert_eq!(state.total_incoming_damage(), 100.0);
+        // The first events were evicted but still count toward the totals
+        assert_eq!(state.events().len(), 3);
+    }
+
+    #[test]
+    fn focus_target_limits_outgoing_dps() {
+        let hit = |secs: u64, target: &str, amount: f32| CombatEvent {
+            target: target.to_string(),
+            amount,
+            ..make_event(secs, "A")
+        };
+        let mut state = EngineState::new();
+        state.push_event(hit(1, "Boss", 100.0));
+        state.push_event(hit(1, "Trash", 300.0));
+        state.push_event(CombatEvent {
+            incoming: true,
+            target: "A".to_string(),
+            amount: 50.0,
+            ..make_event(1, "A")
+        });
+
+        let window = Duration::from_secs(1);
+        let end = Duration::from_secs(2);
+        state.set_focus_target(Some("Boss".to_string()));
+        let focused = state.dps_series(window, end);
+        let sample = focused.iter().find(|s| s.outgoing_dps > 0.0).unwrap();
+        assert_eq!(sample.outgoing_dps, 100.0);
+        assert_eq!(sample.incoming_dps, 50.0);
+        assert!(!sample.outgoing_by_target.contains_key("Trash"));
+
+        // The incremental path agrees, and nothing was thrown away
+        let tail_peak = state
+            .dps_tail(window, end)
+            .iter()
+            .map(|s| s.outgoing_dps)
+            .fold(0.0, f32::max);
+        assert_eq!(tail_peak, 100.0);
+        assert_eq!(state.events().len(), 3);
+
+        state.set_focus_target(None);
+        let all = state.dps_series(window, end);
+        let peak = all.iter().map(|s| s.outgoing_dps).fold(0.0, f32::max);
+        assert_eq!(peak, 400.0);
+    }
+
+    #[test]
+    fn counts_kills_and_rate() {
+        let mut state = EngineState::new();
+        for secs in [10, 40, 70] {
+            state.push_kill(KillEvent {
+                time: Duration::from_secs(secs),
+                character: "A".to_string(),
+                victim: "Starving Damavik".to_string(),
+            });
+        }
+        assert_eq!(state.kill_count(), 3);
+        // Two kills in the last minute
+        let rate = state.kills_per_minute(Duration::from_secs(60), Duration::from_secs(75));
+        assert_eq!(rate, 2.0);
+
+        // Retention prunes old kills but not the count
+        state.set_retention(Duration::from_secs(45));
+        assert_eq!(state.kills.len(), 2);
+        assert_eq!(state.kill_count(), 3);
+    }
 }
@@ -1,14 +1,43 @@
+use chrono::NaiveDateTime;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::fs;
+use std::hash::{Hash, Hasher};
 use std::io;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
+use super::inline_bookmarks::{self, InlineBookmark};
 use super::log_io;
 use super::model;
 use super::parser;
 
-/// Result of reading new log lines: combat events and notify events
+/// Result of reading new log lines: combat events, notify events, kills and inline bookmarks
 pub struct TrackerReadResult {
     pub combat_events: Vec<model::CombatEvent>,
     pub notify_events: Vec<model::NotifyEvent>,
+    pub kill_events: Vec<model::KillEvent>,
+    pub bookmarks: Vec<InlineBookmark>,
+}
+
+/// How many recent lines are remembered to recognize a re-read after a reset
+const RECENT_LINES: usize = 64;
+
+/// How many recent bookmark times are kept for session markers
+const RECENT_MARKERS: usize = 32;
+
+/// Consecutive transient read errors tolerated before they are reported
+const MAX_TRANSIENT_FAILURES: u32 = 5;
+
+/// Errors that network shares (SMB/NFS) raise intermittently while the client writes
+fn is_transient(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::PermissionDenied
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::TimedOut
+    )
 }
 
 #[allow(dead_code)]
@@ -17,26 +46,76 @@ pub struct TrackedGamelog {
     parser: parser::LineParser,
     pub source: String,
     path: PathBuf,
+    /// Hashes of the last `RECENT_LINES` lines read, oldest first
+    recent_lines: VecDeque<u64>,
+    /// The file was truncated or replaced and is being read again from the start;
+    /// lines already seen are skipped until the first new one
+    resyncing: bool,
+    /// Epoch seconds of the most recent inline bookmarks, oldest first
+    recent_markers: VecDeque<u64>,
+    /// Modification time and length after the last read; an unchanged file is not read
+    last_stamp: Option<(SystemTime, u64)>,
+    /// Transient read errors in a row, see `MAX_TRANSIENT_FAILURES`
+    transient_failures: u32,
 }
 
 impl TrackedGamelog {
     pub fn new(source: impl Into<String>, path: impl AsRef<Path>) -> io::Result<Self> {
         let pathbuf = path.as_ref().to_path_buf();
         let tailer = log_io::LogTailer::open(&pathbuf)?;
+        let source: String = source.into();
         Ok(Self {
             tailer,
-            parser: parser::LineParser::new(),
-            source: source.into(),
+            parser: parser::LineParser::new_with_listener(&source),
+            source,
             path: pathbuf,
+            recent_lines: VecDeque::with_capacity(RECENT_LINES),
+            resyncing: false,
+            recent_markers: VecDeque::with_capacity(RECENT_MARKERS),
+            last_stamp: None,
+            transient_failures: 0,
         })
     }
 
+    /// Parse the log with the combat tokens of another client language.
+    pub fn set_locale(&mut self, locale: &parser::LocaleConfig) {
+        self.parser.set_locale(locale.clone());
+    }
+
     /// Read new log lines and parse both combat and notify events
     pub fn read_new_events(&mut self) -> io::Result<TrackerReadResult> {
         let mut combat_events = Vec::new();
         let mut notify_events = Vec::new();
+        let mut kill_events = Vec::new();
+        let mut bookmarks = Vec::new();
+
+        // Most ticks find nothing new; skip the seek and read entirely
+        let stamp = self.file_stamp();
+        if stamp.is_some() && stamp == self.last_stamp {
+            return Ok(TrackerReadResult {
+                combat_events,
+                notify_events,
+                kill_events,
+                bookmarks,
+            });
+        }
+        self.last_stamp = stamp;
 
-        for line in self.tailer.read_new_lines()? {
+        let lines = match self.read_lines() {
+            Ok(lines) => {
+                self.transient_failures = 0;
+                lines
+            }
+            Err(e) => {
+                self.absorb_read_error(e)?;
+                Vec::new()
+            }
+        };
+
+        for line in lines {
+            if !self.remember_line(&line) {
+                continue;
+            }
             // Try parsing as combat event
             if let Some(event) = self.parser.parse_line(&line, &self.source) {
                 combat_events.push(event);
@@ -45,20 +124,269 @@ impl TrackedGamelog {
             if let Some(notify) = self.parser.parse_notify_line(&line, &self.source) {
                 notify_events.push(notify);
             }
+            if let Some(kill) = self.parser.parse_kill_line(&line, &self.source) {
+                kill_events.push(kill);
+            }
+            if let Some(bookmark) = inline_bookmarks::parse_bookmark_line(&line) {
+                if self.recent_markers.len() >= RECENT_MARKERS {
+                    self.recent_markers.pop_front();
+                }
+                self.recent_markers.push_back(bookmark.timestamp_secs);
+                bookmarks.push(bookmark);
+            }
         }
 
         Ok(TrackerReadResult {
             combat_events,
             notify_events,
+            kill_events,
+            bookmarks,
         })
     }
 
+    fn read_lines(&mut self) -> io::Result<Vec<String>> {
+        if self.tailer.reset_if_truncated()? {
+            self.resyncing = true;
+        }
+        self.tailer.read_new_lines()
+    }
+
+    /// Treat a transient read error as "nothing new this tick" so the read is
+    /// retried next tick. Persistent or non-transient errors are returned.
+    fn absorb_read_error(&mut self, error: io::Error) -> io::Result<()> {
+        // Retry on the next tick even if the file looks unchanged
+        self.last_stamp = None;
+        if !is_transient(&error) {
+            return Err(error);
+        }
+        self.transient_failures += 1;
+        if self.transient_failures > MAX_TRANSIENT_FAILURES {
+            return Err(error);
+        }
+        log::debug!(
+            "Transient read error on {:?} ({}/{}): {}",
+            self.path,
+            self.transient_failures,
+            MAX_TRANSIENT_FAILURES,
+            error
+        );
+        Ok(())
+    }
+
+    /// Epoch seconds of the most recent bookmarks in this log, oldest first.
+    pub fn marker_times(&self) -> impl Iterator<Item = u64> + '_ {
+        self.recent_markers.iter().copied()
+    }
+
+    /// Wall-clock time that event timestamps from this log are relative to.
+    /// Unknown until the first timestamped line has been parsed.
+    pub fn base_time(&self) -> Option<NaiveDateTime> {
+        self.parser.get_base_time()
+    }
+
+    /// Current modification time and length of the file, if it can be stat'ed
+    fn file_stamp(&self) -> Option<(SystemTime, u64)> {
+        let metadata = fs::metadata(&self.path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+
+    /// Record a line as read. Returns false for a line that was already counted
+    /// before the file was truncated or replaced.
+    fn remember_line(&mut self, line: &str) -> bool {
+        let mut hasher = DefaultHasher::new();
+        line.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        if self.resyncing {
+            if self.recent_lines.contains(&hash) {
+                return false;
+            }
+            self.resyncing = false;
+        }
+
+        if self.recent_lines.len() == RECENT_LINES {
+            self.recent_lines.pop_front();
+        }
+        self.recent_lines.push_back(hash);
+        true
+    }
+
     pub fn rewind(&mut self) -> io::Result<()> {
+        // A deliberate full re-read (the engine is reset too), so nothing is skipped
+        self.resyncing = false;
+        self.recent_lines.clear();
+        self.last_stamp = None;
         self.tailer.rewind()
     }
 
+    /// Re-open the log file after it disappeared and came back.
+    pub fn reopen(&mut self) -> io::Result<()> {
+        let was_reading = self.tailer.position() > 0;
+        self.last_stamp = None;
+        self.tailer.reopen()?;
+        if was_reading && self.tailer.position() == 0 {
+            self.resyncing = true;
+        }
+        Ok(())
+    }
+
     #[allow(dead_code)]
     pub fn path(&self) -> &Path {
         &self.path
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    const HEADER: &str = "  Session Started: 2025.01.01 12:00:00";
+    const HIT: &str = "[ 2025.01.01 12:01:00 ] (combat) 100 to Enemy - Gun - Hits";
+
+    #[test]
+    fn duplicate_lines_after_truncation_are_not_counted_twice() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("20250101_120000.txt");
+        File::create(&path).unwrap();
+
+        let mut tracker = TrackedGamelog::new("Pilot", &path).unwrap();
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "{}", HEADER).unwrap();
+        writeln!(file, "{}", HIT).unwrap();
+        assert_eq!(tracker.read_new_events().unwrap().combat_events.len(), 1);
+
+        // Truncated and rewritten with the same line (client hiccup/rotation)
+        let mut file = File::create(&path).unwrap();
+        writeln!(file, "{}", HIT).unwrap();
+        assert!(tracker.read_new_events().unwrap().combat_events.is_empty());
+
+        // Genuinely new lines still count, including repeats of the same hit
+        writeln!(
+            file,
+            "[ 2025.01.01 12:01:01 ] (combat) 100 to Enemy - Gun - Hits"
+        )
+        .unwrap();
+        writeln!(
+            file,
+            "[ 2025.01.01 12:01:01 ] (combat) 100 to Enemy - Gun - Hits"
+        )
+        .unwrap();
+        assert_eq!(tracker.read_new_events().unwrap().combat_events.len(), 2);
+    }
+
+    #[test]
+    fn appended_highlight_surfaces_as_marker_time() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("20250101_120000.txt");
+        File::create(&path).unwrap();
+
+        let mut tracker = TrackedGamelog::new("Pilot", &path).unwrap();
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "{}", HEADER).unwrap();
+        writeln!(file, "{}", HIT).unwrap();
+        writeln!(
+            file,
+            "{}",
+            inline_bookmarks::format_bookmark_line(
+                "2025.01.01 12:01:05",
+                "HIGHLIGHT",
+                Some("clutch")
+            )
+        )
+        .unwrap();
+
+        let result = tracker.read_new_events().unwrap();
+        assert_eq!(result.combat_events.len(), 1);
+        assert_eq!(result.bookmarks.len(), 1);
+
+        let expected = NaiveDateTime::parse_from_str("2025.01.01 12:01:05", "%Y.%m.%d %H:%M:%S")
+            .unwrap()
+            .and_utc()
+            .timestamp() as u64;
+        assert_eq!(tracker.marker_times().collect::<Vec<_>>(), vec![expected]);
+    }
+
+    #[test]
+    fn untouched_file_is_not_read_again() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("20250101_120000.txt");
+        File::create(&path).unwrap();
+
+        let mut tracker = TrackedGamelog::new("Pilot", &path).unwrap();
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "{}", HEADER).unwrap();
+        writeln!(file, "{}", HIT).unwrap();
+        assert_eq!(tracker.read_new_events().unwrap().combat_events.len(), 1);
+
+        let position = tracker.tailer.position();
+        let stamp = tracker.last_stamp;
+        assert!(stamp.is_some());
+
+        let result = tracker.read_new_events().unwrap();
+        assert!(result.combat_events.is_empty());
+        assert!(result.notify_events.is_empty());
+        assert_eq!(tracker.tailer.position(), position);
+        assert_eq!(tracker.last_stamp, stamp);
+
+        // An append changes the length, so it's picked up
+        writeln!(file, "{}", HIT).unwrap();
+        assert_eq!(tracker.read_new_events().unwrap().combat_events.len(), 1);
+    }
+
+    #[test]
+    fn transient_read_errors_do_not_stop_tracking() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("20250101_120000.txt");
+        let mut file = File::create(&path).unwrap();
+        writeln!(file, "{}", HEADER).unwrap();
+
+        let mut tracker = TrackedGamelog::new("Pilot", &path).unwrap();
+        assert!(tracker.read_new_events().unwrap().combat_events.is_empty());
+
+        // A flaky share refuses one read
+        let denied = io::Error::from(io::ErrorKind::PermissionDenied);
+        assert!(tracker.absorb_read_error(denied).is_ok());
+
+        writeln!(file, "{}", HIT).unwrap();
+        assert_eq!(tracker.read_new_events().unwrap().combat_events.len(), 1);
+        assert_eq!(tracker.transient_failures, 0);
+
+        // Persistent failures surface once the budget is spent
+        for _ in 0..MAX_TRANSIENT_FAILURES {
+            let blocked = io::Error::from(io::ErrorKind::WouldBlock);
+            assert!(tracker.absorb_read_error(blocked).is_ok());
+        }
+        let blocked = io::Error::from(io::ErrorKind::WouldBlock);
+        assert!(tracker.absorb_read_error(blocked).is_err());
+
+        // Non-transient errors are never swallowed
+        let mut fresh = TrackedGamelog::new("Pilot", &path).unwrap();
+        let missing = io::Error::from(io::ErrorKind::NotFound);
+        assert!(fresh.absorb_read_error(missing).is_err());
+    }
+
+    #[test]
+    fn locale_applies_to_tracked_log() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("20250101_120000.txt");
+        File::create(&path).unwrap();
+
+        let mut tracker = TrackedGamelog::new("Pilot", &path).unwrap();
+        tracker.set_locale(&parser::LocaleConfig {
+            combat_marker: "(Kampf)".to_string(),
+            outgoing: vec!["an".to_string()],
+            incoming: vec!["von".to_string()],
+        });
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "{}", HEADER).unwrap();
+        writeln!(file, "[ 2025.01.01 12:01:00 ] (Kampf) 100 an Enemy - Gun").unwrap();
+
+        let events = tracker.read_new_events().unwrap().combat_events;
+        assert_eq!(events.len(), 1);
+        assert!(!events[0].incoming);
+        assert_eq!(events[0].target, "Enemy");
+    }
+}
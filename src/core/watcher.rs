@@ -1,17 +1,28 @@
 use super::log_io;
-use super::model::{CombatEvent, NotifyEvent};
+use super::model::{CombatEvent, KillEvent, NotifyEvent};
+use super::parser::LocaleConfig;
 use super::tracker::TrackedGamelog;
+use chrono::NaiveDateTime;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 pub struct LogWatcher {
     trackers: HashMap<PathBuf, TrackedGamelog>,
+    /// Tracked files that currently don't exist; reopened once they're back
+    missing: HashSet<PathBuf>,
+    /// Kills read by `read_events`, until collected with `take_kill_events`
+    kills: Vec<KillEvent>,
+    /// Combat tokens every tracker parses with
+    locale: LocaleConfig,
 }
 
 impl LogWatcher {
     pub fn new() -> Self {
         Self {
             trackers: HashMap::new(),
+            missing: HashSet::new(),
+            kills: Vec::new(),
+            locale: LocaleConfig::default(),
         }
     }
 }
@@ -23,17 +34,29 @@ impl Default for LogWatcher {
 }
 
 impl LogWatcher {
+    /// Parse current and future logs with `locale`.
+    pub fn set_locale(&mut self, locale: &LocaleConfig) {
+        if self.locale == *locale {
+            return;
+        }
+        self.locale = locale.clone();
+        for tracker in self.trackers.values_mut() {
+            tracker.set_locale(locale);
+        }
+    }
+
     /// Updates the set of tracked paths.
     /// Returns a list of status messages (e.g., "Started tracking...").
     pub fn update_active_paths(
         &mut self,
         active_paths: &HashSet<PathBuf>,
-        log_dir: &Path,
+        log_dirs: &[PathBuf],
     ) -> Vec<String> {
         let mut messages = Vec::new();
 
         // Remove paths not in active_paths
         self.trackers.retain(|path, _| active_paths.contains(path));
+        self.missing.retain(|path| active_paths.contains(path));
 
         // Find paths that need to be added
         let to_add: Vec<PathBuf> = active_paths
@@ -46,11 +69,12 @@ impl LogWatcher {
             // We only scan if we have something to add.
             // In a real scenario, we might want to cache the scan result,
             // but for now we follow the existing logic: scan when needed.
-            if let Ok(logs) = log_io::scan_gamelogs_dir(log_dir) {
+            if let Ok(logs) = log_io::scan_gamelogs_dirs(log_dirs) {
                 for path in to_add {
                     if let Some(log) = logs.iter().find(|l| l.path == path) {
                         match TrackedGamelog::new(log.character.clone(), path.clone()) {
-                            Ok(tracker) => {
+                            Ok(mut tracker) => {
+                                tracker.set_locale(&self.locale);
                                 messages.push(format!("Started tracking: {}", log.character));
                                 self.trackers.insert(path, tracker);
                             }
@@ -63,7 +87,7 @@ impl LogWatcher {
                     }
                 }
             } else {
-                messages.push(format!("Failed to scan log directory: {:?}", log_dir));
+                messages.push(format!("Failed to scan log directories: {:?}", log_dirs));
             }
         }
 
@@ -77,7 +101,23 @@ impl LogWatcher {
         let mut all_notify_events = Vec::new();
         let mut messages = Vec::new();
 
-        for tracker in self.trackers.values_mut() {
+        for (path, tracker) in self.trackers.iter_mut() {
+            // An open handle to a deleted file just reads nothing, so check the path
+            if !path.exists() {
+                if self.missing.insert(path.clone()) {
+                    messages.push(format!("Log file disappeared: {:?}", path));
+                }
+                continue;
+            }
+            if self.missing.contains(path) {
+                if let Err(e) = tracker.reopen() {
+                    messages.push(format!("Failed to reopen {:?}: {}", path, e));
+                    continue;
+                }
+                self.missing.remove(path);
+                messages.push(format!("Reopened log file: {:?}", path));
+            }
+
             match tracker.read_new_events() {
                 Ok(result) => {
                     if !result.combat_events.is_empty() {
@@ -86,6 +126,7 @@ impl LogWatcher {
                     if !result.notify_events.is_empty() {
                         all_notify_events.extend(result.notify_events);
                     }
+                    self.kills.extend(result.kill_events);
                 }
                 Err(e) => {
                     // Log error but continue
@@ -97,6 +138,49 @@ impl LogWatcher {
         (all_combat_events, all_notify_events, messages)
     }
 
+    /// Kills read since the last call, in read order.
+    pub fn take_kill_events(&mut self) -> Vec<KillEvent> {
+        std::mem::take(&mut self.kills)
+    }
+
+    /// Base time of a tracked log's event timestamps, see `TrackedGamelog::base_time`.
+    pub fn base_time(&self, path: &Path) -> Option<NaiveDateTime> {
+        self.trackers
+            .get(path)
+            .and_then(|tracker| tracker.base_time())
+    }
+
+    /// Character names of all tracked logs, sorted and deduplicated.
+    pub fn character_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .trackers
+            .values()
+            .map(|tracker| tracker.source.clone())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Epoch seconds of recent inline bookmarks across all tracked logs, sorted.
+    pub fn marker_times(&self) -> Vec<u64> {
+        let mut times: Vec<u64> = self
+            .trackers
+            .values()
+            .flat_map(|tracker| tracker.marker_times())
+            .collect();
+        times.sort_unstable();
+        times.dedup();
+        times
+    }
+
+    /// Tracked files that are currently missing, sorted.
+    pub fn missing_paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.missing.iter().cloned().collect();
+        paths.sort();
+        paths
+    }
+
     pub fn rewind_all(&mut self) {
         for tracker in self.trackers.values_mut() {
             let _ = tracker.rewind();
@@ -137,7 +221,7 @@ mod tests {
 
         // 1. Add path
         active_paths.insert(log_path.clone());
-        let msgs = watcher.update_active_paths(&active_paths, dir.path());
+        let msgs = watcher.update_active_paths(&active_paths, &[dir.path().to_path_buf()]);
 
         assert_eq!(msgs.len(), 1);
         assert!(msgs[0].contains("Started tracking: TestChar"));
@@ -164,7 +248,38 @@ mod tests {
 
         // 5. Remove path
         active_paths.clear();
-        let _ = watcher.update_active_paths(&active_paths, dir.path());
+        let _ = watcher.update_active_paths(&active_paths, &[dir.path().to_path_buf()]);
         assert!(!watcher.trackers.contains_key(&log_path));
     }
+
+    #[test]
+    fn test_capacitor_notify_lines_reach_watcher_output() {
+        let dir = tempdir().unwrap();
+        let log_path = dir.path().join("20250101_120000.txt");
+
+        let mut file = File::create(&log_path).unwrap();
+        writeln!(file, "  Gamelog").unwrap();
+        writeln!(file, "  Listener: TestChar").unwrap();
+        writeln!(file, "  Session Started: 2025.01.01 12:00:00").unwrap();
+
+        let mut watcher = LogWatcher::new();
+        let active_paths: HashSet<PathBuf> = [log_path.clone()].into_iter().collect();
+        let _ = watcher.update_active_paths(&active_paths, &[dir.path().to_path_buf()]);
+
+        writeln!(
+            file,
+            "[ 2025.01.01 12:01:00 ] (notify) Small Remote Shield Booster II requires 39.0 units of charge. The capacitor has only 6.2 units."
+        )
+        .unwrap();
+        file.sync_all().unwrap();
+
+        let (combat_events, notify_events, _) = watcher.read_events();
+        assert!(combat_events.is_empty());
+        assert_eq!(notify_events.len(), 1);
+        assert_eq!(notify_events[0].character, "TestChar");
+        assert_eq!(
+            notify_events[0].module_name,
+            "Small Remote Shield Booster II"
+        );
+    }
 }
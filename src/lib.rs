@@ -2,6 +2,8 @@
 // The ultimate strictness: catches things like missing documentation or overflow risks
 #![warn(clippy::restriction)]
 pub mod core;
+pub mod prelude;
 
 pub mod app;
+pub mod cli;
 pub use app::run;
@@ -5,5 +5,9 @@
 #![warn(clippy::restriction)]
 
 fn main() {
+    // Headless commands (e.g. `analyze <path>`) exit before any window is created
+    if let Some(code) = abyss_watcher::cli::try_run(std::env::args()) {
+        std::process::exit(code);
+    }
     abyss_watcher::run();
 }
@@ -0,0 +1,45 @@
+//! Curated re-exports for using the combat engine without the GUI.
+//!
+//! Everything here is kept source-compatible between releases; reach into
+//! `abyss_watcher::core` directly only for things not listed.
+//!
+//! ```
+//! use abyss_watcher::prelude::*;
+//! use std::time::Duration;
+//!
+//! let mut parser = LineParser::new();
+//! parser.parse_line("Session Started: 2024.01.01 12:00:00", "Pilot");
+//! let event = parser
+//!     .parse_line(
+//!         "[ 2024.01.01 12:00:00 ] (combat) 100 to Enemy - Gun - Hits",
+//!         "Pilot",
+//!     )
+//!     .unwrap();
+//!
+//! let series = compute_dps_series(&[event], Duration::from_secs(1), Duration::ZERO);
+//! assert_eq!(series.len(), 1);
+//! assert_eq!(series[0].outgoing_dps, 100.0);
+//! ```
+//!
+//! For live logs, `TrackedGamelog` tails a file and parses new lines, and
+//! `EngineState` keeps the events and computes samples incrementally:
+//!
+//! ```no_run
+//! use abyss_watcher::prelude::*;
+//! use std::time::Duration;
+//!
+//! let mut log = TrackedGamelog::new("Pilot", "20240101_120000_123.txt")?;
+//! let mut engine = EngineState::new();
+//! let read = log.read_new_events()?;
+//! engine.push_events(read.combat_events);
+//! let samples = engine.dps_series(Duration::from_secs(5), Duration::from_secs(60));
+//! # let _ = samples;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+pub use crate::core::analysis::compute_dps_series;
+pub use crate::core::model::{CombatEvent, DpsSample, EventType};
+pub use crate::core::parser::LineParser;
+pub use crate::core::replay_engine::ReplayController;
+pub use crate::core::state::EngineState;
+pub use crate::core::tracker::{TrackedGamelog, TrackerReadResult};
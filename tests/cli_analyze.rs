@@ -0,0 +1,35 @@
+use std::fs;
+use std::time::Duration;
+
+use abyss_watcher::cli;
+use tempfile::tempdir;
+
+#[test]
+fn analyze_prints_peak_and_top_targets() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("20240101_120000_123.txt");
+    fs::write(
+        &path,
+        "Session Started: 2024.01.01 12:00:00\n\
+         [ 2024.01.01 12:00:01 ] (combat) 100 to Enemy - Gun\n\
+         [ 2024.01.01 12:00:02 ] (combat) 100 to Enemy - Gun\n\
+         [ 2024.01.01 12:00:02 ] (combat) 100 to Other - Gun\n",
+    )
+    .unwrap();
+
+    let mut out = Vec::new();
+    cli::analyze(&path, Duration::from_secs(1), &mut out).unwrap();
+    let report = String::from_utf8(out).unwrap();
+
+    assert!(
+        report.contains("Outgoing DPS: peak 300.0"),
+        "unexpected report:\n{}",
+        report
+    );
+    assert!(report.contains("Top targets:\n  Enemy: 200\n  Other: 100"));
+}
+
+#[test]
+fn non_cli_args_start_the_gui() {
+    assert_eq!(cli::try_run(vec!["abyss-watcher".to_string()]), None);
+}
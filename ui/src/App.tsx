@@ -19,6 +19,7 @@ export type { DpsUpdate, CharacterState, CombatAction, TargetHit, Settings, Book
 import WindowFrame from './components/WindowFrame';
 import Tooltip from './components/Tooltip';
 import UpdateChecker from './components/UpdateChecker';
+import { visibleSections } from './display';
 
 function MainApp() {
   const [dpsData, setDpsData] = useState<DpsUpdate | null>(null);
@@ -36,6 +37,9 @@ function MainApp() {
         NeutSensitiveNeuted: { enabled: true, sound: 'Default' },
         CapacitorFailure: { enabled: true, sound: 'Default' },
         LogiNeuted: { enabled: true, sound: 'Default' },
+        EwarApplied: { enabled: true, sound: 'Default' },
+        IncomingDpsSpike: { enabled: true, sound: 'Default' },
+        LowHealthWarning: { enabled: true, sound: 'Default' },
       },
       roles: { logi_characters: [], neut_sensitive_characters: [] },
     },
@@ -75,6 +79,73 @@ function MainApp() {
     };
   }, [showCharacterSelector, showSettings]);
 
+  // Apply the configured theme and damage colors
+  useEffect(() => {
+    const root = document.documentElement;
+    const css = ([r, g, b, a]: [number, number, number, number]) => `rgba(${r}, ${g}, ${b}, ${a / 255})`;
+    root.dataset.theme = (settings.theme ?? 'Dark').toLowerCase();
+    if (settings.accent_color) root.style.setProperty('--color-dps-out', css(settings.accent_color));
+    if (settings.incoming_color) root.style.setProperty('--color-dps-in', css(settings.incoming_color));
+  }, [settings.theme, settings.accent_color, settings.incoming_color]);
+
+  const [focusTarget, setFocusTarget] = useState<string | null>(null);
+  const handleFocusTarget = (target: string) => {
+    const next = target === focusTarget ? null : target;
+    invoke('set_focus_target', { target: next })
+      .then(() => setFocusTarget(next))
+      .catch((err) => console.error('Focus target failed:', err));
+  };
+
+  const toggleLock = () => {
+    invoke<boolean>('toggle_overlay_lock')
+      .then((locked) => setSettings((prev) => ({ ...prev, locked })))
+      .catch((err) => console.error('Toggle overlay lock failed:', err));
+  };
+
+  // Keyboard shortcuts, all in one place:
+  //   W      cycle the DPS window through the presets
+  //   [ / ]  previous / next DPS window preset
+  //   L      lock/unlock the overlay in place
+  //   Space  pause/resume live tracking
+  const trackingPausedRef = useRef(false);
+  useEffect(() => {
+    const setWindow = (command: string, args?: Record<string, unknown>) =>
+      invoke<number>(command, args)
+        .then((seconds) => setSettings((prev) => ({ ...prev, dps_window_seconds: seconds })))
+        .catch((err) => console.error(`${command} failed:`, err));
+
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.ctrlKey || e.metaKey || e.altKey) return;
+      if (e.target instanceof HTMLInputElement || e.target instanceof HTMLTextAreaElement) return;
+      switch (e.key.toLowerCase()) {
+        case 'w':
+          setWindow('cycle_dps_window');
+          break;
+        case '[':
+          setWindow('step_dps_window', { forward: false });
+          break;
+        case ']':
+          setWindow('step_dps_window', { forward: true });
+          break;
+        case 'l':
+          toggleLock();
+          break;
+        case ' ': {
+          e.preventDefault();
+          const paused = !trackingPausedRef.current;
+          invoke('set_tracking_paused', { paused })
+            .then(() => { trackingPausedRef.current = paused; })
+            .catch((err) => console.error('Pause tracking failed:', err));
+          break;
+        }
+        default:
+          return;
+      }
+    };
+    window.addEventListener('keydown', onKeyDown);
+    return () => window.removeEventListener('keydown', onKeyDown);
+  }, []);
+
   useEffect(() => {
     // Load initial data
     const init = async () => {
@@ -115,9 +186,17 @@ function MainApp() {
       }
     });
 
+    // Gamelogs were created or deleted: refresh the character list
+    const unlistenLogs = listen('logs-changed', () => {
+      invoke<CharacterState[]>('get_available_characters')
+        .then(setCharacters)
+        .catch((err) => console.error('Refresh characters failed:', err));
+    });
+
     return () => {
       unlistenDps.then((fn) => fn());
       unlistenAbyssExit.then((fn) => fn());
+      unlistenLogs.then((fn) => fn());
       unlistenAlert.then((fn) => fn());
     };
   }, []);
@@ -139,7 +218,8 @@ function MainApp() {
   const handleSaveSettings = async (newSettings: SettingsWithAlerts) => {
     try {
       await invoke('save_settings', { settings: newSettings });
-      setSettings(newSettings);
+      // The backend may have clamped out-of-range values
+      setSettings(await invoke<SettingsWithAlerts>('get_settings'));
       const loadedChars = await invoke<CharacterState[]>('get_available_characters');
       setCharacters(loadedChars);
       setShowSettings(false);
@@ -265,8 +345,15 @@ function MainApp() {
     </>
   );
 
+  const sections = visibleSections(settings.display_mode);
+
   return (
-    <WindowFrame variant="main" headerActions={headerControls}>
+    <WindowFrame
+      variant="main"
+      headerActions={headerControls}
+      locked={settings.locked ?? false}
+      onToggleLock={toggleLock}
+    >
       <UpdateChecker />
       <div id="app" className="main-overlay">
         {showSettings && (
@@ -291,11 +378,22 @@ function MainApp() {
         )}
 
         <div id="data-container">
-          <StatusBar combatActions={dpsData?.combat_actions_by_character ?? null} />
-          <CombatBreakdown
-            data={dpsData}
-            characters={characters}
-          />
+          {sections.totals && (
+            <StatusBar
+              combatActions={dpsData?.combat_actions_by_character ?? null}
+              numberFormat={settings.number_format}
+            />
+          )}
+          {sections.character_rows && (
+            <CombatBreakdown
+              data={dpsData}
+              characters={characters}
+              showDetails={sections.action_details}
+              focusTarget={focusTarget}
+              onFocusTarget={handleFocusTarget}
+              numberFormat={settings.number_format}
+            />
+          )}
         </div>
       </div>
     </WindowFrame>
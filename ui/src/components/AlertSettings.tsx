@@ -6,6 +6,7 @@
  * - Enable/disable individual alert rules
  */
 import { type FC, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
 import type { AlertEngineConfig, AlertRuleId, CharacterState } from '../types';
 
 // Alert rule metadata for display
@@ -40,6 +41,21 @@ const ALERT_RULES: { id: AlertRuleId; name: string; description: string }[] = [
         name: 'Logi Neuted',
         description: 'Alert when a designated logi character is neuted',
     },
+    {
+        id: 'EwarApplied',
+        name: 'Scrammed / Webbed',
+        description: 'Alert when a tracked character is warp scrambled or webbed',
+    },
+    {
+        id: 'IncomingDpsSpike',
+        name: 'Incoming DPS Spike',
+        description: 'Alert when incoming DPS on a character exceeds a threshold',
+    },
+    {
+        id: 'LowHealthWarning',
+        name: 'Hull Damage',
+        description: 'Alert when a tracked character takes damage to hull',
+    },
 ];
 
 interface AlertSettingsProps {
@@ -100,6 +116,17 @@ const AlertSettings: FC<AlertSettingsProps> = ({ config, trackedCharacters, onCh
         });
     };
 
+    const updateDpsThreshold = (ruleId: AlertRuleId, value: number) => {
+        const current = config.rules[ruleId] || { enabled: false, sound: 'Default', cooldown_seconds: 3, ignore_vorton: true };
+        onChange({
+            ...config,
+            rules: {
+                ...config.rules,
+                [ruleId]: { ...current, dps_threshold: Math.max(0, value) },
+            },
+        });
+    };
+
     const toggleIgnoreVorton = (ruleId: AlertRuleId) => {
         const current = config.rules[ruleId] || { enabled: false, sound: 'Default', cooldown_seconds: 3, ignore_vorton: true };
         onChange({
@@ -111,6 +138,17 @@ const AlertSettings: FC<AlertSettingsProps> = ({ config, trackedCharacters, onCh
         });
     };
 
+    const toggleOnlyInAbyss = (ruleId: AlertRuleId) => {
+        const current = config.rules[ruleId] || { enabled: false, sound: 'Default', cooldown_seconds: 3, ignore_vorton: true };
+        onChange({
+            ...config,
+            rules: {
+                ...config.rules,
+                [ruleId]: { ...current, only_in_abyss: !current.only_in_abyss },
+            },
+        });
+    };
+
     return (
         <div className="alert-settings">
             <div className="section-header" onClick={() => setRulesExpanded(!rulesExpanded)}>
@@ -169,6 +207,8 @@ const AlertSettings: FC<AlertSettingsProps> = ({ config, trackedCharacters, onCh
                             const isEnabled = ruleConfig?.enabled ?? false;
                             const cooldown = ruleConfig?.cooldown_seconds ?? 3;
                             const ignoreVorton = ruleConfig?.ignore_vorton ?? true;
+                            const dpsThreshold = ruleConfig?.dps_threshold ?? 500;
+                            const onlyInAbyss = ruleConfig?.only_in_abyss ?? false;
 
                             return (
                                 <div key={rule.id} className="alert-rule">
@@ -191,9 +231,31 @@ const AlertSettings: FC<AlertSettingsProps> = ({ config, trackedCharacters, onCh
                                                 className="cooldown-input"
                                             />
                                             <span className="cooldown-label">s</span>
+                                            <button
+                                                type="button"
+                                                className="sound-preview-btn"
+                                                title="Test sound"
+                                                onClick={() => invoke('preview_alert_sound', { ruleId: rule.id })
+                                                    .catch(err => console.error('Sound preview failed:', err))}
+                                            >
+                                                ▶
+                                            </button>
                                         </div>
                                     </div>
 
+                                    {isEnabled && (
+                                        <div className="rule-filter">
+                                            <label className="filter-checkbox">
+                                                <input
+                                                    type="checkbox"
+                                                    checked={onlyInAbyss}
+                                                    onChange={() => toggleOnlyInAbyss(rule.id)}
+                                                />
+                                                <span>Only in the Abyss</span>
+                                            </label>
+                                        </div>
+                                    )}
+
                                     {(rule.id === 'FriendlyFire' || rule.id === 'LogiTakingDamage') && isEnabled && (
                                         <div className="rule-filter">
                                             <label className="filter-checkbox">
@@ -206,6 +268,21 @@ const AlertSettings: FC<AlertSettingsProps> = ({ config, trackedCharacters, onCh
                                             </label>
                                         </div>
                                     )}
+
+                                    {rule.id === 'IncomingDpsSpike' && isEnabled && (
+                                        <div className="rule-filter">
+                                            <label className="filter-checkbox">
+                                                <input
+                                                    type="number"
+                                                    min={0}
+                                                    value={dpsThreshold}
+                                                    onChange={(e) => updateDpsThreshold(rule.id, parseInt(e.target.value) || 0)}
+                                                    className="cooldown-input"
+                                                />
+                                                <span>DPS threshold</span>
+                                            </label>
+                                        </div>
+                                    )}
                                 </div>
                             );
                         })}
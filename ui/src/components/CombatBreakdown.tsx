@@ -1,10 +1,18 @@
 import { type FC, useState, useMemo } from 'react';
-import type { CombatAction, DpsUpdate, CharacterState } from '../types';
+import type { CombatAction, DpsUpdate, CharacterState, NumberFormat } from '../types';
+import { formatNumber } from '../format';
 
 interface CombatBreakdownProps {
     data: DpsUpdate | null;
     characters: CharacterState[];
     defaultExpanded?: boolean;
+    /** Show the weapon and target lists under each character (default true) */
+    showDetails?: boolean;
+    /** Target the DPS readout is focused on, if any */
+    focusTarget?: string | null;
+    /** Clicking a target name focuses (or unfocuses) it */
+    onFocusTarget?: (target: string) => void;
+    numberFormat?: NumberFormat;
 }
 
 const getMetricStyle = (type: CombatAction['action_type'], incoming: boolean) => {
@@ -22,9 +30,13 @@ interface CharacterCardProps {
     name: string;
     actions: CombatAction[];
     defaultExpanded?: boolean;
+    showDetails?: boolean;
+    focusTarget?: string | null;
+    onFocusTarget?: (target: string) => void;
+    numberFormat?: NumberFormat;
 }
 
-const CharacterCard: FC<CharacterCardProps> = ({ name, actions, defaultExpanded = false }) => {
+const CharacterCard: FC<CharacterCardProps> = ({ name, actions, defaultExpanded = false, showDetails = true, focusTarget, onFocusTarget, numberFormat }) => {
     const [isCollapsed, setIsCollapsed] = useState(!defaultExpanded);
     const [collapsedGroups, setCollapsedGroups] = useState<Set<string>>(new Set());
 
@@ -81,6 +93,16 @@ const CharacterCard: FC<CharacterCardProps> = ({ name, actions, defaultExpanded
         });
     };
 
+    const focusProps = (target: string) => onFocusTarget ? {
+        className: target === focusTarget ? 'focused' : undefined,
+        style: { cursor: 'pointer' },
+        title: target === focusTarget ? 'Show all targets' : 'Focus DPS on this target',
+        onClick: (e: React.MouseEvent) => {
+            e.stopPropagation();
+            onFocusTarget(target);
+        },
+    } : {};
+
     const renderStatPair = (outVal: number, inVal: number, type: CombatAction['action_type']) => {
         if (outVal <= 0 && inVal <= 0) return null;
         const outStyle = getMetricStyle(type, false);
@@ -88,11 +110,11 @@ const CharacterCard: FC<CharacterCardProps> = ({ name, actions, defaultExpanded
         return (
             <div className="metric-pair" key={type}>
                 <span className={`val-out ${outVal > 0 ? outStyle.class : 'text-dim'}`} style={outVal <= 0 ? { opacity: 0.3 } : {}}>
-                    {outVal.toFixed(0)}
+                    {formatNumber(outVal, numberFormat)}
                 </span>
                 <span className="val-divider">/</span>
                 <span className={`val-in ${inVal > 0 ? inStyle.class : 'text-dim'}`} style={inVal <= 0 ? { opacity: 0.3 } : {}}>
-                    {inVal.toFixed(0)}
+                    {formatNumber(inVal, numberFormat)}
                 </span>
             </div>
         );
@@ -109,7 +131,7 @@ const CharacterCard: FC<CharacterCardProps> = ({ name, actions, defaultExpanded
         <div className="char-strip-container">
             <div className="char-strip" onClick={() => setIsCollapsed(!isCollapsed)}>
                 <div className="char-info">
-                    <span className="collapse-indicator">{isCollapsed ? '▶' : '▼'}</span>
+                    {showDetails && <span className="collapse-indicator">{isCollapsed ? '▶' : '▼'}</span>}
                     <span className="char-name">{name}</span>
                 </div>
                 <div className="metric-container">
@@ -117,7 +139,7 @@ const CharacterCard: FC<CharacterCardProps> = ({ name, actions, defaultExpanded
                 </div>
             </div>
 
-            {!isCollapsed && (
+            {showDetails && !isCollapsed && (
                 <div className="char-content">
                     {Object.entries(groups).map(([type, items]) => {
                         if (items.length === 0) return null;
@@ -150,7 +172,7 @@ const CharacterCard: FC<CharacterCardProps> = ({ name, actions, defaultExpanded
                                                                 <span>{act.name}</span>
                                                             </div>
                                                             <div className={`action-value ${style.class}`}>
-                                                                {act.value.toLocaleString(undefined, { minimumFractionDigits: 0, maximumFractionDigits: 1 })}
+                                                                {formatNumber(act.value, numberFormat)}
                                                                 <span className="action-unit">{style.label}</span>
                                                             </div>
                                                         </div>
@@ -159,9 +181,11 @@ const CharacterCard: FC<CharacterCardProps> = ({ name, actions, defaultExpanded
                                                                 {act.targets.map((t, ti) => (
                                                                     <div className="action-row-sub" key={ti}>
                                                                         <span className="target-arrow">→</span>
-                                                                        <span className="target-name">{t.target}</span>
+                                                                        <span className="target-name">
+                                                                            <span {...focusProps(t.target)}>{t.target}</span>
+                                                                        </span>
                                                                         <span className={`target-value ${style.class}`}>
-                                                                            {t.value.toLocaleString(undefined, { minimumFractionDigits: 0, maximumFractionDigits: 1 })}
+                                                                            {formatNumber(t.value, numberFormat)}
                                                                         </span>
                                                                     </div>
                                                                 ))}
@@ -178,11 +202,13 @@ const CharacterCard: FC<CharacterCardProps> = ({ name, actions, defaultExpanded
                                                         <span className="dir-icon">{icon}</span>
                                                         <span>{act.name}</span>
                                                         {singleTarget && (
-                                                            <span className="inline-target">→ {singleTarget.target}</span>
+                                                            <span className="inline-target">
+                                                                → <span {...focusProps(singleTarget.target)}>{singleTarget.target}</span>
+                                                            </span>
                                                         )}
                                                     </div>
                                                     <div className={`action-value ${style.class}`}>
-                                                        {act.value.toLocaleString(undefined, { minimumFractionDigits: 0, maximumFractionDigits: 1 })}
+                                                        {formatNumber(act.value, numberFormat)}
                                                         <span className="action-unit">{style.label}</span>
                                                     </div>
                                                 </div>
@@ -199,7 +225,7 @@ const CharacterCard: FC<CharacterCardProps> = ({ name, actions, defaultExpanded
     );
 };
 
-const CombatBreakdown: FC<CombatBreakdownProps> = ({ data, characters, defaultExpanded = false }) => {
+const CombatBreakdown: FC<CombatBreakdownProps> = ({ data, characters, defaultExpanded = false, showDetails = true, focusTarget, onFocusTarget, numberFormat }) => {
     const activeData = useMemo(() => {
         const map = new Map<string, CombatAction[]>(
             Object.entries(data?.combat_actions_by_character || {})
@@ -216,7 +242,16 @@ const CombatBreakdown: FC<CombatBreakdownProps> = ({ data, characters, defaultEx
     return (
         <div id="combat-breakdown">
             {activeData.map(([name, actions]) => (
-                <CharacterCard key={name} name={name} actions={actions} defaultExpanded={defaultExpanded} />
+                <CharacterCard
+                    key={name}
+                    name={name}
+                    actions={actions}
+                    defaultExpanded={defaultExpanded}
+                    showDetails={showDetails}
+                    focusTarget={focusTarget}
+                    onFocusTarget={onFocusTarget}
+                    numberFormat={numberFormat}
+                />
             ))}
         </div>
     );
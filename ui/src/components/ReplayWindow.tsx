@@ -40,8 +40,8 @@ function ReplayWindow() {
     useEffect(() => {
         const init = async () => {
             try {
-                const settings = await invoke<{ gamelog_dir: string }>('get_settings');
-                const logs = await invoke<Record<string, { path: string }[]>>('get_logs_by_character', { path: settings.gamelog_dir });
+                // No path: the backend merges every configured gamelog dir
+                const logs = await invoke<Record<string, { path: string }[]>>('get_logs_by_character', {});
                 setAvailableLogs(logs);
             } catch (e) {
                 console.error('Failed to load logs:', e);
@@ -93,8 +93,7 @@ function ReplayWindow() {
 
     const handleStartReplay = async () => {
         try {
-            const settings = await invoke<{ gamelog_dir: string }>('get_settings');
-            const logs = await invoke<Record<string, { path: string }[]>>('get_logs_by_character', { path: settings.gamelog_dir });
+            const logs = await invoke<Record<string, { path: string }[]>>('get_logs_by_character', {});
             setAvailableLogs(logs);
 
             const selection: [string, string][] = [];
@@ -111,7 +110,12 @@ function ReplayWindow() {
                 return;
             }
 
-            const info = await invoke<ReplaySessionInfo>('start_replay', { logs: selection });
+            // A running replay swaps its logs and keeps its play state and speed
+            const replacing = sessionStartTime > 0;
+            const info = await invoke<ReplaySessionInfo>(
+                replacing ? 'replace_replay_logs' : 'start_replay',
+                { logs: selection },
+            );
             setStatus(p => ({ ...p, duration: info.duration }));
             setSessionStartTime(info.start_time);
 
@@ -124,7 +128,9 @@ function ReplayWindow() {
             })));
             setDpsData(null); // Clear old stats
 
-            setIsPlaying(true); // Auto-play enabled
+            if (!replacing) {
+                setIsPlaying(true); // Auto-play enabled
+            }
             setShowLogs(false);
 
             if (selection.length > 0) {
@@ -203,6 +209,7 @@ function ReplayWindow() {
     const handleStopReplay = () => {
         invoke('stop_replay').catch(console.error);
         setIsPlaying(false);
+        setSessionStartTime(0);
         setStatus(p => ({ ...p, progress: 0, current_time: 0 }));
     };
 
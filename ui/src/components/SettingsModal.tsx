@@ -1,6 +1,6 @@
 import { type FC, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import type { SettingsWithAlerts, AlertEngineConfig, CharacterState } from '../types';
+import type { SettingsWithAlerts, AlertEngineConfig, CharacterState, DisplayMode, NumberFormat } from '../types';
 import AlertSettings from './AlertSettings';
 
 interface SettingsModalProps {
@@ -20,6 +20,8 @@ const SettingsModal: FC<SettingsModalProps> = ({
 }) => {
     const [logDir, setLogDir] = useState(settings.gamelog_dir);
     const [dpsWindow, setDpsWindow] = useState(settings.dps_window_seconds);
+    const [numberFormat, setNumberFormat] = useState<NumberFormat>(settings.number_format ?? 'Plain');
+    const [displayMode, setDisplayMode] = useState<DisplayMode>(settings.display_mode ?? 'Full');
     const [alertConfig, setAlertConfig] = useState<AlertEngineConfig>(settings.alert_settings);
 
     const handleBrowse = async () => {
@@ -33,8 +35,11 @@ const SettingsModal: FC<SettingsModalProps> = ({
 
     const handleSave = () => {
         onSave({
+            ...settings,
             gamelog_dir: logDir,
             dps_window_seconds: dpsWindow,
+            number_format: numberFormat,
+            display_mode: displayMode,
             alert_settings: alertConfig,
         });
     };
@@ -59,6 +64,30 @@ const SettingsModal: FC<SettingsModalProps> = ({
                 />
             </div>
 
+            <div className="form-group">
+                <label>Number Format</label>
+                <select
+                    value={numberFormat}
+                    onChange={(e) => setNumberFormat(e.target.value as NumberFormat)}
+                >
+                    <option value="Plain">1234567.8</option>
+                    <option value="Thousands">1,234,567.8</option>
+                    <option value="Suffixed">1.23M</option>
+                </select>
+            </div>
+
+            <div className="form-group">
+                <label>Display Mode</label>
+                <select
+                    value={displayMode}
+                    onChange={(e) => setDisplayMode(e.target.value as DisplayMode)}
+                >
+                    <option value="Full">Full</option>
+                    <option value="Compact">Compact</option>
+                    <option value="NumbersOnly">Numbers only</option>
+                </select>
+            </div>
+
             {/* Alert Settings */}
             <AlertSettings
                 config={alertConfig}
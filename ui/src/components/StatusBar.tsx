@@ -1,8 +1,10 @@
 import { type FC, useMemo } from 'react';
-import type { CombatAction } from '../types';
+import type { CombatAction, NumberFormat } from '../types';
+import { formatNumber } from '../format';
 
 interface StatusBarProps {
     combatActions: Record<string, CombatAction[]> | null;
+    numberFormat?: NumberFormat;
 }
 
 interface StatPairProps {
@@ -11,18 +13,19 @@ interface StatPairProps {
     inValue: number;
     outClass: string;
     inClass: string;
+    numberFormat?: NumberFormat;
 }
 
-const StatItem: FC<StatPairProps> = ({ label, outValue, inValue, outClass, inClass }) => (
+const StatItem: FC<StatPairProps> = ({ label, outValue, inValue, outClass, inClass, numberFormat }) => (
     <div className="stat-item">
         <span className="stat-label">{label}</span>
         <div className="stat-values">
             <span className={`val-out ${outClass}`}>
-                {outValue.toLocaleString(undefined, { minimumFractionDigits: 0, maximumFractionDigits: 1 })}
+                {formatNumber(outValue, numberFormat)}
             </span>
             <span className="val-divider">/</span>
             <span className={`val-in ${inClass}`}>
-                {inValue.toLocaleString(undefined, { minimumFractionDigits: 0, maximumFractionDigits: 1 })}
+                {formatNumber(inValue, numberFormat)}
             </span>
         </div>
     </div>
@@ -32,7 +35,7 @@ const StatItem: FC<StatPairProps> = ({ label, outValue, inValue, outClass, inCla
  * StatusBar computes its totals by summing all character combat actions.
  * This ensures the top-line totals are always exactly the sum of character breakdowns.
  */
-const StatusBar: FC<StatusBarProps> = ({ combatActions }) => {
+const StatusBar: FC<StatusBarProps> = ({ combatActions, numberFormat }) => {
     const totals = useMemo(() => {
         const result = {
             out: { dps: 0, hps: 0, cap: 0, neut: 0 },
@@ -63,6 +66,7 @@ const StatusBar: FC<StatusBarProps> = ({ combatActions }) => {
                 inValue={totals.in.dps}
                 outClass="text-dps-out"
                 inClass="text-dps-in"
+                numberFormat={numberFormat}
             />
             <div className="strip-divider" />
             <StatItem
@@ -71,6 +75,7 @@ const StatusBar: FC<StatusBarProps> = ({ combatActions }) => {
                 inValue={totals.in.hps}
                 outClass="text-rep-out"
                 inClass="text-rep-in"
+                numberFormat={numberFormat}
             />
             <div className="strip-divider" />
             <StatItem
@@ -79,6 +84,7 @@ const StatusBar: FC<StatusBarProps> = ({ combatActions }) => {
                 inValue={totals.in.cap}
                 outClass="text-cap-out"
                 inClass="text-cap-in"
+                numberFormat={numberFormat}
             />
             <div className="strip-divider" />
             <StatItem
@@ -87,6 +93,7 @@ const StatusBar: FC<StatusBarProps> = ({ combatActions }) => {
                 inValue={totals.in.neut}
                 outClass="text-neut-out"
                 inClass="text-neut-in"
+                numberFormat={numberFormat}
             />
         </div>
     );
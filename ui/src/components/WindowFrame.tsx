@@ -1,5 +1,6 @@
 import { type FC, type ReactNode } from 'react';
 import { getCurrentWindow } from '@tauri-apps/api/window';
+import { invoke } from '@tauri-apps/api/core';
 import '../styles/window.css';
 
 interface WindowFrameProps {
@@ -8,12 +9,16 @@ interface WindowFrameProps {
     variant: 'main' | 'replay';
     /* Actions to render in the title bar (e.g. settings buttons) */
     headerActions?: ReactNode;
+    /* Locked overlays can't be dragged or resized */
+    locked?: boolean;
+    onToggleLock?: () => void;
 }
 
-const WindowFrame: FC<WindowFrameProps> = ({ title = "AbyssWatcher", children, variant, headerActions }) => {
+const WindowFrame: FC<WindowFrameProps> = ({ title = "AbyssWatcher", children, variant, headerActions, locked = false, onToggleLock }) => {
     const appWindow = getCurrentWindow();
 
     const startResize = (direction: string) => {
+        if (locked) return;
         // @ts-ignore - Valid tauri v2 call
         appWindow.startResizeDragging(direction);
     };
@@ -25,7 +30,8 @@ const WindowFrame: FC<WindowFrameProps> = ({ title = "AbyssWatcher", children, v
     const handleDrag = (e: React.MouseEvent) => {
         // Only drag on left click
         if (e.button === 0) {
-            appWindow.startDragging();
+            // The backend ignores the drag while the overlay is locked
+            invoke('start_window_drag').catch((err) => console.error('Drag failed:', err));
         }
     };
 
@@ -56,6 +62,16 @@ const WindowFrame: FC<WindowFrameProps> = ({ title = "AbyssWatcher", children, v
                         </div>
                     )}
 
+                    {onToggleLock && (
+                        <button
+                            className={`sys-btn ${locked ? 'active' : ''}`}
+                            onClick={onToggleLock}
+                            title={locked ? 'Unlock overlay (L)' : 'Lock overlay (L)'}
+                        >
+                            {locked ? '🔒' : '🔓'}
+                        </button>
+                    )}
+
                     {/* Window System Buttons */}
                     <button className="sys-btn" onClick={handleMinimize} title="Minimize">─</button>
                     {variant === 'replay' && (
@@ -0,0 +1,13 @@
+import type { DisplayMode, Sections } from './types';
+
+/** Parts of the overlay shown in `mode`; mirrors config::visible_sections */
+export function visibleSections(mode: DisplayMode = 'Full'): Sections {
+    switch (mode) {
+        case 'Compact':
+            return { totals: true, character_rows: true, action_details: false };
+        case 'NumbersOnly':
+            return { totals: true, character_rows: false, action_details: false };
+        default:
+            return { totals: true, character_rows: true, action_details: true };
+    }
+}
@@ -0,0 +1,35 @@
+import type { NumberFormat } from './types';
+
+/** `value.toFixed(digits)`, except exact ties round to even like Rust's `{:.N}` */
+function toFixedEven(value: number, digits: number): string {
+    const abs = Math.abs(value);
+    const scaled = abs * 10 ** digits;
+    const floor = Math.floor(scaled);
+    // A decimal tie is only exact when the value is a short binary fraction
+    const exactTie = scaled - floor === 0.5 && Number.isInteger(abs * 2 ** (digits + 1));
+    if (!exactTie || floor % 2 === 1) return value.toFixed(digits);
+    const rounded = (floor / 10 ** digits).toFixed(digits);
+    return value < 0 ? `-${rounded}` : rounded;
+}
+
+/** Format a DPS or damage value; mirrors model::format_number */
+export function formatNumber(value: number, style: NumberFormat = 'Plain'): string {
+    switch (style) {
+        case 'Thousands': {
+            const formatted = toFixedEven(Math.abs(value), 1);
+            const [intPart, fracPart] = formatted.split('.');
+            const grouped = intPart.replace(/\B(?=(\d{3})+(?!\d))/g, ',');
+            const sign = value < 0 && formatted !== '0.0' ? '-' : '';
+            return `${sign}${grouped}.${fracPart}`;
+        }
+        case 'Suffixed': {
+            const abs = Math.abs(value);
+            // 999_999 would round to "1000k"; show it as "1M" instead
+            if (abs >= 999_995) return `${parseFloat(toFixedEven(value / 1_000_000, 2))}M`;
+            if (abs >= 1_000) return `${parseFloat(toFixedEven(value / 1_000, 2))}k`;
+            return toFixedEven(value, 1);
+        }
+        default:
+            return toFixedEven(value, 1);
+    }
+}
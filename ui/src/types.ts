@@ -24,9 +24,40 @@ export interface CombatAction {
     targets: TargetHit[];
 }
 
+/** Combat stats for a closed Abyss run ('run-summary' event payload) */
+export interface RunSummary {
+    character: string;
+    duration_secs: number;
+    total_outgoing: number;
+    total_incoming: number;
+    peak_dps: number;
+    /** Most damaged targets, highest first */
+    top_targets: TargetHit[];
+}
+
+/** Missing log directory or file ('tracking-errors' event payload items) */
+export type TrackingError =
+    | { LogDirMissing: string }
+    | { LogFileMissing: string };
+
+/** Epoch seconds of recent inline bookmarks ('session-markers' event payload) */
+export type SessionMarkers = number[];
+
+/** Outgoing/incoming damage ratio, banded for color-coding */
+export interface SurvivabilityScore {
+    /** null when no damage was received */
+    ratio: number | null;
+    band: 'Safe' | 'Tense' | 'Dangerous';
+}
+
 /** DPS update payload from backend event */
 export interface DpsUpdate {
     combat_actions_by_character: Record<string, CombatAction[]>;
+    /** Incoming DPS from attackers classified as NPCs / players */
+    incoming_npc_dps: number;
+    incoming_player_dps: number;
+    /** Incoming DPS from Abyss hazards, kept out of the attacker breakdown */
+    incoming_environmental_dps: number;
 }
 
 // ============================================
@@ -43,9 +74,125 @@ export interface CharacterState {
 /** Application settings */
 export interface Settings {
     gamelog_dir: string;
+    /** All gamelog dirs to scan; the first always mirrors gamelog_dir */
+    gamelog_dirs?: string[];
     dps_window_seconds: number;
+    /** Local location names (substring match) that signal being inside the Abyss */
+    abyss_location_markers?: string[];
+    /** Time zone for displayed timestamps; Fixed is minutes east of UTC */
+    display_timezone?: TzMode;
+    /** How DPS and damage totals are written (default 'Plain') */
+    number_format?: NumberFormat;
+    /** Per-character chart colors as [r, g, b, a] */
+    character_colors?: Record<string, [number, number, number, number]>;
+    /** Clear peak DPS when a character enters the Abyss (default true) */
+    reset_peaks_on_run_start?: boolean;
+    /** Stream combat events and DPS samples as JSON lines to this file ("-" for stdout) */
+    jsonl_sink?: string | null;
+    /** Ignore combat where no tracked character is the source or target */
+    tracked_only_combat?: boolean;
+    /** How much DPS history the charts show, in seconds (default 120) */
+    chart_history_seconds?: number;
+    /** Which damage directions the charts plot (default both) */
+    chart_lines?: ChartLines;
+    /** Force attacker names to be counted as NPC or player damage */
+    attacker_overrides?: Record<string, 'Npc' | 'Player'>;
+    /** On startup, track the most recently active character if nothing is tracked */
+    auto_track_latest?: boolean;
+    /** RGBA color for outgoing damage and highlights */
+    accent_color?: [number, number, number, number];
+    /** RGBA color for incoming damage and danger readouts */
+    incoming_color?: [number, number, number, number];
+    theme?: 'Light' | 'Dark';
+    /** DPS window lengths (seconds) cycled by cycle_dps_window (default [3, 5, 10, 30]) */
+    window_presets?: number[];
+    /** Seconds after the last combat event before the DPS clock stops; 0 never stops (default 15) */
+    idle_timeout_seconds?: number;
+    /** Incoming damage sources treated as Abyss hazards (default ["Unstable Abyssal Depths"]) */
+    environmental_sources?: string[];
+    /** Overlay is locked in place: no dragging or resizing */
+    locked?: boolean;
+    display_mode?: DisplayMode;
+    /** Raw character name -> pilot whose stats it is reported under */
+    character_aliases?: Record<string, string>;
+    /** Combat tokens of the game client's language (default English) */
+    locale?: LocaleConfig;
+}
+
+/** Combat marker and damage direction words of a client language */
+export interface LocaleConfig {
+    /** e.g. "(combat)" */
+    combat_marker: string;
+    /** e.g. ["to", "against"] */
+    outgoing: string[];
+    /** e.g. ["from"] */
+    incoming: string[];
+}
+
+/** One tracked character's current state (get_fleet_status) */
+export interface CharacterStatus {
+    character: string;
+    outgoing_dps: number;
+    incoming_dps: number;
+    in_abyss: boolean;
+    /** Newest combat event time (serialized std::time::Duration), null before any combat */
+    last_seen: { secs: number; nanos: number } | null;
+}
+
+/** How much of a gamelog the parser understood (diagnose_log) */
+export interface LogDiagnostics {
+    header_found: boolean;
+    character: string | null;
+    /** Serialized std::time::SystemTime */
+    session_start: { secs_since_epoch: number; nanos_since_epoch: number } | null;
+    encoding: 'Utf8' | 'Utf16Le';
+    total_lines: number;
+    combat_events: number;
+    notify_events: number;
+    /** (combat) misses; they carry no damage and aren't turned into events */
+    miss_lines: number;
+    /** (combat) lines the parser couldn't make sense of */
+    dropped_lines: number;
+    /** The first few dropped lines, verbatim */
+    dropped_sample: string[];
+}
+
+/** Abyss run state of a tracked character (get_run_status) */
+export interface RunStatus {
+    in_abyss: boolean;
+    /** Run start, seconds since the Unix epoch */
+    entered_at_secs: number | null;
+    elapsed_secs: number;
+}
+
+/** Peak DPS since the last reset ('dps-peaks' event payload) */
+export interface DpsPeaks {
+    outgoing: number;
+    incoming: number;
 }
 
+/** Number style for DPS and totals (mirrors model::NumberFormat) */
+export type NumberFormat = 'Plain' | 'Thousands' | 'Suffixed';
+
+/** Which damage directions the charts plot */
+export interface ChartLines {
+    show_outgoing: boolean;
+    show_incoming: boolean;
+}
+
+/** How much of the overlay is shown (default 'Full') */
+export type DisplayMode = 'Full' | 'Compact' | 'NumbersOnly';
+
+/** Parts of the overlay a display mode shows (config::Sections) */
+export interface Sections {
+    totals: boolean;
+    character_rows: boolean;
+    action_details: boolean;
+}
+
+/** Display time zone (mirrors model::TzMode) */
+export type TzMode = 'Utc' | 'Local' | { Fixed: number };
+
 // ============================================
 // Bookmark Types (mirror src/core/inline_bookmarks.rs)
 // ============================================
@@ -60,10 +207,51 @@ export type RoomMarkerState = 'Idle' | 'InRoom';
 /** A bookmark parsed from the gamelog file */
 export interface Bookmark {
     timestamp_secs: number;
+    /** timestamp_secs formatted in the display time zone */
+    display_time: string;
     bookmark_type: string;
     label?: string;
 }
 
+/** A bookmark crossed during replay (payload of `replay-bookmarks`) */
+export interface ReplayBookmark {
+    /** Offset on the replay timeline (serialized std::time::Duration) */
+    time: { secs: number; nanos: number };
+    /** Absolute UTC time since the Unix epoch (serialized std::time::Duration) */
+    absolute: { secs: number; nanos: number };
+    kind: 'RunStart' | 'RunEnd' | 'RoomStart' | 'RoomEnd' | 'Highlight';
+    label?: string | null;
+}
+
+/** A run recorded by RUN_START/RUN_END bookmarks (list_runs); load_run(gamelog_path, index) returns its Bookmarks */
+export interface StoredRun {
+    gamelog_path: string;
+    /** Position of the run within its gamelog */
+    index: number;
+    start_secs: number;
+    /** null when the run has no RUN_END */
+    end_secs: number | null;
+    duration_secs: number | null;
+    /** start_secs formatted in the display time zone */
+    display_time: string;
+    /** System the run started from, if recorded */
+    origin: string | null;
+    /** Rooms and highlights placed during the run */
+    bookmark_count: number;
+}
+
+/** Combined stats over selected runs (aggregate_runs result) */
+export interface MultiRunStats {
+    run_count: number;
+    /** Summed run durations, in seconds */
+    combat_secs: number;
+    /** Mean of the runs' outgoing DPS */
+    average_dps: number;
+    best_dps: number;
+    /** Highest damage first */
+    weapon_totals: { weapon: string; damage: number }[];
+}
+
 /** Room marker toggle response (simplified) */
 export interface RoomMarkerResponse {
     room_open: boolean;
@@ -80,7 +268,10 @@ export type AlertRuleId =
     | 'LogiTakingDamage'
     | 'NeutSensitiveNeuted'
     | 'CapacitorFailure'
-    | 'LogiNeuted';
+    | 'LogiNeuted'
+    | 'EwarApplied'
+    | 'IncomingDpsSpike'
+    | 'LowHealthWarning';
 
 /** Sound options for alerts */
 export type AlertSound = 'Default' | 'Warning' | 'Critical' | 'None';
@@ -91,8 +282,14 @@ export interface AlertRuleConfig {
     sound: AlertSound;
     /** Per-rule cooldown in seconds (default: 3) */
     cooldown_seconds?: number;
+    /** Cooldown per affected character, or one for the whole rule (default) */
+    cooldown_scope?: 'RulePerSubject' | 'RuleGlobal';
     /** For FriendlyFire: ignore damage from Vorton weapons */
     ignore_vorton?: boolean;
+    /** For IncomingDpsSpike: incoming DPS that fires the alert (default: 500) */
+    dps_threshold?: number;
+    /** Only fire while the affected character is in the Abyss */
+    only_in_abyss?: boolean;
 }
 
 /** Character role designations */
@@ -105,6 +302,8 @@ export interface CharacterRoles {
 export interface AlertEngineConfig {
     rules: Record<AlertRuleId, AlertRuleConfig>;
     roles: CharacterRoles;
+    /** Minimum cooldown (seconds) applied on top of every rule's own cooldown */
+    min_cooldown_seconds?: number;
 }
 
 /** Alert event from backend */
@@ -118,3 +317,10 @@ export interface AlertEvent {
 export interface SettingsWithAlerts extends Settings {
     alert_settings: AlertEngineConfig;
 }
+
+/** Recent backend diagnostic line (get_backend_logs items) */
+export interface LogLine {
+    /** Wall-clock time in ms since the Unix epoch */
+    timestamp_ms: number;
+    message: string;
+}